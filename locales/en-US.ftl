@@ -0,0 +1,297 @@
+error-title = Hmm, something went wrong. If it keeps happening, please create a diagnostic report and send it to the developer.
+loading = Loading...
+
+## Side bar
+my-collections = My Collections
+sidebar-home = Home
+sidebar-explore = Explore
+sidebar-library = Library
+sidebar-installing-mods = Mods - Installing
+sidebar-downloads = Downloads
+sidebar-guide = Guide
+sidebar-settings = Settings
+
+## Main page
+main-playing-created-by-me = Playing • Created by me
+main-suggested-action = Suggested action
+main-quick-setup-hint = You haven't finished the quick setup yet, we suggest doing it soon
+main-need-help = Need help?
+main-need-help-hint = Read the manual and tutorials
+main-explore = Explore content
+main-explore-hint = Start exploring community content for Minecraft
+main-creation-center = Creation center
+main-creation-center-hint = Build your own collection
+main-personalize = Personalize your collections
+main-personalize-hint = Decorate your collections with personalization
+main-update-ready-hint = A launcher update is ready
+main-suggest-quick-setup = Suggested: Quick setup
+main-suggest-update = Suggested: Update reminder
+main-manual = Manual
+main-personal-collections = Personal collections
+main-hero-line-1 = Explore  Create
+main-hero-line-2 = Endless  Boundless
+main-hero-line-3 = Create  Possibilities
+main-my-collections-hint = All your favourite collections are here
+
+## Collections
+collections-choose-folder = Choose folder
+collections-search = Search collections
+collection-menu-launch = Launch game
+collection-menu-change-folder = Change folder
+collection-menu-details = View details
+collection-menu-personalize = Personalization
+collection-menu-browse-folder = Browse local folder
+collection-menu-export = Export/share collection
+collection-menu-duplicate = Duplicate collection
+collection-menu-delete = Delete collection
+
+## Collection display
+collection-search = Search in this collection
+collection-created-by-me = Created by me
+collection-tab-mods = Mods
+collection-tab-worlds = Worlds
+collection-tab-resource-packs = Resource packs
+collection-tab-shader-packs = Shader packs
+collection-tab-screenshots = Screenshots
+screenshots-file-name = File name
+screenshots-details = (Date taken/File size/Resolution)
+screenshots-sort-date = Date
+screenshots-sort-size = Size
+screenshots-sort-name = Name
+screenshots-copy = Copy
+screenshots-reveal = Show in folder
+screenshots-delete = Delete
+screenshots-delete-prompt = Delete { $name }?
+screenshots-delete-cancel = Cancel
+screenshots-delete-confirm = Delete permanently
+
+## Mods
+mod-column-icon = Icon
+mod-column-name = Name (source / file name)
+mod-column-author = Author
+mod-column-platform = Platform
+mod-column-last-updated = Last updated
+mod-column-file-size = File size
+mod-column-update = Update
+mod-column-delete = Delete
+mod-column-more = More
+mod-column-status = Status
+mod-filter-all = All
+mod-filter-enabled = Enabled
+mod-filter-disabled = Disabled
+mod-table-columns = Columns
+mod-side-both = Client & Server
+mod-side-client = Client
+mod-side-server = Server
+mod-side-unknown = Unknown
+mod-versions-summary = { $count } versions · latest { $latest }
+mod-switch-source = Use the { $platform } file
+mod-translate = Translate
+mod-show-original = Show original
+mod-pin = Pin version
+mod-pinned = Version pinned
+mod-unmanaged = Unmanaged
+mod-unmanaged-lookup = Look up { $count } unmanaged mods on Modrinth and CurseForge
+mod-not-on-platform = This mod is not on { $platform }, showing the installed version instead
+mod-version-history = Version history
+mod-no-changelog = No changelog
+mod-current-version = Current
+mod-incompatible = Incompatible
+mod-install = Install
+
+## Mod profiles
+profile-all-mods = All mods
+profile-delete = Delete
+profile-new-name = New profile name
+profile-save = Save
+
+## Downloads
+download-total = Total { $total } / Downloaded { $downloaded }
+download-running = In progress
+download-finished = Finished
+download-queued = Queued
+download-paused = Paused
+download-failed = Failed: { $error }
+download-pause = Pause
+download-resume = Resume
+download-cancel = Cancel
+download-concurrency = Concurrent downloads
+download-bandwidth-limit = Limit (KB/s)
+download-unlimited = Unlimited
+download-average-speed = Average { $speed }/s
+download-retry = Retry
+download-retry-unavailable = Install it again instead, what followed the download can't be redone from here
+download-remove = Remove
+download-history-empty = Nothing has finished downloading yet
+download-history-clear = Clear history
+download-eta = { $time } left
+download-failures = { $count ->
+    [one] 1 file failed
+   *[other] { $count } files failed
+}
+download-category-assets = Game assets
+download-category-libraries = Libraries
+download-category-loader = Mod loader
+download-category-mods = Mods
+download-category-other = Other files
+
+## Collection builder
+builder-title = Create collection
+builder-subtitle = Build your collection from scratch
+builder-cover = Cover and background image
+builder-name = Collection name
+builder-default-name = New collection
+builder-untitled = New collection
+builder-game-version = Game version
+builder-show-snapshots = Show snapshots
+builder-memory = Allocated memory
+builder-advanced = Advanced options
+builder-advanced-toggle = More
+builder-mod-loader = Mod loader
+builder-cancel = Cancel
+builder-finish = Done
+
+## Collection edit
+edit-personalization = Personalization
+edit-personalization-hint = Customize the look of your collection
+edit-data-log = Collection log
+edit-data-log-hint = View information about this collection
+edit-share = Share
+edit-share-hint = Share your collection or export it to your computer
+edit-share-export = Share & export
+edit-advanced = Advanced options
+edit-advanced-hint = Change advanced options of this collection only
+edit-back = Back
+edit-sidebar-summary = Created by me • 18 minutes • Opened recently
+edit-rename = Rename
+edit-rename-hint = The name is used everywhere this collection is shown
+edit-cover = Cover and icon image
+edit-cover-hint = Preview the image before applying it, a recognizable picture works best
+edit-default-cover = Default cover image
+edit-default-cover-hint = Use a default image provided by Era Connect
+edit-cover-from-computer = Browse computer
+edit-cover-from-computer-hint = Use an image from your computer
+edit-accent = Accent colour
+edit-accent-hint = Tints this collection's pages and download progress, taken from the cover unless picked here
+edit-accent-vibrant = Vibrant colour of the cover
+edit-accent-dominant = Dominant colour of the cover
+edit-accent-from-cover = Use the cover's colour
+
+## Import
+import-title = Add to { $name }
+import-skipped = Not recognized, skipped
+import-incompatible = Made for { $target }, which this collection isn't, skipped
+import-overwrites = Replaces { $count } existing files or folders
+import-kind-mod = Mod
+import-kind-resource-pack = Resource pack
+import-kind-shader-pack = Shader pack
+import-kind-world = World
+import-kind-modpack = Modpack
+import-kind-unknown = Unknown
+import-cancel = Cancel
+import-confirm = Import
+
+## Worlds
+world-count = { $count ->
+    [one] 1 world
+   *[other] { $count } worlds
+}
+world-import = Import world
+world-backup = Back up
+world-export = Export
+world-backups = Backups ({ $total })
+world-no-backups = No backups yet
+world-backup-before-launch = Back up before launch
+world-backup-automatic = Before launch
+world-restore = Restore
+world-restore-title = Restore { $world }
+world-restore-no-changes = The world is the same as this backup
+world-restore-restored = Files brought back
+world-restore-changed = Files reverted
+world-restore-removed = Files deleted
+world-restore-cancel = Cancel
+world-restore-confirm = Restore
+
+## Notifications
+notification-title = Notifications
+notification-empty = No notifications yet
+notification-clear = Clear
+notification-close = Close
+notification-dismiss = Dismiss
+notification-desktop = Desktop notifications
+notification-kind-download-finished = Downloads finished
+notification-kind-collection-created = Collection created
+notification-kind-game-crashed = Game crashed
+notification-kind-mod-updates = Mod updates
+notification-downloads-finished = Downloads finished
+notification-downloads-finished-body = { $count ->
+    [one] 1 file downloaded
+   *[other] { $count } files downloaded
+}
+notification-collection-downloaded = Collection ready
+notification-collection-created = Collection created
+notification-game-crashed = { $name } crashed
+notification-game-crashed-body = A crash report was saved to the crash-reports folder
+notification-mod-updates = { $count ->
+    [one] 1 mod in { $name } can be updated
+   *[other] { $count } mods in { $name } can be updated
+}
+
+## Toasts
+toast-copy-error = Copy error
+toast-dismiss = Dismiss
+
+## Errors
+app-error-collection = This collection could not be read or saved.
+app-error-builder = The collection could not be created.
+app-error-manifest = The Minecraft version list could not be loaded.
+app-error-io = A file could not be read or written.
+app-error-network = The network request failed, check your connection and try again.
+app-error-folder = Something went wrong in { $path }.
+app-error-other = Something unexpected happened.
+app-error-details = Technical details
+app-error-retry = Retry
+app-error-open-folder = Open folder
+app-error-disable-mod = Disable mod
+
+## Diagnostics
+diagnostic-title = Diagnostic report
+diagnostic-hint = Bundles the app log, system and Java details, this collection's mods and its latest game log and crash report into a zip to attach to a bug report.
+diagnostic-create = Create diagnostic report
+diagnostic-preview-title = Review the report
+diagnostic-preview-hint = This is everything the report contains. Your home folder and user name have been replaced.
+diagnostic-cancel = Cancel
+diagnostic-save = Save report
+diagnostic-saved = Diagnostic report saved
+diagnostic-reveal = Show in folder
+
+## Logs
+logs-title = Logs
+logs-record-level = Record
+logs-show-level = Show
+logs-module-filter = Filter by module
+logs-follow = Follow
+logs-empty = Nothing logged yet
+
+## Settings
+settings-title = Settings
+settings-language = Language
+settings-theme = Theme
+settings-theme-dark = Dark
+settings-theme-light = Light
+settings-theme-hint = Themes are json files in { $folder }, reload after adding one.
+settings-theme-reload = Reload themes
+settings-theme-open-folder = Open themes folder
+settings-accent = Accent
+settings-accent-reset = Use the theme's accent
+settings-default-memory = Default memory
+settings-default-memory-hint = How much memory new collections start with.
+settings-java = Java
+settings-java-hint = The Java executable and JVM arguments new collections are launched with, the executable is also described in diagnostic reports.
+settings-java-path = Java path, leave empty to use the one on PATH
+settings-java-arguments = JVM arguments
+settings-downloads = Downloads
+settings-notifications = Notifications
+settings-data-directory = Data directory
+settings-data-directory-hint = Currently { $current }. Changes take effect after a restart, and existing data is not moved.
+settings-diagnostics-hint = Bundles the app log and the system and Java details into a zip to attach to a bug report.
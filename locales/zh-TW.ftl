@@ -0,0 +1,285 @@
+error-title = 糟糕，發生錯誤了。如果問題持續發生，請建立診斷報告並提供給開發者。
+loading = 載入中...
+
+## Side bar
+my-collections = 我的錦集
+sidebar-home = 首頁
+sidebar-explore = 探索
+sidebar-library = 收藏庫
+sidebar-installing-mods = 模組 - 安裝中
+sidebar-downloads = 下載
+sidebar-guide = 指南
+sidebar-settings = 設定
+
+## Main page
+main-playing-created-by-me = 遊玩中•由我建立
+main-suggested-action = 建議動作
+main-quick-setup-hint = 你還沒完成快速設定，我們建議你盡快完成
+main-need-help = 需要幫助？
+main-need-help-hint = 查看使用手冊與教學
+main-explore = 探索內容
+main-explore-hint = 開始探索 Minecraft 的第三方社群內容
+main-creation-center = 創造中心
+main-creation-center-hint = 建立你的個人化收藏
+main-personalize = 打造個人化收藏
+main-personalize-hint = 你可以透過風格化功能來裝飾你的收藏
+main-update-ready-hint = 啟動器更新已經準備就緒
+main-suggest-quick-setup = 建議：快速設定
+main-suggest-update = 建議：更新提醒
+main-manual = 使用手冊
+main-personal-collections = 個人化收藏
+main-hero-line-1 = 探索  創造
+main-hero-line-2 = 無窮  無限
+main-hero-line-3 = 創作  可能
+main-my-collections-hint = 你最愛的收藏都在這裡
+
+## Collections
+collections-choose-folder = 選擇資料夾
+collections-search = 搜尋合集
+collection-menu-launch = 開始遊戲
+collection-menu-change-folder = 更改資料夾
+collection-menu-details = 檢視詳情
+collection-menu-personalize = 風格化選項
+collection-menu-browse-folder = 瀏覽本機資料夾
+collection-menu-export = 匯出/分享合集
+collection-menu-duplicate = 複製合集
+collection-menu-delete = 刪除合集
+
+## Collection display
+collection-search = 搜尋合集中的內容
+collection-created-by-me = 由我建立
+collection-tab-mods = 模組
+collection-tab-worlds = 地圖
+collection-tab-resource-packs = 資源包
+collection-tab-shader-packs = 光影包
+collection-tab-screenshots = 螢幕捷圖
+screenshots-file-name = 檔案名稱
+screenshots-details = (拍攝日期/檔案大小/圖片大小)
+screenshots-sort-date = 日期
+screenshots-sort-size = 大小
+screenshots-sort-name = 名稱
+screenshots-copy = 複製
+screenshots-reveal = 在資料夾中顯示
+screenshots-delete = 刪除
+screenshots-delete-prompt = 要刪除 { $name } 嗎？
+screenshots-delete-cancel = 取消
+screenshots-delete-confirm = 永久刪除
+
+## Mods
+mod-column-icon = 圖示
+mod-column-name = 名稱（來源／文件名稱）
+mod-column-author = 作者
+mod-column-platform = 平台
+mod-column-last-updated = 最後更新
+mod-column-file-size = 檔案大小
+mod-column-update = 更新
+mod-column-delete = 刪除
+mod-column-more = 更多
+mod-column-status = 狀態
+mod-filter-all = 全部
+mod-filter-enabled = 已啟用
+mod-filter-disabled = 已停用
+mod-table-columns = 顯示欄位
+mod-side-both = 客戶端 & 伺服端
+mod-side-client = 客戶端
+mod-side-server = 伺服端
+mod-side-unknown = 未知
+mod-versions-summary = { $count } 個版本・最新 { $latest }
+mod-switch-source = 改用 { $platform } 的檔案
+mod-translate = 翻譯
+mod-show-original = 顯示原文
+mod-pin = 釘選版本
+mod-pinned = 已釘選版本
+mod-unmanaged = 未管理
+mod-unmanaged-lookup = 在 Modrinth 與 CurseForge 上查詢 { $count } 個未管理的模組
+mod-not-on-platform = { $platform } 上沒有這個模組，顯示的是已安裝的版本資訊
+mod-version-history = 版本紀錄
+mod-no-changelog = 沒有更新日誌
+mod-current-version = 目前版本
+mod-incompatible = 不相容
+mod-install = 安裝
+
+## Mod profiles
+profile-all-mods = 所有模組
+profile-delete = 刪除
+profile-new-name = 新設定檔名稱
+profile-save = 儲存
+
+## Downloads
+download-total = 總計 { $total }/已下載 { $downloaded }
+download-running = 正在進行
+download-finished = 已完成
+download-queued = 排隊中
+download-paused = 已暫停
+download-failed = 失敗：{ $error }
+download-pause = 暫停
+download-resume = 繼續
+download-cancel = 取消
+download-concurrency = 同時下載數
+download-bandwidth-limit = 速度上限 (KB/s)
+download-unlimited = 無限制
+download-average-speed = 平均 { $speed }/s
+download-retry = 重試
+download-retry-unavailable = 請重新安裝，下載後續的步驟無法從這裡重做
+download-remove = 移除
+download-history-empty = 尚無已完成的下載
+download-history-clear = 清除紀錄
+download-eta = 剩餘 { $time }
+download-failures = { $count } 個檔案下載失敗
+download-category-assets = 遊戲資源
+download-category-libraries = 函式庫
+download-category-loader = 模組載入器
+download-category-mods = 模組
+download-category-other = 其他檔案
+
+## Collection builder
+builder-title = 建立合集
+builder-subtitle = 從頭開始建立你的合集
+builder-cover = 封面與背景圖片
+builder-name = 合集名稱
+builder-default-name = 新的合集
+builder-untitled = 新的收藏
+builder-game-version = 遊戲版本
+builder-show-snapshots = 顯示快照版本
+builder-memory = 分配記憶體
+builder-advanced = 進階選項
+builder-advanced-toggle = 進
+builder-mod-loader = 模組載入器
+builder-cancel = 取消
+builder-finish = 完成
+
+## Collection edit
+edit-personalization = 風格化
+edit-personalization-hint = 自訂你的收藏樣式
+edit-data-log = 收藏紀錄
+edit-data-log-hint = 查看這個收藏的資訊
+edit-share = 分享
+edit-share-hint = 分享你的收藏或是將它匯出至電腦
+edit-share-export = 分享&匯出
+edit-advanced = 進階選項
+edit-advanced-hint = 單獨修改此收藏的進階選項
+edit-back = 返回頁面
+edit-sidebar-summary = 由我建立•18 分鐘•不久前開啟
+edit-rename = 更改名稱
+edit-rename-hint = 名稱將會套用至此收藏的所有顯示位置
+edit-cover = 封面與圖示圖片
+edit-cover-hint = 預覽即將套用的圖片，建議使用辨識度較高的圖片
+edit-default-cover = 預設封面圖片
+edit-default-cover-hint = 使用Era Connect提供的預設圖片
+edit-cover-from-computer = 從電腦尋找
+edit-cover-from-computer-hint = 使用你電腦中的圖片
+edit-accent = 強調色
+edit-accent-hint = 用於此收藏的頁面與下載進度，除非在此選擇，否則取自封面
+edit-accent-vibrant = 封面的鮮豔色
+edit-accent-dominant = 封面的主色
+edit-accent-from-cover = 使用封面的顏色
+
+## Import
+import-title = 加入到 { $name }
+import-skipped = 無法辨識，將略過
+import-incompatible = 此模組包適用於 { $target }，與此收藏不符，將略過
+import-overwrites = 將取代 { $count } 個現有的檔案或資料夾
+import-kind-mod = 模組
+import-kind-resource-pack = 資源包
+import-kind-shader-pack = 光影包
+import-kind-world = 世界
+import-kind-modpack = 模組包
+import-kind-unknown = 未知
+import-cancel = 取消
+import-confirm = 匯入
+
+## Worlds
+world-count = { $count } 個世界
+world-import = 匯入世界
+world-backup = 備份
+world-export = 匯出
+world-backups = 備份（{ $total }）
+world-no-backups = 尚無備份
+world-backup-before-launch = 啟動前備份
+world-backup-automatic = 啟動前
+world-restore = 還原
+world-restore-title = 還原 { $world }
+world-restore-no-changes = 世界與此備份相同
+world-restore-restored = 將復原的檔案
+world-restore-changed = 將還原的檔案
+world-restore-removed = 將刪除的檔案
+world-restore-cancel = 取消
+world-restore-confirm = 還原
+
+## Notifications
+notification-title = 通知
+notification-empty = 尚無通知
+notification-clear = 清除
+notification-close = 關閉
+notification-dismiss = 移除
+notification-desktop = 桌面通知
+notification-kind-download-finished = 下載完成
+notification-kind-collection-created = 合集建立完成
+notification-kind-game-crashed = 遊戲崩潰
+notification-kind-mod-updates = 模組更新
+notification-downloads-finished = 下載完成
+notification-downloads-finished-body = 已下載 { $count } 個檔案
+notification-collection-downloaded = 合集已就緒
+notification-collection-created = 合集建立完成
+notification-game-crashed = { $name } 崩潰了
+notification-game-crashed-body = 崩潰報告已儲存至 crash-reports 資料夾
+notification-mod-updates = { $name } 有 { $count } 個模組可以更新
+
+## Toasts
+toast-copy-error = 複製錯誤
+toast-dismiss = 關閉
+
+## Errors
+app-error-collection = 無法讀取或儲存此合集。
+app-error-builder = 無法建立合集。
+app-error-manifest = 無法載入 Minecraft 版本列表。
+app-error-io = 無法讀取或寫入檔案。
+app-error-network = 網路請求失敗，請檢查連線後再試一次。
+app-error-folder = { $path } 發生錯誤。
+app-error-other = 發生了預期外的錯誤。
+app-error-details = 技術細節
+app-error-retry = 重試
+app-error-open-folder = 開啟資料夾
+app-error-disable-mod = 停用模組
+
+## Diagnostics
+diagnostic-title = 診斷報告
+diagnostic-hint = 將應用程式日誌、系統與 Java 資訊、此合集的模組以及最新的遊戲日誌和崩潰報告打包成 zip，方便附加在錯誤回報中。
+diagnostic-create = 建立診斷報告
+diagnostic-preview-title = 檢查報告內容
+diagnostic-preview-hint = 以下是報告包含的所有內容。你的家目錄與使用者名稱已被替換。
+diagnostic-cancel = 取消
+diagnostic-save = 儲存報告
+diagnostic-saved = 診斷報告已儲存
+diagnostic-reveal = 在資料夾中顯示
+
+## Logs
+logs-title = 日誌
+logs-record-level = 記錄等級
+logs-show-level = 顯示等級
+logs-module-filter = 依模組篩選
+logs-follow = 自動捲動
+logs-empty = 目前沒有任何紀錄
+
+## Settings
+settings-title = 設定
+settings-language = 語言
+settings-theme = 主題
+settings-theme-dark = 深色
+settings-theme-light = 淺色
+settings-theme-hint = 主題為 { $folder } 中的 json 檔案，新增後請重新載入。
+settings-theme-reload = 重新載入主題
+settings-theme-open-folder = 開啟主題資料夾
+settings-accent = 強調色
+settings-accent-reset = 使用主題的強調色
+settings-default-memory = 預設記憶體
+settings-default-memory-hint = 新合集預設分配的記憶體。
+settings-java = Java
+settings-java-hint = 新合集啟動時使用的 Java 執行檔與 JVM 參數，診斷報告也會記錄此 Java 執行檔。
+settings-java-path = Java 路徑，留空則使用 PATH 中的 Java
+settings-java-arguments = JVM 參數
+settings-downloads = 下載
+settings-notifications = 通知
+settings-data-directory = 資料目錄
+settings-data-directory-hint = 目前位於 { $current }。變更會在重新啟動後生效，現有資料不會被搬移。
+settings-diagnostics-hint = 將應用程式日誌以及系統與 Java 資訊打包成 zip，方便附加在錯誤回報中。
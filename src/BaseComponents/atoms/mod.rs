@@ -4,8 +4,22 @@ pub mod switch;
 
 #[must_use]
 pub fn markdown_to_html(s: &str) -> String {
-    let parser = pulldown_cmark::Parser::new(s);
+    markdown_to_html_with(s, &ammonia::Builder::default())
+}
+
+/// Same as [`markdown_to_html`], sanitizing with `sanitizer` instead of ammonia's defaults
+#[must_use]
+pub fn markdown_to_html_with(s: &str, sanitizer: &ammonia::Builder) -> String {
+    events_to_html(pulldown_cmark::Parser::new(s), sanitizer)
+}
+
+/// Renders already parsed markdown, for callers that rewrite the events first
+#[must_use]
+pub fn events_to_html<'a>(
+    events: impl Iterator<Item = pulldown_cmark::Event<'a>>,
+    sanitizer: &ammonia::Builder,
+) -> String {
     let mut string = String::new();
-    pulldown_cmark::html::push_html(&mut string, parser);
-    ammonia::clean(&string)
+    pulldown_cmark::html::push_html(&mut string, events);
+    sanitizer.clean(&string).to_string()
 }
@@ -4,6 +4,7 @@ use dioxus::prelude::*;
 pub fn FileInput(
     #[props(extends = label, extends = GlobalAttributes)] attributes: Vec<Attribute>,
     filename: Signal<Option<String>>,
+    #[props(default = String::from(".png,.jpg,.avif,.heif"))] accept: String,
     children: Element,
 ) -> Element {
     rsx! {
@@ -13,7 +14,7 @@ pub fn FileInput(
             input {
                 r#type: "file",
                 class: "hidden",
-                accept: ".png,.jpg,.avif,.heif",
+                accept,
                 multiple: false,
                 onchange: move |evt| {
                     if let Some(files) = evt.files() {
@@ -1,2 +1,3 @@
 pub mod markdown_renderer;
 pub mod modal;
+pub mod toast;
@@ -0,0 +1,250 @@
+use std::{
+    rc::Rc,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use dioxus::prelude::*;
+use dioxus_logger::tracing::{error, warn};
+use tailwind_fuse::tw_merge;
+
+use crate::{collection_display::screenshots::CLIPBOARD, t};
+
+/// Toasts on screen, oldest first
+static TOASTS: GlobalSignal<Vec<Toast>> = Signal::global(Vec::new);
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// How long a toast stays before dismissing itself
+    const fn duration(self) -> Duration {
+        match self {
+            Self::Info | Self::Success => Duration::from_secs(4),
+            Self::Warning => Duration::from_secs(6),
+            Self::Error => Duration::from_secs(10),
+        }
+    }
+
+    const fn class(self) -> &'static str {
+        match self {
+            Self::Info => "border-secondary-surface",
+            Self::Success => "border-green",
+            Self::Warning => "border-orange",
+            Self::Error => "border-red",
+        }
+    }
+}
+
+/// A button on a toast, the toast is dismissed after it runs
+#[derive(Clone)]
+pub struct ToastAction {
+    label: String,
+    handler: Rc<dyn Fn()>,
+}
+
+impl PartialEq for ToastAction {
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label && Rc::ptr_eq(&self.handler, &other.handler)
+    }
+}
+
+/// A short lived message in the corner of the window, for failures the user can carry on from.
+///
+/// # Example
+///
+/// ```ignore
+/// Toast::warning(t!("some-warning"))
+///     .action(t!("some-retry"), move || retry())
+///     .show();
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct Toast {
+    id: u64,
+    severity: Severity,
+    message: String,
+    detail: Option<String>,
+    actions: Vec<ToastAction>,
+    /// `None` stays until dismissed
+    duration: Option<Duration>,
+}
+
+impl Toast {
+    #[must_use]
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            id: 0,
+            severity,
+            message: message.into(),
+            detail: None,
+            actions: Vec::new(),
+            duration: Some(severity.duration()),
+        }
+    }
+
+    #[must_use]
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(Severity::Info, message)
+    }
+
+    #[must_use]
+    pub fn success(message: impl Into<String>) -> Self {
+        Self::new(Severity::Success, message)
+    }
+
+    #[must_use]
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    #[must_use]
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    /// Smaller text under the message
+    #[must_use]
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    #[must_use]
+    pub fn action(mut self, label: impl Into<String>, handler: impl Fn() + 'static) -> Self {
+        self.actions.push(ToastAction {
+            label: label.into(),
+            handler: Rc::new(handler),
+        });
+        self
+    }
+
+    /// Keeps the toast until it's dismissed
+    #[must_use]
+    pub fn persistent(mut self) -> Self {
+        self.duration = None;
+        self
+    }
+
+    pub fn show(mut self) {
+        self.id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        TOASTS.write().push(self);
+    }
+}
+
+fn dismiss(id: u64) {
+    TOASTS.write().retain(|x| x.id != id);
+}
+
+/// Shows a recoverable error as a toast instead of the fatal error modal, with its chain copyable
+pub fn toast_error(err: impl Into<anyhow::Error>) {
+    let err = err.into();
+    error!("{err:?}");
+    let report = format!("{err:?}");
+    Toast::error(err.to_string())
+        .detail(
+            err.chain()
+                .skip(1)
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+        .action(t!("toast-copy-error"), move || {
+            let Ok(mut clipboard) = CLIPBOARD.lock() else {
+                return;
+            };
+            let copied = clipboard.as_mut().map(|x| x.set_text(report.clone()));
+            match copied {
+                Some(Ok(())) => {}
+                Some(Err(err)) => warn!("Failed to copy the error: {err}"),
+                None => warn!("Failed to copy the error: the clipboard is unavailable"),
+            }
+        })
+        .show();
+}
+
+/// Renders every toast, it belongs at the root of the app
+#[component]
+pub fn ToastHost() -> Element {
+    let toasts = TOASTS.read().clone();
+    rsx! {
+        div {
+            class: "fixed bottom-[30px] right-[30px] z-[2000000] flex flex-col items-end gap-[10px] pointer-events-none",
+            for toast in toasts {
+                ToastView {
+                    key: "{toast.id}",
+                    toast,
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ToastView(toast: Toast) -> Element {
+    let (id, duration) = (toast.id, toast.duration);
+    let mut hovered = use_signal(|| false);
+    // dismissed once the duration passes without the pointer resting on the toast
+    use_hook(move || {
+        if let Some(duration) = duration {
+            spawn(async move {
+                loop {
+                    tokio::time::sleep(duration).await;
+                    if !hovered() {
+                        dismiss(id);
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    rsx! {
+        div {
+            class: tw_merge!(
+                "pointer-events-auto w-[400px] flex flex-col gap-[10px] p-[20px] rounded-[20px] border-l-4 bg-background",
+                toast.severity.class()
+            ),
+            onmouseenter: move |_| hovered.set(true),
+            onmouseleave: move |_| hovered.set(false),
+            div {
+                class: "flex gap-[10px]",
+                div {
+                    class: "grow text-white text-[16px] font-bold",
+                    {toast.message.clone()}
+                }
+                button {
+                    class: "self-start text-hint text-[14px] trim",
+                    onclick: move |_| dismiss(id),
+                    {t!("toast-dismiss")}
+                }
+            }
+            if let Some(detail) = toast.detail.clone().filter(|x| !x.is_empty()) {
+                div {
+                    class: "text-hint text-[14px] font-medium whitespace-pre-wrap",
+                    {detail}
+                }
+            }
+            if !toast.actions.is_empty() {
+                div {
+                    class: "flex justify-end gap-[15px]",
+                    for ToastAction { label , handler } in toast.actions.clone() {
+                        button {
+                            class: "text-white text-[14px] font-bold trim",
+                            onclick: move |_| {
+                                handler();
+                                dismiss(id);
+                            },
+                            {label}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
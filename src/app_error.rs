@@ -0,0 +1,349 @@
+use std::path::PathBuf;
+
+use dioxus::{prelude::*, CapturedError};
+use dioxus_logger::tracing::info;
+use dioxus_radio::prelude::RadioStation;
+use rust_lib::api::{
+    backend_exclusive::errors::ManifestProcessingError,
+    shared_resources::collection::{
+        CollectionError, CollectionId, CollectionRadioChannel, Collections,
+    },
+};
+use snafu::prelude::*;
+use tailwind_fuse::tw_merge;
+
+use crate::{
+    builder::CollectionBuilderError,
+    diagnostics::DiagnosticReportButton,
+    platform_api::PlatformApiError,
+    t,
+    BaseComponents::organisms::toast::{toast_error, Severity},
+    ErrorFormatted,
+};
+
+/// Every failure that can reach an error boundary.
+///
+/// Carries what the user is shown: a localized message, a severity and the ways to recover, while
+/// [`ErrorFormatted`] stays around for the technical detail.
+#[derive(Snafu, Debug)]
+#[snafu(visibility(pub(crate)))]
+pub enum AppError {
+    #[snafu(transparent)]
+    Collection { source: CollectionError },
+    #[snafu(transparent)]
+    Builder { source: CollectionBuilderError },
+    #[snafu(transparent)]
+    Manifest { source: ManifestProcessingError },
+    #[snafu(transparent)]
+    Io { source: std::io::Error },
+    #[snafu(transparent)]
+    Network { source: reqwest::Error },
+    #[snafu(transparent)]
+    PlatformApi { source: PlatformApiError },
+    /// Something went wrong inside `path`
+    #[snafu(display("Failed in {}", path.display()))]
+    Folder {
+        path: PathBuf,
+        #[snafu(source(from(AppError, Box::new)))]
+        source: Box<AppError>,
+    },
+    #[snafu(display("{source}"))]
+    Other {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl From<anyhow::Error> for AppError {
+    /// Recovers the concrete error where possible, so it keeps its message and recovery actions
+    fn from(err: anyhow::Error) -> Self {
+        let err = match err.downcast::<Self>() {
+            Ok(x) => return x,
+            Err(err) => err,
+        };
+        let err = match err.downcast::<CollectionError>() {
+            Ok(source) => return Self::Collection { source },
+            Err(err) => err,
+        };
+        let err = match err.downcast::<CollectionBuilderError>() {
+            Ok(source) => return Self::Builder { source },
+            Err(err) => err,
+        };
+        let err = match err.downcast::<ManifestProcessingError>() {
+            Ok(source) => return Self::Manifest { source },
+            Err(err) => err,
+        };
+        let err = match err.downcast::<std::io::Error>() {
+            Ok(source) => return Self::Io { source },
+            Err(err) => err,
+        };
+        let err = match err.downcast::<reqwest::Error>() {
+            Ok(source) => return Self::Network { source },
+            Err(err) => err,
+        };
+        match err.downcast::<PlatformApiError>() {
+            Ok(source) => Self::PlatformApi { source },
+            Err(err) => Self::Other { source: err.into() },
+        }
+    }
+}
+
+/// A way out of an error, offered next to its message
+#[derive(Debug, Clone, PartialEq)]
+pub enum Recovery {
+    /// Renders the failed part again
+    Retry,
+    OpenFolder(PathBuf),
+    DisableMod {
+        collection_id: CollectionId,
+        name: String,
+    },
+}
+
+impl AppError {
+    #[must_use]
+    pub fn message(&self) -> String {
+        match self {
+            Self::Collection { .. } => t!("app-error-collection"),
+            Self::Builder { .. } => t!("app-error-builder"),
+            Self::Manifest { .. } => t!("app-error-manifest"),
+            Self::Io { .. } => t!("app-error-io"),
+            Self::Network { .. } | Self::PlatformApi { .. } => t!("app-error-network"),
+            Self::Folder { path, .. } => {
+                t!("app-error-folder", path = path.display().to_string())
+            }
+            Self::Other { .. } => t!("app-error-other"),
+        }
+    }
+
+    /// Network failures tend to go away on their own, the rest needs looking at
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::Network { .. } | Self::PlatformApi { .. } => Severity::Warning,
+            Self::Folder { source, .. } => source.severity(),
+            _ => Severity::Error,
+        }
+    }
+
+    /// Actions offered for this error, the outermost context first
+    #[must_use]
+    pub fn recoveries(&self) -> Vec<Recovery> {
+        let mut recoveries = match self {
+            Self::Folder { path, source } => {
+                let mut recoveries = vec![Recovery::OpenFolder(path.clone())];
+                recoveries.extend(source.recoveries());
+                recoveries
+            }
+            _ => vec![Recovery::Retry],
+        };
+        recoveries.dedup();
+        recoveries
+    }
+}
+
+/// Catches errors of its children so only they are replaced by the error, instead of the whole window.
+///
+/// Retrying mounts the children again, so the resources that failed run again.
+#[component]
+pub fn ScopedErrorBoundary(
+    children: Element,
+    /// Offered on top of the ones of the caught error
+    #[props(default)]
+    recoveries: Vec<Recovery>,
+    /// The collection a diagnostic report is about
+    #[props(default)]
+    collection_id: Option<CollectionId>,
+    #[props(default)] class: String,
+) -> Element {
+    let mut generation = use_signal(|| 0_u32);
+    rsx! {
+        ErrorBoundary {
+            handle_error: move |error: ErrorContext| {
+                let errors = error.errors().to_vec();
+                let retry = EventHandler::new(move |()| {
+                    generation += 1;
+                    error.clear_errors();
+                });
+                rsx! {
+                    div {
+                        class: tw_merge!("flex flex-col gap-[20px] p-[30px] rounded-[20px] bg-background", class),
+                        for captured in errors {
+                            AppErrorView {
+                                error: captured,
+                                retry,
+                                extra: recoveries.clone(),
+                                collection_id,
+                            }
+                        }
+                    }
+                }
+            },
+            // a new key replaces the children rather than diffing them
+            for generation in [generation()] {
+                Fragment {
+                    key: "{generation}",
+                    {children.clone()}
+                }
+            }
+        }
+    }
+}
+
+/// Message, severity and recovery actions of an error caught by a boundary, with the technical detail folded away
+#[component]
+pub fn AppErrorView(
+    error: CapturedError,
+    retry: EventHandler,
+    /// Offered on top of the ones of `error`
+    #[props(default)]
+    extra: Vec<Recovery>,
+    #[props(default)] collection_id: Option<CollectionId>,
+) -> Element {
+    let Some(app_error) = error.downcast::<AppError>() else {
+        return rsx! {
+            div {
+                class: "flex flex-col gap-[15px]",
+                div {
+                    class: "text-red text-[20px] font-bold trim",
+                    {t!("app-error-other")}
+                }
+                RecoveryActions {
+                    recoveries: [vec![Recovery::Retry], extra].concat(),
+                    retry,
+                }
+                DiagnosticReportButton {
+                    error: format!("{error:#?}"),
+                    collection_id,
+                }
+                ErrorDetail {
+                    detail: format!("{error:#?}"),
+                }
+            }
+        };
+    };
+    let mut recoveries = app_error.recoveries();
+    for recovery in extra {
+        if !recoveries.contains(&recovery) {
+            recoveries.push(recovery);
+        }
+    }
+    let class = match app_error.severity() {
+        Severity::Error => "text-red",
+        Severity::Warning => "text-orange",
+        Severity::Info | Severity::Success => "text-white",
+    };
+    rsx! {
+        div {
+            class: "flex flex-col gap-[15px]",
+            div {
+                class: "{class} text-[20px] font-bold",
+                {app_error.message()}
+            }
+            RecoveryActions {
+                recoveries,
+                retry,
+            }
+            DiagnosticReportButton {
+                error: app_error.to_formatted(),
+                collection_id,
+            }
+            ErrorDetail {
+                detail: app_error.to_formatted(),
+            }
+        }
+    }
+}
+
+#[component]
+fn ErrorDetail(detail: String) -> Element {
+    let mut expanded = use_signal(|| false);
+    rsx! {
+        button {
+            class: "self-start text-hint text-[14px] trim",
+            onclick: move |_| expanded.toggle(),
+            {t!("app-error-details")}
+        }
+        if expanded() {
+            pre {
+                class: "max-w-full overflow-x-scroll text-[13px] font-bold",
+                {detail}
+            }
+        }
+    }
+}
+
+#[component]
+fn RecoveryActions(recoveries: Vec<Recovery>, retry: EventHandler) -> Element {
+    // the fatal modal sits above the collections, so mods can't be reached from there
+    let has_collections =
+        try_consume_context::<RadioStation<Collections, CollectionRadioChannel>>().is_some();
+    rsx! {
+        div {
+            class: "flex gap-[10px]",
+            for recovery in recoveries {
+                {
+                    match recovery {
+                        Recovery::Retry => rsx! {
+                            button {
+                                class: "h-[40px] px-[15px] rounded-[15px] bg-white text-black text-[14px] font-medium trim",
+                                onclick: move |_| retry.call(()),
+                                {t!("app-error-retry")}
+                            }
+                        },
+                        Recovery::OpenFolder(path) => rsx! {
+                            button {
+                                class: "h-[40px] px-[15px] rounded-[15px] bg-deep-background text-white text-[14px] font-medium trim",
+                                onclick: move |_| {
+                                    if let Err(err) = opener::reveal(&path) {
+                                        toast_error(err);
+                                    }
+                                },
+                                {t!("app-error-open-folder")}
+                            }
+                        },
+                        Recovery::DisableMod { collection_id, name } if has_collections => rsx! {
+                            DisableModButton {
+                                collection_id,
+                                name,
+                                retry,
+                            }
+                        },
+                        Recovery::DisableMod { .. } => rsx! {},
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn DisableModButton(collection_id: CollectionId, name: String, retry: EventHandler) -> Element {
+    let mut radio = collection_id.use_collection_radio();
+    rsx! {
+        button {
+            class: "h-[40px] px-[15px] rounded-[15px] bg-deep-background text-white text-[14px] font-medium trim",
+            onclick: move |_| {
+                let name = name.clone();
+                spawn(async move {
+                    let binding = || async move {
+                        let collection = radio.read_owned();
+                        let Some(mut controller) = collection.mod_controller.clone() else {
+                            return Ok(());
+                        };
+                        if let Some(mods) = controller.manager.mods.iter_mut().find(|x| x.name == name) {
+                            info!("Disable mod {name}");
+                            mods.disable().await?;
+                            radio.with_mut(|x| x.mod_controller = Some(controller))?;
+                        }
+                        Ok::<(), anyhow::Error>(())
+                    };
+                    match binding().await {
+                        Ok(()) => retry.call(()),
+                        Err(err) => toast_error(err),
+                    }
+                });
+            },
+            {t!("app-error-disable-mod")}
+        }
+    }
+}
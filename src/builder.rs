@@ -5,13 +5,18 @@ use std::path::PathBuf;
 use rust_lib::api::{
     backend_exclusive::{errors::ManifestProcessingError, vanilla::version::VersionMetadata},
     shared_resources::{
-        collection::{CollectionError, CollectionId, CollectionsRadio, ModLoader, ModLoaderType},
+        collection::{
+            AdvancedOptions, CollectionError, CollectionId, CollectionsRadio, ModLoader,
+            ModLoaderType, Size,
+        },
         entry,
     },
 };
 use snafu::prelude::*;
 
 use crate::get_random_collection_picture;
+use crate::settings::SETTINGS;
+use crate::t;
 
 #[derive(Snafu, Debug)]
 pub enum CollectionBuilderError {
@@ -40,14 +45,22 @@ pub async fn collection_builder(
     version: VersionMetadata,
     collections_radio: CollectionsRadio,
 ) -> Result<CollectionId, CollectionBuilderError> {
+    let advanced_options = {
+        let settings = SETTINGS.peek();
+        AdvancedOptions {
+            jvm_max_memory: Some(Size::Gigabytes(settings.default_memory)),
+            java_path: settings.java_path.clone(),
+            java_arguments: settings.java_arguments.clone(),
+        }
+    };
     let id = entry::create_collection(
-        "新的收藏",
+        t!("builder-untitled"),
         picture_path
             .into()
             .unwrap_or_else(get_random_collection_picture),
         version,
         ModLoader::new(ModLoaderType::Fabric, None),
-        None,
+        advanced_options,
         collections_radio,
     )
     .await?;
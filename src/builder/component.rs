@@ -11,8 +11,12 @@ use rust_lib::api::{
 };
 
 use crate::{
+    app_error::AppError,
     get_random_collection_picture,
+    notifications::{notify, NotificationKind},
+    settings::SETTINGS,
     svgs::{self, CLOSE_CROSS, CREATE_COLLECTION, FOLDER_UPLOAD, LINE, SHADOW_ADD, UPLOAD_FILE},
+    t,
     BaseComponents::{
         atoms::{
             center::Center,
@@ -52,11 +56,11 @@ fn Header() -> Element {
                     class: "flex flex-col gap-[15px] justify-center",
                     div {
                         class: "text-[30px] font-bold trim",
-                        "建立合集"
+                        {t!("builder-title")}
                     }
                     div {
                         class: "text-[15px] font-normal text-secondary-text trim",
-                        "從頭開始建立你的合集"
+                        {t!("builder-subtitle")}
                     }
                 }
             }
@@ -100,7 +104,7 @@ fn PicturePicker(cover_img: Signal<PathBuf>, background_img: Signal<PathBuf>) ->
         div {
             class: "flex flex-col gap-[20px]",
             Title {
-                title: "封面與背景圖片",
+                title: t!("builder-cover"),
             }
             div {
                 class: "flex gap-[20px] justify-center",
@@ -171,7 +175,7 @@ fn SetupName(mut title: Signal<Option<String>>) -> Element {
         div {
             class: "flex flex-col gap-[20px]",
             Title {
-                title: "合集名稱",
+                title: t!("builder-name"),
             }
             div {
                 class: "flex gap-[5px]",
@@ -181,7 +185,7 @@ fn SetupName(mut title: Signal<Option<String>>) -> Element {
                     oninput: move |x| {
                         title.set(Some(x.value()));
                     },
-                    value: title().unwrap_or_else(|| "新的合集".into()),
+                    value: title().unwrap_or_else(|| t!("builder-default-name")),
                 }
                 button {
                     class: "p-[10px] bg-background rounded-[20px] w-[60px] inline-flex justify-center items-center",
@@ -261,7 +265,7 @@ pub fn GameVersion(selected_version: Signal<Option<VersionMetadata>>) -> Element
         div {
             class: "flex flex-col gap-[20px] z-50",
             Title {
-                title: "遊戲版本",
+                title: t!("builder-game-version"),
             }
             div {
                 class: "flex gap-[5px] h-[60px] z-50",
@@ -290,7 +294,7 @@ pub fn GameVersion(selected_version: Signal<Option<VersionMetadata>>) -> Element
                         onclick: move |_| {
                             snapshot_status.toggle();
                         },
-                        "顯示快照版本"
+                        {t!("builder-show-snapshots")}
                     }
                     CLOSE_CROSS {
                         class: "justify-self-end inline-flex justify-center items-center",
@@ -301,27 +305,26 @@ pub fn GameVersion(selected_version: Signal<Option<VersionMetadata>>) -> Element
     }
 }
 
-const DEFAULT_MEMORY: usize = 8;
+/// Gigabytes of memory a collection can be given
+pub(crate) const MEMORY_CHOICES: [usize; 6] = [1, 2, 4, 8, 16, 32];
 
 #[component]
 fn MemorySelector(memory_selected: Signal<usize>) -> Element {
-    let memory_selector = [1, 2, 4, 8, 16, 32];
-
     rsx! {
         div {
             class: "mt-[35px] flex flex-col gap-[20px]",
             Title {
-                title: "分配記憶體",
+                title: t!("builder-memory"),
             }
             div {
                 class: "h-[60px] flex gap-[5px] *:bg-background *:rounded-[20px]",
                 div {
                     class: "aspect-square p-[10px]",
                     onclick: move |_| {
-                        memory_selected.set(DEFAULT_MEMORY);
+                        memory_selected.set(SETTINGS.peek().default_memory);
                     }
                 }
-                for i in memory_selector {
+                for i in MEMORY_CHOICES {
                     div {
                         class: "w-fit px-[20px] flex items-center aria-selected:bg-white [&_*]:pointer-events-none",
                         aria_selected: memory_selected() == i,
@@ -359,7 +362,7 @@ pub fn AdvancedOption(memory_selected: Signal<usize>) -> Element {
                     class: "w-full flex items-center gap-[15px]",
                     div {
                         class: "text-[18px] font-normal min-w-fit text-white group-data-[enabled=false]:text-hint",
-                        "進階選項"
+                        {t!("builder-advanced")}
                     }
                     LINE {
                         class: "w-full grow [&_*]:stroke-background group-data-[enabled=true]:[&_*]:stroke-secondary-surface",
@@ -367,7 +370,7 @@ pub fn AdvancedOption(memory_selected: Signal<usize>) -> Element {
                     }
                     div {
                         class: "w-fit grow-0",
-                        "進"
+                        {t!("builder-advanced-toggle")}
                     }
                 }
             },
@@ -397,7 +400,7 @@ pub fn ModLoaderSelector(modloader_selected: Signal<Option<ModLoader>>) -> Eleme
         div {
             class: "flex flex-col gap-[20px] z-40",
             Title {
-                title: "模組載入器",
+                title: t!("builder-mod-loader"),
             }
             div {
                 class: "flex gap-[5px] h-[60px] z-40",
@@ -440,7 +443,7 @@ pub fn Footer(canceled: Signal<bool>, finished: Signal<bool>) -> Element {
                 }
                 div {
                     class: "justify-self-end trim text-[20px] pointer-events-none",
-                    "取消"
+                    {t!("builder-cancel")}
                 }
             }
             button {
@@ -454,7 +457,7 @@ pub fn Footer(canceled: Signal<bool>, finished: Signal<bool>) -> Element {
                 }
                 div {
                     class: "justify-self-end trim text-[20px] pointer-events-none",
-                    "完成"
+                    {t!("builder-finish")}
                 }
             }
         }
@@ -468,7 +471,7 @@ pub fn BuildCollection(active: Signal<bool>) -> Element {
     let background_img = use_signal(get_random_collection_picture);
     let selected_version = use_signal(|| None);
     let modloader_selected = use_signal(|| None);
-    let memory_selected = use_signal(|| DEFAULT_MEMORY);
+    let memory_selected = use_signal(|| SETTINGS.peek().default_memory);
 
     let canceled = use_signal(|| false);
     let finished = use_signal(|| false);
@@ -494,14 +497,15 @@ pub fn BuildCollection(active: Signal<bool>) -> Element {
                     None => match VersionMetadata::latest_release().await {
                         Ok(v) => v,
                         Err(err) => {
-                            throw_error(err);
+                            throw_error(AppError::from(err));
                             return;
                         }
                     },
                 };
 
+                let title = title().unwrap_or_else(|| t!("builder-untitled"));
                 if let Err(err) = entry::create_collection(
-                    title().unwrap_or_else(|| String::from("新的收藏")),
+                    title.clone(),
                     cover_img(),
                     version,
                     ModLoader {
@@ -510,15 +514,22 @@ pub fn BuildCollection(active: Signal<bool>) -> Element {
                     },
                     AdvancedOptions {
                         jvm_max_memory: Some(Size::Gigabytes(memory_selected())),
-                        java_arguments: String::new(),
+                        java_path: SETTINGS.peek().java_path.clone(),
+                        java_arguments: SETTINGS.peek().java_arguments.clone(),
                     },
                     collections_radio,
                 )
                 .await
                 {
-                    throw_error(err);
+                    throw_error(AppError::from(err));
+                    return;
                 }
                 info!("Finished collection creation");
+                notify(
+                    NotificationKind::CollectionCreated,
+                    t!("notification-collection-created"),
+                    title,
+                );
                 // active.set(false);
             }
         });
@@ -1,22 +1,34 @@
 pub mod mod_renderer;
+pub mod profiles;
+pub mod screenshots;
+pub mod worlds;
+
+use std::path::Path;
 
 use dioxus::{prelude::*, CapturedError};
 use dioxus_logger::tracing::{debug, error, info, trace, warn, Level};
 use mod_renderer::ModViewer;
-use notify::Watcher;
+use profiles::{use_profile_applier, ModProfiles, ProfilePicker};
 use rust_lib::api::{
     backend_exclusive::vanilla::launcher::LoggerEvent,
-    shared_resources::collection::{CollectionError, CollectionId, ScreenShot},
+    shared_resources::collection::{CollectionId, ScreenShot},
 };
+use screenshots::Screenshots;
 use strum::EnumIter;
 use tokio::{fs, io::BufReader};
 use tokio_stream::StreamExt;
+use worlds::{use_world_backup, WorldViewer};
 
 use crate::{
-    impl_context_switcher,
+    app_error::AppError,
+    cover_palette::{accent_channels, use_collection_accent},
+    download_queue, impl_context_switcher,
+    import::{use_import_drop, ImportConfirmation},
     main_page::STAR,
     pages::Pages,
-    use_error_handler,
+    persistence::use_collection_persisted,
+    t,
+    watcher::{folder_changes, WatchedFolder},
     BaseComponents::{
         atoms::button::{Button, FillMode, Roundness},
         molecules::{
@@ -24,9 +36,9 @@ use crate::{
             search_bar::SearchBar,
             switcher::{Comparison, StateSwitcher},
         },
+        organisms::toast::toast_error,
         string_placements::{Alignment, ContentType, Contents},
     },
-    SnafuToCapturedError,
 };
 
 pub static DISPLAY_BACKGROUND: Asset = asset!("/assets/cool_image.png");
@@ -83,12 +95,24 @@ pub fn ScrollableFootBar(main: Element, footer: Element, bottom: Element) -> Ele
     }
 }
 
+/// Files and folders directly in `dir`, packs can be either
+async fn entry_count(dir: &Path) -> usize {
+    let Ok(mut entries) = fs::read_dir(dir).await else {
+        return 0;
+    };
+    let mut count = 0;
+    while let Ok(Some(_)) = entries.next_entry().await {
+        count += 1;
+    }
+    count
+}
+
 #[component]
 fn Footer(
     collection_id: ReadOnlySignal<CollectionId>,
     status: Signal<CollectionDisplayTopSelection>,
     search: Signal<String>,
-    screenshots: Resource<Result<Vec<ScreenShot>, CollectionError>>,
+    screenshots: Resource<Result<Vec<ScreenShot>, CapturedError>>,
     default: String,
 ) -> Element {
     let mut radio = collection_id().use_collection_radio();
@@ -98,11 +122,24 @@ fn Footer(
         .mod_controller()
         .map(|x| x.manager.mods.iter().filter(|x| x.enabled).count());
 
-    let mut error_handler = use_error_handler();
+    let profiles = use_collection_persisted::<ModProfiles>(collection_id);
+    let apply_selected_profile = use_profile_applier(collection_id, profiles);
+    let backup_worlds = use_world_backup(collection_id);
+
+    let packs_len = use_resource(move || async move {
+        folder_changes(collection_id(), WatchedFolder::ResourcePacks);
+        folder_changes(collection_id(), WatchedFolder::ShaderPacks);
+        let game_directory = radio.read().game_directory().to_path_buf();
+        (
+            entry_count(&game_directory.join(WatchedFolder::ResourcePacks.dir_name())).await,
+            entry_count(&game_directory.join(WatchedFolder::ShaderPacks.dir_name())).await,
+        )
+    });
+    let (resource_packs_len, shader_packs_len) = packs_len().unwrap_or_default();
 
     let screenshots_len = match &*screenshots.read() {
         Some(Ok(x)) => x.len(),
-        Some(Err(err)) => Err(err.to_render_error())?,
+        Some(Err(err)) => Err(err.clone())?,
         None => 0,
     };
 
@@ -113,10 +150,6 @@ fn Footer(
 
         let msg = logs.message().unwrap_or_default();
 
-        if msg.contains("screenshot") {
-            screenshots.restart();
-        }
-
         let output = format!("[{}] {msg}", logs.thread());
 
         drop(logs);
@@ -146,8 +179,8 @@ fn Footer(
         let len = match s {
             S::Mods => mods_len.unwrap_or_default(),
             S::World => 0,
-            S::ResourcePack => 0,
-            S::ShaderPacks => 0,
+            S::ResourcePack => resource_packs_len,
+            S::ShaderPacks => shader_packs_len,
             S::ScreenShots => screenshots_len,
         };
 
@@ -163,12 +196,14 @@ fn Footer(
                 div {
                     aria_selected: status() == s,
                     class: "aria-selected:text-white justify-self-start",
-                    match s {
-                        S::Mods => "模組",
-                        S::World => "地圖",
-                        S::ResourcePack => "資源包",
-                        S::ShaderPacks => "光影包",
-                        S::ScreenShots => "螢幕捷圖"
+                    {
+                        match s {
+                            S::Mods => t!("collection-tab-mods"),
+                            S::World => t!("collection-tab-worlds"),
+                            S::ResourcePack => t!("collection-tab-resource-packs"),
+                            S::ShaderPacks => t!("collection-tab-shader-packs"),
+                            S::ScreenShots => t!("collection-tab-screenshots"),
+                        }
                     }
                 }
                 div {
@@ -223,20 +258,41 @@ fn Footer(
                 fill_mode: FillMode::Fit,
                 string_placements: vec![ContentType::svg(STAR).css("svg-[40px]").align_center()],
             }
+            ProfilePicker {
+                collection_id,
+                profiles,
+            }
             Button {
                 roundness: Roundness::Squircle,
                 extended_css_class: "bg-white min-w-[150px]",
                 fill_mode: FillMode::Fit,
                 onclick: move |()| async move {
+                    if let Err(err) = apply_selected_profile().await {
+                        error!("failed to apply mod profile {err:?}");
+                        toast_error(err);
+                        return;
+                    }
+                    // a missing backup isn't worth keeping the game from starting
+                    if let Err(err) = backup_worlds().await {
+                        warn!("failed to back up worlds {err:?}");
+                        toast_error(err);
+                    }
                     if let Err(err) = radio
                         .with_async_mut(move |mut collection| async move {
-                            collection.launch_game(logs).await?;
+                            // the game files it fetches can be paused and cancelled from the downloads page
+                            let label = collection.display_name().clone();
+                            download_queue::run_backend(
+                                collection_id(),
+                                label,
+                                collection.launch_game(logs),
+                            )
+                            .await??;
                             Ok(collection)
                         })
                         .await
                     {
                         error!("collection throwed {err:?}");
-                        error_handler.set(Err(err.into()));
+                        toast_error(err);
                     }
                 },
                 string_placements: vec![{ ContentType::svg(GAME_CONTROLLER).align_center() }],
@@ -249,12 +305,14 @@ fn Footer(
 fn Content(collection_id: ReadOnlySignal<CollectionId>) -> Element {
     let radio = collection_id().use_collection_radio();
     let mod_loader = CopyValue::new(radio.read().mod_loader().map(ToString::to_string));
+    let accent = use_collection_accent(collection_id);
     rsx! {
         div {
             class: "rounded-[30px] w-full h-full p-[40px] grid grid-flow-col justify-stretch items-end",
-            background_color: "#191919",
+            background_color: "rgb(var(--color-background))",
             background: format!(
-                "linear-gradient(145deg, rgba(25, 25, 25, 0.00) 18.18%, #191919 88.98%), url(\'{}\') lightgray 50% / cover no-repeat",
+                "linear-gradient(145deg, rgb({} / 0.15) 18.18%, rgb(var(--color-background)) 88.98%), url(\'{}\') lightgray 50% / cover no-repeat",
+                accent_channels(accent()),
                 DISPLAY_BACKGROUND,
             ),
             div {
@@ -298,7 +356,7 @@ fn Content(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                         clickable: false,
                         string_placements: vec![
                             ContentType::svg(GAME_CONTROLLER).css("svg-[30px]").align_left(),
-                            ContentType::text("由我建立").css("text-black").align_right(),
+                            ContentType::text(t!("collection-created-by-me")).css("text-black").align_right(),
                         ],
                     }
                     Button {
@@ -308,7 +366,7 @@ fn Content(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                         clickable: false,
                         string_placements: vec![
                             ContentType::svg(GAME_CONTROLLER).css("svg-[30px]").align_left(),
-                            ContentType::text("我的錦集").css("text-black").align_right(),
+                            ContentType::text(t!("my-collections")).css("text-black").align_right(),
                         ],
                     }
                 }
@@ -339,112 +397,41 @@ fn Content(collection_id: ReadOnlySignal<CollectionId>) -> Element {
     }
 }
 
-#[component]
-fn Screenshots(
-    collection_id: ReadOnlySignal<CollectionId>,
-    screenshots: Resource<Result<Vec<ScreenShot>, CollectionError>>,
-    search: ReadOnlySignal<String>,
-) -> Element {
-    let read = screenshots.read();
-    let screenshots = match &*read {
-        Some(Ok(x)) => x,
-        Some(Err(err)) => Err(err.to_render_error())?,
-        None => &vec![],
-    };
-
-    let block_height = "310px";
-    let block_width = "390px";
-
-    rsx! {
-        div {
-            class: "rounded-[30px] bg-background px-[30px] pb-[30px] flex flex-col gap-[20px] text-[18px] text-white",
-            div {
-                class: "grid grid-flow-col items-center justify-stretch gap-[10px] px-[20px] my-[10px] h-[70px]",
-                div {
-                    class: "justify-self-start flex items-center gap-[5px] grow w-full font-medium",
-                    div {
-                        class: "w-[100px]",
-                        "檔案名稱",
-                    }
-                    div {
-                        class: "w-full grow text-hint font-display",
-                        "(拍攝日期/檔案大小/圖片大小)"
-                    }
-                }
-                div {
-                    class: "justify-self-end flex w-[180px] items-center self-stretch gap-[5px]",
-                    "依照日期排序"
-                    crate::svgs::ARROW_DOWN {}
-                }
-            }
-            div {
-                class: "grid grid-flow-row gap-[5px]",
-                grid_auto_rows: "{block_height}",
-                grid_auto_columns: "{block_width}",
-                grid_template_columns: "repeat(auto-fill,{block_width})",
-                for screenshot in screenshots {
-                    div {
-                        class: "flex flex-col items-start bg-deep-background rounded-[30px]",
-                        div {
-                            class: "text-white flex justify-start items-center p-[20px] text-[18px] font-english font-bold",
-                            height: "60px",
-                            div {
-                                class: "trim",
-                                "{screenshot.path.file_name().unwrap_or_default().to_string_lossy()}"
-                            }
-                        }
-                        img {
-                            src: "{screenshot.path.to_string_lossy()}",
-                            width: block_width,
-                            height: block_height,
-                            object_fit: "cover"
-                        }
-                        div {
-                            class: "flex gap-[5px] items-center p-[20px] text-secondary font-english text-[15px] font-medium",
-                            height: "50px",
-                            div {
-                                class: "trim",
-                                {screenshot.get_creation_date()?.format("%Y.%m.%d").to_string()}
-                            }
-                            div {
-                                "/"
-                            }
-                            div {
-                                class: "trim",
-                                "{screenshot.get_size()?.to_megabytes():.2} MB"
-                            }
-                            div {
-                                "/"
-                            }
-                            div {
-                                class: "trim",
-                                {
-                                    image::image_dimensions(&screenshot.path).map(|(x,y)| format!("{x}x{y}")).unwrap_or_default()
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-}
-
 #[component]
 pub fn CollectionDisplay(collection_id: ReadOnlySignal<CollectionId>) -> Element {
     let status: Signal<CollectionDisplayTopSelection> =
         use_signal(|| CollectionDisplayTopSelection::Mods);
 
-    let default = CopyValue::new(String::from("搜尋合集中的內容"));
+    let default = CopyValue::new(t!("collection-search"));
     let search = use_signal(|| default.cloned());
 
     let radio = collection_id().use_collection_radio();
 
-    let screenshots = use_resource(move || async move { radio.read().get_screenshots().await });
+    let screenshots = use_resource(move || async move {
+        folder_changes(collection_id(), WatchedFolder::Screenshots);
+        let path = radio
+            .read()
+            .game_directory()
+            .join(WatchedFolder::Screenshots.dir_name());
+        radio.read().get_screenshots().await.map_err(|err| {
+            CapturedError::from(AppError::Folder {
+                path,
+                source: Box::new(err.into()),
+            })
+        })
+    });
+
+    let import = use_import_drop();
 
     rsx! {
+        ImportConfirmation {
+            collection_id,
+            import,
+        }
         div {
             class: "mr-[20px] w-full h-full",
+            ondragover: move |e| e.prevent_default(),
+            ondrop: move |e| import.ondrop(&e),
             ScrollableFootBar {
                 footer: rsx! {
                     Footer {
@@ -475,7 +462,7 @@ pub fn CollectionDisplay(collection_id: ReadOnlySignal<CollectionId>) -> Element
                             }
                             CollectionDisplayTopSelection::World => {
                                 rsx! {
-                                    ModViewer {
+                                    WorldViewer {
                                         collection_id,
                                         default,
                                         search: search(),
@@ -505,7 +492,8 @@ pub fn CollectionDisplay(collection_id: ReadOnlySignal<CollectionId>) -> Element
                                     Screenshots {
                                         collection_id,
                                         screenshots,
-                                        search: search()
+                                        default,
+                                        search: search(),
                                     }
                                 }
                             }
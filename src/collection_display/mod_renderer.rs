@@ -1,6 +1,9 @@
 mod details;
+pub mod table;
+pub mod unmanaged;
+pub mod versions;
 
-use std::ops::Deref;
+use std::{collections::HashMap, ops::Deref, path::PathBuf};
 
 use dioxus::prelude::*;
 use dioxus_logger::tracing::info;
@@ -10,64 +13,107 @@ use rust_lib::api::{
 };
 use tailwind_fuse::tw_merge;
 
+use table::{
+    mod_sizes, ModColumn, ModRow, ModSource, ModTableHeader, ModTableSettings, ModTableToolbar,
+};
+use unmanaged::{use_unmanaged_mods, HashLookupToggle, UnmanagedMod, UnmanagedModRow};
+use versions::{use_update_checker, PinnedMods};
+
 use crate::{
+    app_error::{Recovery, ScopedErrorBoundary},
     collection_display::{CURSEFORGE, DELETE, HORIZ, MODRINTH, UNARCHIVE},
-    use_error_handler,
+    download_progress::SizeFromMegaBytes,
+    persistence::use_collection_persisted,
     BaseComponents::{
         atoms::{
             button::{Button, FillMode, Roundness},
             switch::Switch,
         },
         molecules::search_bar::fuzzy_search,
+        organisms::toast::toast_error,
         string_placements::ContentType,
     },
 };
 
 #[component]
-pub fn GridRow<const T: usize>(
-    items: [Element; T],
+pub fn GridRow(
+    items: Vec<(ModColumn, Element)>,
     #[props(default)] class: String,
     #[props(extends = div, extends = GlobalAttributes)] attributes: Vec<Attribute>,
 ) -> Element {
-    const {
-        assert!(T > 2, "items should have length that is larger than 2");
-    }
     let class = tw_merge!("flex items-center gap-[20px]", class);
     rsx! {
         div {
             class,
             ..attributes,
-            div {
-                class: "grow flex items-center h-full w-full gap-[20px]",
-                div {
-                    class: "flex-none inline-flex justify-center w-[75px]",
-                    {&items[0]}
-                }
+            for (column, item) in items {
                 div {
-                    class: "grow w-full flex items-center gap-[10px]",
-                    div {
-                        class: "grow w-full py-[10px]",
-                        {&items[1]}
-                    }
-                    div {
-                        class: "min-w-[150px] max-w-[150px]",
-                        {&items[2]}
-                    }
-                }
-            }
-            div {
-                class: "flex items-center h-full gap-[10px]",
-                for i in 3..T {
-                    div {
-                        class: "flex-none w-[75px] py-[10px] inline-flex justify-center items-center",
-                        {&items[i]}
-                    }
+                    class: column.cell_class(),
+                    {item}
                 }
             }
         }
     }
 }
 
+/// A row of the mod table, either an entry of the mod manager or a jar it doesn't know about
+#[derive(Clone, PartialEq)]
+enum TableRow {
+    Managed(ModMetadata),
+    Unmanaged(UnmanagedMod),
+}
+
+impl ModRow for TableRow {
+    fn name(&self) -> &str {
+        match self {
+            Self::Managed(x) => ModRow::name(x),
+            Self::Unmanaged(x) => ModRow::name(x),
+        }
+    }
+
+    fn authors(&self) -> String {
+        match self {
+            Self::Managed(x) => ModRow::authors(x),
+            Self::Unmanaged(x) => ModRow::authors(x),
+        }
+    }
+
+    fn enabled(&self) -> bool {
+        match self {
+            Self::Managed(x) => ModRow::enabled(x),
+            Self::Unmanaged(x) => ModRow::enabled(x),
+        }
+    }
+
+    fn source(&self) -> Option<ModSource> {
+        match self {
+            Self::Managed(x) => ModRow::source(x),
+            Self::Unmanaged(x) => ModRow::source(x),
+        }
+    }
+
+    fn last_updated(&self) -> i64 {
+        match self {
+            Self::Managed(x) => ModRow::last_updated(x),
+            Self::Unmanaged(x) => ModRow::last_updated(x),
+        }
+    }
+
+    fn file_size(&self) -> Option<u64> {
+        match self {
+            Self::Managed(x) => ModRow::file_size(x),
+            Self::Unmanaged(x) => ModRow::file_size(x),
+        }
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        match self {
+            Self::Managed(x) => ModRow::path(x),
+            Self::Unmanaged(x) => ModRow::path(x),
+        }
+    }
+}
+
 #[component]
 pub fn ModViewer(
     collection_id: ReadOnlySignal<CollectionId>,
@@ -75,75 +121,79 @@ pub fn ModViewer(
     default: String,
 ) -> Element {
     let radio = collection_id().use_collection_radio();
-    let mods = use_memo(move || {
+    let settings = use_collection_persisted::<ModTableSettings>(collection_id);
+    let pins = use_collection_persisted::<PinnedMods>(collection_id);
+    let unmanaged = use_unmanaged_mods(collection_id);
+    // read again only when the mods change, rather than on every render
+    let sizes = use_resource(move || {
+        let mods = radio
+            .read()
+            .mod_controller()
+            .map(|x| x.manager.mods.clone())
+            .unwrap_or_default();
+        mod_sizes(mods)
+    });
+    let rows = use_memo(move || {
         let value = default.clone();
         let collection = radio.read();
-        let mods = collection
+        let settings = settings.read();
+        let managed = collection
             .mod_controller()
             .into_iter()
-            .flat_map(move |x| x.manager.mods.clone().into_iter());
-        fuzzy_search(&search.read(), &value, mods, |x| &x.name).collect::<Vec<_>>()
+            .flat_map(move |x| x.manager.mods.clone().into_iter())
+            .map(TableRow::Managed);
+        let unmanaged = unmanaged
+            .read()
+            .mods
+            .clone()
+            .into_iter()
+            .map(TableRow::Unmanaged);
+        let rows = managed.chain(unmanaged).filter(|x| settings.retains(x));
+        let mut rows = fuzzy_search(&search.read(), &value, rows, |x| x.name()).collect::<Vec<_>>();
+        settings.sort(&mut rows, sizes.read().as_ref().unwrap_or(&HashMap::new()));
+        rows
     });
+    let columns = use_memo(move || settings.read().visible_columns());
     rsx! {
         div {
             class: "bg-background flex flex-col gap-[20px] rounded-t-[30px] pb-[30px] h-full overflow-x-hidden",
-            GridRow {
-                class: "w-full border-b-[3px] border-b-secondary-surface rounded-t-[30px] h-[70px] px-[50px] py-[10px] backdrop-blur-[7.5px] sticky top-0 z-[2000]",
-                background: "rgba(25, 25, 25, 0.90)",
-                items: [
-                    rsx!(
-                        div {
-                            class: "flex-none inline-flex justify-center w-[80px] text-white text-lg h-full trim",
-                            "圖示"
-                        }
-                    ),
-                    rsx!(
-                        div {
-                            class: "text-white text-lg h-full trim",
-                            "名稱（來源／文件名稱）"
-                        }
-                    ),
-                    rsx!(
-                        div {
-                            class: "text-white text-lg h-full trim",
-                            "作者"
-                        }
-                    ),
-                    rsx!(
-                        div {
-                            class: "text-white text-lg h-full trim",
-                            "更新"
-                        }
-                    ),
-                    rsx!(
-                        div {
-                            class: "text-white text-lg h-full trim",
-                            "刪除"
-                        }
-                    ),
-                    rsx!(
-                        div {
-                            class: "text-white text-lg h-full trim",
-                            "更多"
-                        }
-                    ),
-                    rsx!(
-                        div {
-                            class: "text-white text-lg h-full trim",
-                            "狀態"
-                        }
-                    ),
-                ]
+            ModTableHeader {
+                settings,
+            }
+            ModTableToolbar {
+                settings,
+            }
+            HashLookupToggle {
+                unmanaged,
             }
-
             div {
                 class: "bg-background w-full h-full flex flex-col px-[30px]",
                 div {
                     class: "flex flex-col gap-[5px]",
-                    for ele in mods() {
-                        SubModViewer {
-                            collection_id,
-                            mods: ele
+                    for row in rows() {
+                        if let TableRow::Managed(ele) = row {
+                            ScopedErrorBoundary {
+                                key: "{ModRow::path(&ele).unwrap_or_default().display()}",
+                                class: "p-[20px]",
+                                collection_id: collection_id(),
+                                recoveries: vec![Recovery::DisableMod {
+                                    collection_id: collection_id(),
+                                    name: ele.name.clone(),
+                                }],
+                                SubModViewer {
+                                    collection_id,
+                                    file_size: ModRow::path(&ele).and_then(|x| sizes.read().as_ref()?.get(&x).copied()),
+                                    mods: ele,
+                                    columns: columns(),
+                                    pins,
+                                }
+                            }
+                        } else if let TableRow::Unmanaged(ele) = row {
+                            UnmanagedModRow {
+                                key: "{ele.path.display()}",
+                                mods: ele,
+                                columns: columns(),
+                            }
                         }
                     }
                 }
@@ -158,7 +208,6 @@ fn use_active_controller(
     mods: ReadOnlySignal<ModMetadata>,
 ) {
     let mut radio = collection_id().use_collection_radio();
-    let mut error_handler = use_error_handler();
     let _ = use_resource(move || {
         let clicked = clicked();
         async move {
@@ -191,7 +240,7 @@ fn use_active_controller(
                 Ok(())
             };
             if let Err(x) = binding().await {
-                error_handler.set(Err(x));
+                toast_error(x);
             }
         }
     });
@@ -201,10 +250,23 @@ fn use_active_controller(
 fn SubModViewer(
     collection_id: ReadOnlySignal<CollectionId>,
     mods: ReadOnlySignal<ModMetadata>,
+    /// `None` while it's being read
+    file_size: Option<u64>,
+    columns: Vec<ModColumn>,
+    pins: Signal<PinnedMods>,
 ) -> Element {
-    let clicked = use_signal(|| mods.read().enabled);
+    let mut clicked = use_signal(|| mods.read().enabled);
     let mut dialog = use_signal(|| false);
+    let mut updating = use_signal(|| false);
+    let check_update = use_update_checker(collection_id, pins);
     use_active_controller(clicked, collection_id, mods);
+    // keeps the switch in sync when mods are toggled elsewhere, e.g. by a mod profile
+    use_effect(move || {
+        let enabled = mods.read().enabled;
+        if *clicked.peek() != enabled {
+            clicked.set(enabled);
+        }
+    });
     let icon = rsx!(if let Some(icon) = mods.read().icon_url.as_ref() {
         {
             ContentType::image(icon.to_string()).css("size-[80px] rounded-[15px]")
@@ -220,20 +282,35 @@ fn SubModViewer(
                 class: "text-white text-[28px] font-bold font-english trim",
                 {mods.read().name.clone()}
             }
-            div {
-                class: "w-[40px] bg-background inline-flex items-center justify-center h-[30px] px-[10px] rounded-[30px]",
-                {
-                    ContentType::svg(
-                        match mods.read().platform() {
-                            Platform::Modrinth => MODRINTH,
-                            Platform::Curseforge => CURSEFORGE
-                        })
-                    .get_element()
-                }
+        }
+    );
+    let platform = rsx!(
+        div {
+            class: "w-[40px] bg-background inline-flex items-center justify-center h-[30px] px-[10px] rounded-[30px]",
+            {
+                ContentType::svg(
+                    match mods.read().platform() {
+                        Platform::Modrinth => MODRINTH,
+                        Platform::Curseforge => CURSEFORGE
+                    })
+                .get_element()
             }
         }
     );
-    let file_name = rsx!(
+    let last_updated = rsx!(
+        div {
+            class: "text-[15px] text-secondary-text font-english trim",
+            {mods.read().last_updated.date_naive().format("%Y.%m.%d").to_string()}
+        }
+    );
+    #[allow(clippy::cast_precision_loss)]
+    let file_size = rsx!(
+        div {
+            class: "text-[15px] text-secondary-text font-english trim",
+            {file_size.map(|x| (x as f64).display_size_from_megabytes()).unwrap_or_default()}
+        }
+    );
+    let version = rsx!(
         if let Some(version) = &mods.read().mod_version {
             div {
                 class: "font-medium text-secondary-text text-[15px] font-english trim",
@@ -247,10 +324,28 @@ fn SubModViewer(
             {mods.read().authors.join(", ")}
         }
     );
+    let pinned = pins.read().is_mod_pinned(&mods.read());
     let upgrade = rsx!(Button {
         roundness: Roundness::None,
-        extended_css_class:
-            "flex items-center justify-center bg-background rounded-[15px] h-[60px]",
+        clickable: !pinned && !updating(),
+        onclick: move |()| {
+            if pinned || updating() {
+                return;
+            }
+            updating.set(true);
+            spawn(async move {
+                match check_update(mods()).await {
+                    Ok(updated) => info!("{} updated: {updated}", mods.read().name),
+                    Err(err) => toast_error(err),
+                }
+                updating.set(false);
+            });
+        },
+        extended_css_class: if pinned || updating() {
+            "flex items-center justify-center bg-background rounded-[15px] h-[60px] opacity-30"
+        } else {
+            "flex items-center justify-center bg-background rounded-[15px] h-[60px]"
+        },
         string_placements: vec![ContentType::svg(UNARCHIVE).align_center()],
         fill_mode: FillMode::Fill
     });
@@ -272,30 +367,42 @@ fn SubModViewer(
         fill_mode: FillMode::Fill
     });
     let status = rsx!(Switch { clicked });
+    let name = rsx!(
+        div {
+            class: "flex flex-col justify-center gap-[15px]",
+            {name}
+            {version}
+        }
+    );
+    let items = columns
+        .into_iter()
+        .map(|column| {
+            let element = match column {
+                ModColumn::Icon => icon.clone(),
+                ModColumn::Name => name.clone(),
+                ModColumn::Author => author.clone(),
+                ModColumn::Platform => platform.clone(),
+                ModColumn::LastUpdated => last_updated.clone(),
+                ModColumn::FileSize => file_size.clone(),
+                ModColumn::Update => upgrade.clone(),
+                ModColumn::Delete => delete.clone(),
+                ModColumn::More => more.clone(),
+                ModColumn::Status => status.clone(),
+            };
+            (column, element)
+        })
+        .collect::<Vec<_>>();
     rsx! {
         details::ModDetails {
             mods,
             active: dialog,
             clicked,
             collection_id,
+            pins,
         }
         GridRow {
             class: "bg-deep-background items-center rounded-[20px] p-[20px]",
-            items: [
-                icon,
-                rsx!(
-                    div {
-                        class: "flex flex-col justify-center gap-[15px]",
-                        {name}
-                        {file_name}
-                    }
-                ),
-                author,
-                upgrade,
-                delete,
-                more,
-                status
-            ]
+            items,
         }
     }
 }
@@ -6,15 +6,22 @@ use rust_lib::api::{
 };
 
 use crate::{
-    collection_display::mod_renderer::use_active_controller,
+    collection_display::mod_renderer::{
+        table::ModSource,
+        use_active_controller,
+        versions::{game_target, use_source_switcher, PinnedMods, VersionHistory},
+    },
+    description::Description,
+    platform_api::{latest_compatible, PlatformApiError, RemoteProject, RemoteVersion},
     svgs::{CURSEFORGE_OUTLINE, MODRINTH_OUTLINE},
+    t,
+    translation::TRANSLATION_CONFIG,
     BaseComponents::{
         atoms::{
             center::Center,
-            markdown_to_html,
             switch::{self, FloatingSwitch, Switch},
         },
-        organisms::{markdown_renderer::RenderTranslatedMarkdown, modal::Modal},
+        organisms::{modal::Modal, toast::toast_error},
     },
 };
 
@@ -43,6 +50,30 @@ fn PlatformSwitch(platform: Signal<switch::State>) -> Element {
     }
 }
 
+const fn state_to_source(state: switch::State) -> ModSource {
+    match state {
+        switch::State::Left => ModSource::Curseforge,
+        switch::State::Right => ModSource::Modrinth,
+    }
+}
+
+const fn source_to_state(source: ModSource) -> switch::State {
+    match source {
+        ModSource::Curseforge => switch::State::Left,
+        ModSource::Modrinth => switch::State::Right,
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_download_count(count: u64) -> String {
+    match count as f64 {
+        f if f < 1_000. => format!("{count}"),
+        f if f < 1_000_000. => format!("{:.1}K", f / 1_000.),
+        f if f < 1_000_000_000. => format!("{:.1}M", f / 1_000_000.),
+        f => format!("{:.1}B", f / 1_000_000_000.),
+    }
+}
+
 #[component]
 fn Authors(mods: ReadOnlySignal<ModMetadata>) -> Element {
     rsx! {
@@ -60,7 +91,7 @@ fn Authors(mods: ReadOnlySignal<ModMetadata>) -> Element {
 }
 
 #[component]
-fn DownloadCount(mods: ReadOnlySignal<ModMetadata>) -> Element {
+fn DownloadCount(mods: ReadOnlySignal<ModMetadata>, remote: Option<RemoteProject>) -> Element {
     rsx! {
         div {
             class: "flex w-fit justify-start gap-[7px] items-center",
@@ -69,14 +100,20 @@ fn DownloadCount(mods: ReadOnlySignal<ModMetadata>) -> Element {
             }
             div {
                 class: "text-[15px] font-english font-medium text-hint trim",
-                {mods.read().get_formatted_download_count()}
+                {
+                    remote.map_or_else(
+                        || mods.read().get_formatted_download_count(),
+                        |x| format_download_count(x.downloads),
+                    )
+                }
             }
         }
     }
 }
 
 #[component]
-fn LastUpdated(mods: ReadOnlySignal<ModMetadata>) -> Element {
+fn LastUpdated(mods: ReadOnlySignal<ModMetadata>, remote: Option<RemoteProject>) -> Element {
+    let last_updated = remote.map_or_else(|| mods.read().last_updated, |x| x.last_updated);
     rsx! {
         div {
             class: "flex w-fit justify-start gap-[7px] items-center",
@@ -85,7 +122,7 @@ fn LastUpdated(mods: ReadOnlySignal<ModMetadata>) -> Element {
             }
             div {
                 class: "text-[15px] font-english font-medium text-hint trim",
-                {mods.read().last_updated.date_naive().format("%Y.%m.%d").to_string()}
+                {last_updated.date_naive().format("%Y.%m.%d").to_string()}
             }
         }
     }
@@ -117,10 +154,10 @@ fn SupportedSideDisplay(mods: ReadOnlySignal<ModMetadata>) -> Element {
         let client = sides.contains(&SupportedSide::Client);
         let server = sides.contains(&SupportedSide::Server);
         match (client, server) {
-            (true, true) => "客戶端 & 伺服端",
-            (true, false) => "客戶端",
-            (false, true) => "伺服端",
-            (false, false) => "未知",
+            (true, true) => t!("mod-side-both"),
+            (true, false) => t!("mod-side-client"),
+            (false, true) => t!("mod-side-server"),
+            (false, false) => t!("mod-side-unknown"),
         }
     });
 
@@ -138,16 +175,153 @@ fn SupportedSideDisplay(mods: ReadOnlySignal<ModMetadata>) -> Element {
     }
 }
 
+#[component]
+fn LatestVersion(versions: Vec<RemoteVersion>) -> Element {
+    rsx! {
+        div {
+            class: "flex w-fit justify-start gap-[7px] items-center",
+            div {
+                class: "bg-white size-[20px]",
+            }
+            div {
+                class: "text-[15px] font-english font-medium text-hint trim",
+                if let Some(latest) = versions.first() {
+                    {t!("mod-versions-summary", count = versions.len(), latest = latest.version_number.clone())}
+                }
+            }
+        }
+    }
+}
+
+/// Moves `mods` over to `version`, which comes from the other platform.
+#[component]
+fn SourceSwitcher(
+    mods: ReadOnlySignal<ModMetadata>,
+    collection_id: ReadOnlySignal<CollectionId>,
+    active: Signal<bool>,
+    version: ReadOnlySignal<RemoteVersion>,
+) -> Element {
+    let switch_source = use_source_switcher(collection_id);
+    let mut switching = use_signal(|| false);
+    rsx! {
+        button {
+            class: "h-[60px] px-[20px] rounded-[20px] bg-white text-black font-bold text-[18px] aria-busy:opacity-50 trim",
+            aria_busy: switching(),
+            disabled: switching(),
+            onclick: move |_| async move {
+                switching.set(true);
+                match switch_source(mods(), version()).await {
+                    Ok(()) => active.set(false),
+                    Err(err) => toast_error(err),
+                }
+                switching.set(false);
+            },
+            {t!("mod-switch-source", platform = version.read().source.title())}
+        }
+    }
+}
+
+#[component]
+fn TranslateButton(translate: Signal<bool>) -> Element {
+    rsx! {
+        button {
+            class: "h-[60px] px-[20px] rounded-[20px] bg-deep-background text-hint font-bold text-[18px] aria-pressed:bg-white aria-pressed:text-black trim",
+            aria_pressed: translate(),
+            onclick: move |_| translate.toggle(),
+            if translate() {
+                {t!("mod-show-original")}
+            } else {
+                {t!("mod-translate")}
+            }
+        }
+    }
+}
+
+#[component]
+fn PinButton(mods: ReadOnlySignal<ModMetadata>, pins: Signal<PinnedMods>) -> Element {
+    let project =
+        use_resource(
+            move || async move { ModSource::installed_project(&mods()).await.map(|x| x.id) },
+        );
+    let project_id = project
+        .read()
+        .as_ref()
+        .and_then(|x| x.as_ref().ok())
+        .cloned();
+    let pinned = project_id
+        .as_ref()
+        .is_some_and(|x| pins.read().is_pinned(x));
+    rsx! {
+        button {
+            class: "h-[60px] px-[20px] rounded-[20px] bg-deep-background text-hint font-bold text-[18px] aria-pressed:bg-white aria-pressed:text-black disabled:opacity-50 trim",
+            aria_pressed: pinned,
+            // only mods found on their platform can be told apart from others with the same name
+            disabled: project_id.is_none(),
+            onclick: move |_| {
+                if let Some(id) = &project_id {
+                    pins.write().toggle(id);
+                }
+            },
+            if pinned {
+                {t!("mod-pinned")}
+            } else {
+                {t!("mod-pin")}
+            }
+        }
+    }
+}
+
 #[component]
 fn InnerModDetails(
     mods: ReadOnlySignal<ModMetadata>,
     active: Signal<bool>,
     clicked: Signal<bool>,
     collection_id: ReadOnlySignal<CollectionId>,
+    pins: Signal<PinnedMods>,
 ) -> Element {
     use_active_controller(clicked, collection_id, mods);
 
-    let description = markdown_to_html(&mods.read().long_description);
+    let radio = collection_id().use_collection_radio();
+
+    let translate = use_signal(|| false);
+
+    let installed_source = ModSource::of(&mods.read());
+    let platform = use_signal(|| source_to_state(installed_source));
+
+    let remote = use_resource(move || async move {
+        let source = state_to_source(platform());
+        let mods = mods();
+        let project = source.project_of(&mods).await?;
+        let versions = source.versions(&project.id).await?;
+        Ok::<_, PlatformApiError>((project, versions))
+    });
+
+    let selected_source = state_to_source(platform());
+    let binding = remote.read();
+    let (remote_project, remote_versions) = match &*binding {
+        Some(Ok((project, versions))) => (Some(project.clone()), versions.clone()),
+        _ => (None, Vec::new()),
+    };
+    let not_found = matches!(
+        &*binding,
+        Some(Err(PlatformApiError::ProjectNotFound { .. }))
+    );
+    drop(binding);
+
+    let compatible_version = (selected_source != installed_source)
+        .then(|| {
+            let (game_version, loader) = game_target(&radio.read());
+            latest_compatible(&remote_versions, &game_version, loader.as_deref()).cloned()
+        })
+        .flatten();
+
+    let description_source = remote_project
+        .as_ref()
+        .map_or(installed_source, |x| x.source);
+    let description = remote_project.as_ref().map_or_else(
+        || mods.read().long_description.clone(),
+        |x| x.description.clone(),
+    );
 
     // if description.contains("Welcome to Create") {
     //     println!("{description}");
@@ -162,8 +336,6 @@ fn InnerModDetails(
         .map(|x| x.display().to_string())
         .join("\n");
 
-    let platform = use_signal(|| switch::State::Left);
-
     rsx! {
         div {
             class: "flex flex-col w-full bg-background",
@@ -192,6 +364,23 @@ fn InnerModDetails(
                 }
                 div {
                     class: "justify-self-end flex gap-[10px]",
+                    if let Some(version) = compatible_version {
+                        SourceSwitcher {
+                            mods,
+                            collection_id,
+                            active,
+                            version,
+                        }
+                    }
+                    if TRANSLATION_CONFIG.read().backend.translator().is_some() {
+                        TranslateButton {
+                            translate,
+                        }
+                    }
+                    PinButton {
+                        mods,
+                        pins,
+                    }
                     PlatformSwitch {
                         platform,
                     }
@@ -208,10 +397,14 @@ fn InnerModDetails(
                 }
                 DownloadCount {
                     mods,
+                    remote: remote_project.clone(),
                 }
-
                 LastUpdated {
                     mods,
+                    remote: remote_project.clone(),
+                }
+                LatestVersion {
+                    versions: remote_versions.clone(),
                 }
                 TotalSize {
                     mods,
@@ -220,8 +413,24 @@ fn InnerModDetails(
                     mods,
                 }
             }
-            RenderTranslatedMarkdown {
-                html: description,
+            if not_found {
+                div {
+                    class: "px-[30px] py-[10px] text-[15px] font-medium text-hint",
+                    {t!("mod-not-on-platform", platform = selected_source.title())}
+                }
+            }
+            Description {
+                source: description,
+                bbcode: description_source == ModSource::Curseforge,
+                translate,
+            }
+            if !remote_versions.is_empty() {
+                VersionHistory {
+                    mods,
+                    collection_id,
+                    active,
+                    versions: remote_versions,
+                }
             }
         }
     }
@@ -233,6 +442,7 @@ pub fn ModDetails(
     active: Signal<bool>,
     clicked: Signal<bool>,
     collection_id: ReadOnlySignal<CollectionId>,
+    pins: Signal<PinnedMods>,
 ) -> Element {
     rsx! {
         Modal {
@@ -246,6 +456,7 @@ pub fn ModDetails(
                         active,
                         clicked,
                         collection_id,
+                        pins,
                     }
                 }
             }
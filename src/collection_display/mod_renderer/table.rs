@@ -0,0 +1,391 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, HashMap},
+    path::PathBuf,
+};
+
+use dioxus::prelude::*;
+use itertools::Itertools;
+use rust_lib::api::backend_exclusive::mod_management::mods::{ModMetadata, Platform};
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+use crate::{
+    collection_display::mod_renderer::GridRow, persistence::Persisted, t,
+    BaseComponents::molecules::context_menu::DropDown,
+};
+
+/// Every column `ModViewer` knows how to render, in display order
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, EnumIter,
+)]
+pub enum ModColumn {
+    Icon,
+    Name,
+    Author,
+    Platform,
+    LastUpdated,
+    FileSize,
+    Update,
+    Delete,
+    More,
+    Status,
+}
+
+impl ModColumn {
+    #[must_use]
+    pub fn title(self) -> String {
+        match self {
+            Self::Icon => t!("mod-column-icon"),
+            Self::Name => t!("mod-column-name"),
+            Self::Author => t!("mod-column-author"),
+            Self::Platform => t!("mod-column-platform"),
+            Self::LastUpdated => t!("mod-column-last-updated"),
+            Self::FileSize => t!("mod-column-file-size"),
+            Self::Update => t!("mod-column-update"),
+            Self::Delete => t!("mod-column-delete"),
+            Self::More => t!("mod-column-more"),
+            Self::Status => t!("mod-column-status"),
+        }
+    }
+
+    /// Columns that can be clicked on to sort the table
+    #[must_use]
+    pub const fn sortable(self) -> bool {
+        matches!(
+            self,
+            Self::Name
+                | Self::Author
+                | Self::Platform
+                | Self::LastUpdated
+                | Self::FileSize
+                | Self::Status
+        )
+    }
+
+    /// `Name` is the anchor of each row, so it can never be hidden
+    #[must_use]
+    pub const fn hideable(self) -> bool {
+        !matches!(self, Self::Name)
+    }
+
+    #[must_use]
+    pub const fn cell_class(self) -> &'static str {
+        match self {
+            Self::Icon => "flex-none inline-flex justify-center w-[75px]",
+            Self::Name => "grow w-full py-[10px]",
+            Self::Author => "flex-none min-w-[150px] max-w-[150px]",
+            Self::Platform => "flex-none w-[75px] inline-flex justify-center items-center",
+            Self::LastUpdated | Self::FileSize => "flex-none w-[120px]",
+            Self::Update | Self::Delete | Self::More | Self::Status => {
+                "flex-none w-[75px] py-[10px] inline-flex justify-center items-center"
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
+pub enum EnabledFilter {
+    #[default]
+    All,
+    Enabled,
+    Disabled,
+}
+
+impl EnabledFilter {
+    #[must_use]
+    pub fn title(self) -> String {
+        match self {
+            Self::All => t!("mod-filter-all"),
+            Self::Enabled => t!("mod-filter-enabled"),
+            Self::Disabled => t!("mod-filter-disabled"),
+        }
+    }
+}
+
+/// Serializable mirror of [`Platform`]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, EnumIter,
+)]
+pub enum ModSource {
+    Modrinth,
+    Curseforge,
+}
+
+impl ModSource {
+    #[must_use]
+    pub fn of(mods: &ModMetadata) -> Self {
+        match mods.platform() {
+            Platform::Modrinth => Self::Modrinth,
+            Platform::Curseforge => Self::Curseforge,
+        }
+    }
+
+    #[must_use]
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::Modrinth => "Modrinth",
+            Self::Curseforge => "CurseForge",
+        }
+    }
+}
+
+/// What the table filters and sorts rows by, so jars the mod manager doesn't know about are ordered
+/// alongside its entries
+pub trait ModRow {
+    fn name(&self) -> &str;
+    fn authors(&self) -> String;
+    fn enabled(&self) -> bool;
+    /// `None` when the mod couldn't be traced back to a platform
+    fn source(&self) -> Option<ModSource>;
+    /// Unix timestamp
+    fn last_updated(&self) -> i64;
+    /// In bytes, `None` when it has to be read from the disk, see [`mod_sizes`]
+    fn file_size(&self) -> Option<u64>;
+    /// The file of the mod, `None` for an entry without one
+    fn path(&self) -> Option<PathBuf>;
+}
+
+impl ModRow for ModMetadata {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn authors(&self) -> String {
+        self.authors.join(", ")
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn source(&self) -> Option<ModSource> {
+        Some(ModSource::of(self))
+    }
+
+    fn last_updated(&self) -> i64 {
+        self.last_updated.timestamp()
+    }
+
+    fn file_size(&self) -> Option<u64> {
+        None
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        self.get_filepaths().into_iter().flatten().next()
+    }
+}
+
+/// Sorting, column visibility and filters of a collection's mod table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModTableSettings {
+    /// `None` keeps the fuzzy search order
+    pub sort_by: Option<ModColumn>,
+    pub descending: bool,
+    pub hidden_columns: BTreeSet<ModColumn>,
+    pub enabled_filter: EnabledFilter,
+    pub hidden_sources: BTreeSet<ModSource>,
+}
+
+impl Persisted for ModTableSettings {
+    const FILE_NAME: &'static str = "mod_table.json";
+}
+
+impl ModTableSettings {
+    #[must_use]
+    pub fn visible_columns(&self) -> Vec<ModColumn> {
+        ModColumn::iter()
+            .filter(|x| !self.hidden_columns.contains(x))
+            .collect()
+    }
+
+    /// Sorts by `column`, or flips the direction when it is already the sorted column
+    pub fn toggle_sort(&mut self, column: ModColumn) {
+        if self.sort_by == Some(column) {
+            self.descending = !self.descending;
+        } else {
+            self.sort_by = Some(column);
+            self.descending = false;
+        }
+    }
+
+    pub fn toggle_column(&mut self, column: ModColumn) {
+        if !self.hidden_columns.remove(&column) && column.hideable() {
+            self.hidden_columns.insert(column);
+        }
+    }
+
+    pub fn toggle_source(&mut self, source: ModSource) {
+        if !self.hidden_sources.remove(&source) {
+            self.hidden_sources.insert(source);
+        }
+    }
+
+    #[must_use]
+    pub fn retains(&self, mods: &impl ModRow) -> bool {
+        let enabled = match self.enabled_filter {
+            EnabledFilter::All => true,
+            EnabledFilter::Enabled => mods.enabled(),
+            EnabledFilter::Disabled => !mods.enabled(),
+        };
+        enabled
+            && mods
+                .source()
+                .map_or(true, |x| !self.hidden_sources.contains(&x))
+    }
+
+    /// Stable sort, so mods that compare equal keep their search ranking.
+    ///
+    /// Keys are computed once per mod, the sizes the disk has to be read for come from `sizes`.
+    pub fn sort<T: ModRow>(&self, mods: &mut [T], sizes: &HashMap<PathBuf, u64>) {
+        let Some(column) = self.sort_by else {
+            return;
+        };
+        let key: fn(&T, &HashMap<PathBuf, u64>) -> SortKey = match column {
+            ModColumn::Name => |x, _| SortKey::Text(x.name().to_lowercase()),
+            ModColumn::Author => |x, _| SortKey::Text(x.authors().to_lowercase()),
+            ModColumn::Platform => |x, _| SortKey::Source(x.source()),
+            ModColumn::LastUpdated => |x, _| SortKey::Time(x.last_updated()),
+            ModColumn::FileSize => |x, sizes| {
+                SortKey::Size(
+                    x.file_size()
+                        .or_else(|| sizes.get(&x.path()?).copied())
+                        .unwrap_or_default(),
+                )
+            },
+            ModColumn::Status => |x, _| SortKey::Flag(x.enabled()),
+            ModColumn::Icon | ModColumn::Update | ModColumn::Delete | ModColumn::More => return,
+        };
+        if self.descending {
+            mods.sort_by_cached_key(|x| Reverse(key(x, sizes)));
+        } else {
+            mods.sort_by_cached_key(|x| key(x, sizes));
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum SortKey {
+    Text(String),
+    Source(Option<ModSource>),
+    Time(i64),
+    Size(u64),
+    Flag(bool),
+}
+
+/// Accumulated size in bytes of every file that belongs to each of `mods`, by [`ModRow::path`]
+pub async fn mod_sizes(mods: Vec<ModMetadata>) -> HashMap<PathBuf, u64> {
+    let mut sizes = HashMap::new();
+    for x in mods {
+        let Some(path) = ModRow::path(&x) else {
+            continue;
+        };
+        let mut size = 0;
+        for file in x.get_filepaths().into_iter().flatten() {
+            size += tokio::fs::metadata(file).await.map_or(0, |x| x.len());
+        }
+        sizes.insert(path, size);
+    }
+    sizes
+}
+
+#[component]
+pub fn ModTableHeader(settings: Signal<ModTableSettings>) -> Element {
+    let items = settings
+        .read()
+        .visible_columns()
+        .into_iter()
+        .map(|column| {
+            let sorted = settings.read().sort_by == Some(column);
+            let descending = settings.read().descending;
+            let element = rsx! {
+                div {
+                    class: "flex items-center gap-[5px] text-white text-lg h-full",
+                    role: if column.sortable() { "button" } else { "" },
+                    aria_selected: sorted,
+                    onclick: move |_| {
+                        if column.sortable() {
+                            settings.write().toggle_sort(column);
+                        }
+                    },
+                    div {
+                        class: "trim",
+                        {column.title()}
+                    }
+                    if sorted {
+                        crate::svgs::ARROW_DOWN {
+                            class: if descending { "" } else { "rotate-180" },
+                        }
+                    }
+                }
+            };
+            (column, element)
+        })
+        .collect_vec();
+    rsx! {
+        GridRow {
+            class: "w-full border-b-[3px] border-b-secondary-surface rounded-t-[30px] h-[70px] px-[50px] py-[10px] backdrop-blur-[7.5px] sticky top-0 z-[2000]",
+            background: "rgb(var(--color-background) / 0.90)",
+            items,
+        }
+    }
+}
+
+#[component]
+fn Chip(selected: bool, onclick: EventHandler, children: Element) -> Element {
+    rsx! {
+        button {
+            class: "px-[20px] py-[10px] rounded-full bg-deep-background text-hint font-medium text-[16px] transition-all aria-selected:bg-white aria-selected:text-black",
+            aria_selected: selected,
+            onclick: move |_| onclick(()),
+            {children}
+        }
+    }
+}
+
+#[component]
+pub fn ModTableToolbar(settings: Signal<ModTableSettings>) -> Element {
+    let selector_visibility = use_signal(|| false);
+    rsx! {
+        div {
+            class: "flex items-center gap-[10px] px-[30px]",
+            for filter in EnabledFilter::iter() {
+                Chip {
+                    selected: settings.read().enabled_filter == filter,
+                    onclick: move |()| {
+                        settings.write().enabled_filter = filter;
+                    },
+                    {filter.title()}
+                }
+            }
+            div {
+                class: "w-[3px] h-[30px] bg-secondary-surface rounded-full",
+            }
+            for source in ModSource::iter() {
+                Chip {
+                    selected: !settings.read().hidden_sources.contains(&source),
+                    onclick: move |()| {
+                        settings.write().toggle_source(source);
+                    },
+                    {source.title()}
+                }
+            }
+            DropDown {
+                class: "ml-auto max-w-[240px] h-[45px]",
+                base: rsx!({t!("mod-table-columns")}),
+                selector_visibility,
+                for column in ModColumn::iter().filter(|x| x.hideable()) {
+                    div {
+                        class: "text-[18px] text-hint aria-selected:text-white trim",
+                        aria_selected: !settings.read().hidden_columns.contains(&column),
+                        onclick: move |_| {
+                            settings.write().toggle_column(column);
+                        },
+                        {column.title()}
+                    }
+                }
+            }
+        }
+    }
+}
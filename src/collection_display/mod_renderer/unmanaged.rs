@@ -0,0 +1,680 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::Read,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use dioxus::prelude::*;
+use dioxus_logger::tracing::{info, warn};
+use rust_lib::api::{
+    backend_exclusive::mod_management::mods::ModMetadata,
+    shared_resources::collection::{Collection, CollectionId},
+};
+use serde::{Deserialize, Serialize};
+use snafu::prelude::*;
+
+use super::{
+    table::{ModColumn, ModRow},
+    GridRow,
+};
+use crate::{
+    collection_display::{mod_renderer::table::ModSource, CURSEFORGE, DELETE, MODRINTH, UNARCHIVE},
+    download_progress::SizeFromMegaBytes,
+    persistence::{use_collection_persisted, Persisted},
+    platform_api::identify_file,
+    t,
+    watcher::{folder_changes, WatchedFolder},
+    BaseComponents::{
+        atoms::{
+            button::{Button, FillMode, Roundness},
+            switch::Switch,
+        },
+        organisms::toast::toast_error,
+        string_placements::ContentType,
+    },
+};
+
+#[derive(Snafu, Debug)]
+pub enum ReconcileError {
+    #[snafu(display("Failed to read {}", path.display()))]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("{} is not a valid jar", path.display()))]
+    Zip {
+        path: PathBuf,
+        source: zip::result::ZipError,
+    },
+    #[snafu(display("Failed to move {}", path.display()))]
+    Rename {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Failed to delete {}", path.display()))]
+    Delete {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Metadata reading panicked"))]
+    Join { source: tokio::task::JoinError },
+}
+
+/// A jar in the mods folder the mod manager doesn't know about, e.g. one copied in by hand
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnmanagedMod {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    /// From the jar's metadata, or its file name if it has none
+    pub name: String,
+    pub version: Option<String>,
+    pub authors: Vec<String>,
+    /// Found by looking up the file's hash
+    pub source: Option<ModSource>,
+    pub project_id: Option<String>,
+    /// The version the file has been published as, the mod manager entry is made for it
+    #[serde(default)]
+    pub version_id: Option<String>,
+    /// Whether the hash has been looked up, so jars read while the lookup was off are looked up once it's on
+    #[serde(default)]
+    pub looked_up: bool,
+}
+
+impl ModRow for UnmanagedMod {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn authors(&self) -> String {
+        self.authors.join(", ")
+    }
+
+    fn enabled(&self) -> bool {
+        !self.path.to_string_lossy().ends_with(".disabled")
+    }
+
+    fn source(&self) -> Option<ModSource> {
+        self.source
+    }
+
+    fn last_updated(&self) -> i64 {
+        self.modified
+            .map(|x| chrono::DateTime::<chrono::Utc>::from(x).timestamp())
+            .unwrap_or_default()
+    }
+
+    fn file_size(&self) -> Option<u64> {
+        Some(self.size)
+    }
+
+    fn path(&self) -> Option<PathBuf> {
+        Some(self.path.clone())
+    }
+}
+
+/// Remembers what has been read from the jars that couldn't be handed over to the mod manager
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnmanagedMods {
+    /// Whether unknown jars are looked up on Modrinth and CurseForge, off until asked for since it
+    /// sends their hashes to both
+    pub lookup_hashes: bool,
+    pub mods: Vec<UnmanagedMod>,
+}
+
+impl Persisted for UnmanagedMods {
+    const FILE_NAME: &'static str = "unmanaged_mods.json";
+}
+
+#[derive(Debug, Default)]
+struct JarMetadata {
+    name: Option<String>,
+    version: Option<String>,
+    authors: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct FabricModJson {
+    id: String,
+    name: Option<String>,
+    version: String,
+    #[serde(default)]
+    authors: Vec<FabricPerson>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FabricPerson {
+    Name(String),
+    Detailed { name: String },
+}
+
+#[derive(Deserialize)]
+struct QuiltModJson {
+    quilt_loader: QuiltLoader,
+}
+
+#[derive(Deserialize)]
+struct QuiltLoader {
+    id: String,
+    version: String,
+    #[serde(default)]
+    metadata: QuiltMetadata,
+}
+
+#[derive(Deserialize, Default)]
+struct QuiltMetadata {
+    name: Option<String>,
+    /// name -> role
+    #[serde(default)]
+    contributors: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct ForgeModsToml {
+    mods: Vec<ForgeMod>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ForgeMod {
+    mod_id: String,
+    display_name: Option<String>,
+    version: Option<String>,
+    authors: Option<String>,
+}
+
+fn read_entry<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+) -> Option<String> {
+    let mut content = String::new();
+    archive
+        .by_name(name)
+        .ok()?
+        .read_to_string(&mut content)
+        .ok()?;
+    Some(content)
+}
+
+/// Reads the name, version and authors out of a fabric, quilt, forge or neoforge jar
+fn read_jar(path: &Path) -> Result<JarMetadata, ReconcileError> {
+    let file = std::fs::File::open(path).context(ReadSnafu { path })?;
+    let mut archive = zip::ZipArchive::new(file).context(ZipSnafu { path })?;
+
+    if let Some(x) = read_entry(&mut archive, "fabric.mod.json")
+        .and_then(|x| serde_json::from_str::<FabricModJson>(&x).ok())
+    {
+        return Ok(JarMetadata {
+            name: Some(x.name.unwrap_or(x.id)),
+            version: Some(x.version),
+            authors: x
+                .authors
+                .into_iter()
+                .map(|x| match x {
+                    FabricPerson::Name(name) | FabricPerson::Detailed { name } => name,
+                })
+                .collect(),
+        });
+    }
+
+    if let Some(x) = read_entry(&mut archive, "quilt.mod.json")
+        .and_then(|x| serde_json::from_str::<QuiltModJson>(&x).ok())
+    {
+        let loader = x.quilt_loader;
+        return Ok(JarMetadata {
+            name: Some(loader.metadata.name.unwrap_or(loader.id)),
+            version: Some(loader.version),
+            authors: loader.metadata.contributors.into_keys().collect(),
+        });
+    }
+
+    let forge = ["META-INF/mods.toml", "META-INF/neoforge.mods.toml"]
+        .into_iter()
+        .find_map(|x| read_entry(&mut archive, x))
+        .and_then(|x| toml::from_str::<ForgeModsToml>(&x).ok())
+        .and_then(|x| x.mods.into_iter().next());
+    if let Some(x) = forge {
+        // forge jars usually take the version from the manifest
+        let version = match x.version {
+            Some(version) if version.starts_with("${") => {
+                read_entry(&mut archive, "META-INF/MANIFEST.MF").and_then(|x| {
+                    x.lines()
+                        .find_map(|x| x.strip_prefix("Implementation-Version:"))
+                        .map(|x| x.trim().to_owned())
+                })
+            }
+            version => version,
+        };
+        return Ok(JarMetadata {
+            name: Some(x.display_name.unwrap_or(x.mod_id)),
+            version,
+            authors: x
+                .authors
+                .map(|x| x.split(',').map(|x| x.trim().to_owned()).collect())
+                .unwrap_or_default(),
+        });
+    }
+
+    Ok(JarMetadata::default())
+}
+
+/// `path` without the `.disabled` suffix the mod manager appends to disabled mods
+fn enabled_path(path: &Path) -> PathBuf {
+    let path = path.to_string_lossy();
+    PathBuf::from(path.strip_suffix(".disabled").unwrap_or(&path))
+}
+
+fn disabled_path(path: &Path) -> PathBuf {
+    let mut path = enabled_path(path).into_os_string();
+    path.push(".disabled");
+    PathBuf::from(path)
+}
+
+/// Lists the jars in `mods_dir` that aren't in `managed`.
+///
+/// Jars that haven't changed since `previous` was computed are not read again, unless they still have to
+/// be looked up.
+///
+/// # Errors
+///
+/// This function will return an error if the folder cannot be read.
+pub async fn reconcile(
+    mods_dir: PathBuf,
+    managed: BTreeSet<PathBuf>,
+    previous: Vec<UnmanagedMod>,
+    lookup_hashes: bool,
+) -> Result<Vec<UnmanagedMod>, ReconcileError> {
+    let managed = managed
+        .iter()
+        .map(|x| enabled_path(x))
+        .collect::<BTreeSet<_>>();
+    let mut entries = match tokio::fs::read_dir(&mods_dir).await {
+        Ok(x) => x,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => {
+            return Err(ReconcileError::Read {
+                path: mods_dir,
+                source,
+            })
+        }
+    };
+
+    let mut unmanaged = Vec::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .context(ReadSnafu { path: &mods_dir })?
+    {
+        let path = entry.path();
+        let enabled = enabled_path(&path);
+        if enabled.extension().map_or(true, |x| x != "jar") || managed.contains(&enabled) {
+            continue;
+        }
+        let metadata = entry.metadata().await.context(ReadSnafu { path: &path })?;
+        let modified = metadata.modified().ok();
+        if let Some(x) = previous.iter().find(|x| {
+            x.path == path
+                && x.size == metadata.len()
+                && x.modified == modified
+                && (x.looked_up || !lookup_hashes)
+                // found before the version was kept, looked up again so it can be adopted
+                && (x.project_id.is_none() || x.version_id.is_some())
+        }) {
+            unmanaged.push(x.clone());
+            continue;
+        }
+
+        let jar = {
+            let path = path.clone();
+            tokio::task::spawn_blocking(move || read_jar(&path))
+                .await
+                .context(JoinSnafu)?
+        };
+        let jar = jar.inspect_err(|err| warn!("{err}")).unwrap_or_default();
+
+        let identified = if lookup_hashes {
+            let bytes = tokio::fs::read(&path)
+                .await
+                .context(ReadSnafu { path: &path })?;
+            identify_file(&bytes)
+                .await
+                .inspect_err(|err| warn!("{err}"))
+                .ok()
+        } else {
+            None
+        };
+
+        info!("Found unmanaged mod {}", path.display());
+        unmanaged.push(UnmanagedMod {
+            name: jar.name.unwrap_or_else(|| {
+                enabled
+                    .file_stem()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string()
+            }),
+            version: jar.version,
+            authors: jar.authors,
+            looked_up: identified.is_some(),
+            source: identified
+                .as_ref()
+                .and_then(|x| x.as_ref().map(|x| x.source)),
+            project_id: identified
+                .as_ref()
+                .and_then(|x| x.as_ref().map(|x| x.project_id.clone())),
+            version_id: identified.flatten().map(|x| x.id),
+            size: metadata.len(),
+            modified,
+            path,
+        });
+    }
+    unmanaged.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    Ok(unmanaged)
+}
+
+/// Makes the mod manager entry of the version `version_id` of the project `project_id`, with the jar at
+/// `path` as its file.
+///
+/// The entry is made on a copy of `collection`, hand it to [`push_to_manager`] inside `with_mut` so edits
+/// made to the collection in the meantime are kept. The jar is moved to where the entry expects its file,
+/// so it isn't downloaded again.
+///
+/// # Errors
+///
+/// This function will return an error if the project cannot be added or the jar cannot be moved.
+pub async fn add_to_manager(
+    collection: &Collection,
+    path: &Path,
+    source: ModSource,
+    project_id: &str,
+    version_id: &str,
+) -> anyhow::Result<ModMetadata> {
+    let mut scratch = collection.clone();
+    let before = collection
+        .mod_controller()
+        .map(|x| x.manager.mods.clone())
+        .unwrap_or_default();
+    match source {
+        ModSource::Modrinth => {
+            scratch
+                .add_modrinth_mod(
+                    project_id.to_owned(),
+                    Vec::new(),
+                    Some(version_id.to_owned()),
+                )
+                .await?;
+        }
+        ModSource::Curseforge => {
+            scratch
+                .add_curseforge_mod(project_id.parse()?, Vec::new(), Some(version_id.parse()?))
+                .await?;
+        }
+    }
+    let Some(entry) = scratch
+        .mod_controller
+        .and_then(|x| x.manager.mods.into_iter().find(|x| !before.contains(x)))
+    else {
+        anyhow::bail!("{project_id} was not added to the mod manager");
+    };
+    if let Some(target) = entry.get_filepaths().into_iter().flatten().next() {
+        if target != path {
+            tokio::fs::rename(path, &target)
+                .await
+                .context(RenameSnafu { path })?;
+        }
+    }
+    info!("Added {} to the mod manager", path.display());
+    Ok(entry)
+}
+
+/// Adds the entries made by [`add_to_manager`] to `collection`, collections without a mod manager are left
+/// as they are
+pub fn push_to_manager(collection: &mut Collection, entries: Vec<ModMetadata>) {
+    if let Some(controller) = collection.mod_controller.as_mut() {
+        controller.manager.mods.extend(entries);
+    }
+}
+
+/// Hands a jar that was traced back to its version over to the mod manager, keeping it disabled if it was
+async fn adopt(collection: &Collection, unmanaged: &UnmanagedMod) -> anyhow::Result<ModMetadata> {
+    let (Some(source), Some(project_id), Some(version_id)) = (
+        unmanaged.source,
+        &unmanaged.project_id,
+        &unmanaged.version_id,
+    ) else {
+        anyhow::bail!("{} hasn't been traced back to a version", unmanaged.name);
+    };
+    let mut entry =
+        add_to_manager(collection, &unmanaged.path, source, project_id, version_id).await?;
+    if !unmanaged.enabled() {
+        entry.disable().await?;
+    }
+    Ok(entry)
+}
+
+/// Keeps the unmanaged mods of a collection in sync with its mods folder.
+///
+/// Jars that are traced back to their project are added to the mod manager, the rest are remembered here.
+pub fn use_unmanaged_mods(collection_id: ReadOnlySignal<CollectionId>) -> Signal<UnmanagedMods> {
+    let mut radio = collection_id().use_collection_radio();
+    let mut unmanaged = use_collection_persisted::<UnmanagedMods>(collection_id);
+    let lookup_hashes = use_memo(move || unmanaged.read().lookup_hashes);
+    let _ = use_resource(move || async move {
+        folder_changes(collection_id(), WatchedFolder::Mods);
+        let lookup_hashes = lookup_hashes();
+        let (mods_dir, managed) = {
+            let collection = radio.read();
+            let managed = collection
+                .mod_controller()
+                .into_iter()
+                .flat_map(|x| x.manager.mods.iter())
+                .flat_map(|x| x.get_filepaths().into_iter().flatten())
+                .collect::<BTreeSet<_>>();
+            (collection.game_directory().join("mods"), managed)
+        };
+        let previous = unmanaged.peek().mods.clone();
+        let found = match reconcile(mods_dir, managed, previous, lookup_hashes).await {
+            Ok(x) => x,
+            Err(err) => {
+                toast_error(err);
+                return;
+            }
+        };
+
+        let (resolved, mut mods): (Vec<_>, Vec<_>) =
+            found.into_iter().partition(|x| x.version_id.is_some());
+        if !resolved.is_empty() {
+            let collection = Collection::clone(&radio.read());
+            let mut adopted = Vec::new();
+            for x in resolved {
+                match adopt(&collection, &x).await {
+                    Ok(entry) => adopted.push(entry),
+                    Err(err) => {
+                        toast_error(err);
+                        mods.push(x);
+                    }
+                }
+            }
+            // only written when something changed, since writing runs this again
+            if !adopted.is_empty() {
+                if let Err(err) = radio.with_mut(|x| push_to_manager(x, adopted)) {
+                    toast_error(err);
+                }
+            }
+        }
+        if unmanaged.peek().mods != mods {
+            unmanaged.write().mods = mods;
+        }
+    });
+    unmanaged
+}
+
+/// Lets the hash lookup of unmanaged jars be turned on, shown while there are any
+#[component]
+pub fn HashLookupToggle(unmanaged: Signal<UnmanagedMods>) -> Element {
+    let mut lookup = use_signal(|| unmanaged.peek().lookup_hashes);
+    let count = unmanaged.read().mods.len();
+    if count == 0 {
+        return rsx!();
+    }
+    rsx! {
+        div {
+            class: "flex items-center justify-between gap-[20px] px-[30px]",
+            div {
+                class: "text-[16px] text-hint font-medium trim",
+                {t!("mod-unmanaged-lookup", count = count)}
+            }
+            Switch {
+                clicked: lookup,
+                onclick: move |()| {
+                    unmanaged.write().lookup_hashes = lookup();
+                },
+            }
+        }
+    }
+}
+
+#[component]
+pub fn UnmanagedModRow(mods: ReadOnlySignal<UnmanagedMod>, columns: Vec<ModColumn>) -> Element {
+    let mut enabled = use_signal(|| mods.peek().enabled());
+    // the file is renamed by the switch, so it follows the rescan of the folder
+    use_effect(move || {
+        let current = mods.read().enabled();
+        if *enabled.peek() != current {
+            enabled.set(current);
+        }
+    });
+    let mods = mods.read();
+    let path = mods.path.clone();
+    let name = rsx!(
+        div {
+            class: "flex flex-col justify-center gap-[15px]",
+            div {
+                class: "flex items-center gap-[7px]",
+                div {
+                    class: "text-white text-[28px] font-bold font-english trim",
+                    {mods.name.clone()}
+                }
+                div {
+                    class: "px-[10px] py-[5px] rounded-full bg-secondary-surface text-hint text-[13px] font-medium trim",
+                    {t!("mod-unmanaged")}
+                }
+            }
+            if let Some(version) = &mods.version {
+                div {
+                    class: "font-medium text-secondary-text text-[15px] font-english trim",
+                    {version.clone()}
+                }
+            }
+        }
+    );
+    let platform = rsx!(if let Some(source) = mods.source {
+        div {
+            class: "w-[40px] bg-background inline-flex items-center justify-center h-[30px] px-[10px] rounded-[30px]",
+            {
+                ContentType::svg(
+                    match source {
+                        ModSource::Modrinth => MODRINTH,
+                        ModSource::Curseforge => CURSEFORGE,
+                    })
+                .get_element()
+            }
+        }
+    });
+    let last_updated = mods
+        .modified
+        .map(|x| {
+            chrono::DateTime::<chrono::Local>::from(x)
+                .format("%Y.%m.%d")
+                .to_string()
+        })
+        .unwrap_or_default();
+    #[allow(clippy::cast_precision_loss)]
+    let file_size = (mods.size as f64).display_size_from_megabytes();
+    // there is no platform to look for updates on
+    let upgrade = rsx!(Button {
+        roundness: Roundness::None,
+        clickable: false,
+        extended_css_class:
+            "flex items-center justify-center bg-background rounded-[15px] h-[60px] opacity-30",
+        string_placements: vec![ContentType::svg(UNARCHIVE).align_center()],
+        fill_mode: FillMode::Fill
+    });
+    let delete_path = path.clone();
+    let delete = rsx!(Button {
+        roundness: Roundness::None,
+        onclick: move |()| {
+            let path = delete_path.clone();
+            spawn(async move {
+                if let Err(err) = tokio::fs::remove_file(&path)
+                    .await
+                    .context(DeleteSnafu { path: &path })
+                {
+                    toast_error(err);
+                }
+            });
+        },
+        extended_css_class:
+            "flex items-center justify-center bg-background rounded-[15px] h-[60px]",
+        string_placements: vec![ContentType::svg(DELETE).align_center()],
+        fill_mode: FillMode::Fill
+    });
+    let status = rsx!(Switch {
+        clicked: enabled,
+        onclick: move |()| {
+            let path = path.clone();
+            let target = if enabled() {
+                enabled_path(&path)
+            } else {
+                disabled_path(&path)
+            };
+            spawn(async move {
+                if let Err(err) = tokio::fs::rename(&path, &target)
+                    .await
+                    .context(RenameSnafu { path: &path })
+                {
+                    toast_error(err);
+                }
+            });
+        },
+    });
+    let items = columns
+        .into_iter()
+        .map(|column| {
+            let element = match column {
+                ModColumn::Icon => rsx!(div {
+                    class: "size-[80px] rounded-[15px] bg-background",
+                }),
+                ModColumn::Name => name.clone(),
+                ModColumn::Author => rsx!(div {
+                    class: "text-[15px] text-secondary-text font-english trim",
+                    {mods.authors.join(", ")}
+                }),
+                ModColumn::Platform => platform.clone(),
+                ModColumn::LastUpdated => rsx!(div {
+                    class: "text-[15px] text-secondary-text font-english trim",
+                    {last_updated.clone()}
+                }),
+                ModColumn::FileSize => rsx!(div {
+                    class: "text-[15px] text-secondary-text font-english trim",
+                    {file_size.clone()}
+                }),
+                ModColumn::Update => upgrade.clone(),
+                ModColumn::Delete => delete.clone(),
+                ModColumn::Status => status.clone(),
+                ModColumn::More => rsx!(),
+            };
+            (column, element)
+        })
+        .collect::<Vec<_>>();
+    rsx! {
+        GridRow {
+            class: "bg-deep-background items-center rounded-[20px] p-[20px] aria-disabled:opacity-50",
+            aria_disabled: !mods.enabled(),
+            items,
+        }
+    }
+}
@@ -0,0 +1,503 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::PathBuf,
+    rc::Rc,
+};
+
+use dioxus::prelude::*;
+use dioxus_logger::tracing::{debug, info};
+use futures_util::future::LocalBoxFuture;
+use rust_lib::api::{
+    backend_exclusive::mod_management::mods::ModMetadata,
+    shared_resources::collection::{Collection, CollectionId},
+};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use crate::{
+    collection_display::mod_renderer::{
+        table::ModSource,
+        unmanaged::{add_to_manager, push_to_manager},
+    },
+    description::Description,
+    download_queue::FollowUp,
+    notifications::{notify, NotificationKind, NOTIFICATION_SETTINGS},
+    persistence::{use_collection_persisted, Persisted},
+    platform_api::{self, latest_compatible, PlatformApiError, RemoteVersion},
+    t,
+    BaseComponents::{molecules::foldables::Foldable, organisms::toast::toast_error},
+};
+
+/// Mods the update checker leaves alone
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PinnedMods {
+    /// Project ids, which unlike names can't be shared by two mods
+    pub mods: BTreeSet<String>,
+}
+
+impl Persisted for PinnedMods {
+    const FILE_NAME: &'static str = "pinned_mods.json";
+}
+
+impl PinnedMods {
+    #[must_use]
+    pub fn is_pinned(&self, project_id: &str) -> bool {
+        self.mods.contains(project_id)
+    }
+
+    /// Whether `mods` is pinned, `false` until its project id has been looked up
+    #[must_use]
+    pub fn is_mod_pinned(&self, mods: &ModMetadata) -> bool {
+        ModSource::known_project_id(mods).is_some_and(|x| self.is_pinned(&x))
+    }
+
+    pub fn toggle(&mut self, project_id: &str) {
+        if !self.mods.remove(project_id) {
+            self.mods.insert(project_id.to_owned());
+        }
+    }
+}
+
+/// The minecraft version and lowercased mod loader remote versions have to support
+#[must_use]
+pub fn game_target(collection: &Collection) -> (String, Option<String>) {
+    (
+        collection.minecraft_version().id.clone(),
+        collection
+            .mod_loader()
+            .map(|x| x.to_string().to_lowercase()),
+    )
+}
+
+/// Whether `version` is the one `mods` is installed as
+#[must_use]
+pub fn is_current(mods: &ModMetadata, version: &RemoteVersion) -> bool {
+    mods.get_filepaths()
+        .into_iter()
+        .flatten()
+        .any(|x| version.is_installed_at(&x))
+        || mods.mod_version.as_deref() == Some(version.version_number.as_str())
+}
+
+/// Replaces the files of a mod with the given version, downloading it into the collection's `mods` folder.
+///
+/// The old files are only replaced once the download matches its published hash. The new jar takes the
+/// place of the first old file, so the mod manager entry keeps pointing at it and a disabled mod stays
+/// disabled, the entry is updated to the new version. Retrying a failed download does all of this again.
+pub fn use_version_installer(
+    collection_id: ReadOnlySignal<CollectionId>,
+) -> impl Fn(ModMetadata, RemoteVersion) -> LocalBoxFuture<'static, anyhow::Result<()>> + Copy {
+    let mut radio = collection_id().use_collection_radio();
+    move |old, version| {
+        Box::pin(async move {
+            let Some(file) = version.primary_file().cloned() else {
+                anyhow::bail!("{} has no files to download", version.version_number);
+            };
+            let mods_dir = radio.read().game_directory().join("mods");
+            let follow_up =
+                move |new_path: PathBuf| -> LocalBoxFuture<'static, anyhow::Result<()>> {
+                    let old = old.clone();
+                    let version = version.clone();
+                    Box::pin(async move {
+                        info!(
+                            "Installed {} {} at {}",
+                            old.name,
+                            version.version_number,
+                            new_path.display()
+                        );
+
+                        let mut old_paths = old.get_filepaths().into_iter().flatten();
+                        if let Some(target) = old_paths.next() {
+                            tokio::fs::rename(&new_path, &target).await?;
+                            for path in old_paths.filter(|x| *x != target) {
+                                tokio::fs::remove_file(path).await?;
+                            }
+                        } else if !old.enabled {
+                            let mut disabled = new_path.clone().into_os_string();
+                            disabled.push(".disabled");
+                            tokio::fs::rename(&new_path, disabled).await?;
+                        }
+
+                        let collection = radio.read_owned();
+                        let Some(mut controller) = collection.mod_controller.clone() else {
+                            return Ok(());
+                        };
+                        if let Some(entry) = controller.manager.mods.iter_mut().find(|x| **x == old)
+                        {
+                            entry.mod_version = Some(version.version_number.clone());
+                            entry.last_updated = version.published;
+                        }
+                        radio.with_mut(|x| x.mod_controller = Some(controller))?;
+                        Ok(())
+                    })
+                };
+            platform_api::download_file(
+                Some(collection_id()),
+                &file,
+                &mods_dir,
+                FollowUp::Run(Rc::new(follow_up)),
+            )
+            .await?;
+            Ok(())
+        })
+    }
+}
+
+/// Moves a mod over to a version from the other platform, downloading it into the collection's `mods` folder.
+///
+/// Once the download matches its published hash, the mod manager entry is replaced by one made for the new
+/// version, so the mod is identified and updated on its new platform from then on, and the old files are
+/// removed. A disabled mod stays disabled.
+pub fn use_source_switcher(
+    collection_id: ReadOnlySignal<CollectionId>,
+) -> impl Fn(ModMetadata, RemoteVersion) -> LocalBoxFuture<'static, anyhow::Result<()>> + Copy {
+    let mut radio = collection_id().use_collection_radio();
+    move |old, version| {
+        Box::pin(async move {
+            let Some(file) = version.primary_file().cloned() else {
+                anyhow::bail!("{} has no files to download", version.version_number);
+            };
+            let mods_dir = radio.read().game_directory().join("mods");
+            let follow_up =
+                move |new_path: PathBuf| -> LocalBoxFuture<'static, anyhow::Result<()>> {
+                    let old = old.clone();
+                    let version = version.clone();
+                    Box::pin(async move {
+                        let collection = Collection::clone(&radio.read());
+                        let mut entry = add_to_manager(
+                            &collection,
+                            &new_path,
+                            version.source,
+                            &version.project_id,
+                            &version.id,
+                        )
+                        .await?;
+                        if !old.enabled {
+                            entry.disable().await?;
+                        }
+
+                        let new_paths = entry
+                            .get_filepaths()
+                            .into_iter()
+                            .flatten()
+                            .collect::<Vec<_>>();
+                        for path in old.get_filepaths().into_iter().flatten() {
+                            platform_api::forget_installed_project(&path);
+                            if !new_paths.contains(&path) {
+                                tokio::fs::remove_file(path).await?;
+                            }
+                        }
+                        // the old project may have been found for the file the new one now sits at
+                        for path in &new_paths {
+                            platform_api::forget_installed_project(path);
+                        }
+
+                        radio.with_mut(|x| {
+                            if let Some(controller) = x.mod_controller.as_mut() {
+                                controller.manager.mods.retain(|x| *x != old);
+                            }
+                            push_to_manager(x, vec![entry]);
+                        })?;
+                        info!(
+                            "Switched {} to {} {}",
+                            old.name,
+                            version.source.title(),
+                            version.version_number
+                        );
+                        Ok(())
+                    })
+                };
+            platform_api::download_file(
+                Some(collection_id()),
+                &file,
+                &mods_dir,
+                FollowUp::Run(Rc::new(follow_up)),
+            )
+            .await?;
+            Ok(())
+        })
+    }
+}
+
+/// The newest compatible version of `mods` on its own platform, unless it's already installed or pinned
+///
+/// # Errors
+///
+/// This function will return an error if the project or its versions can't be fetched.
+pub async fn available_update(
+    mods: &ModMetadata,
+    pins: &PinnedMods,
+    game_version: &str,
+    loader: Option<&str>,
+) -> Result<Option<RemoteVersion>, PlatformApiError> {
+    let project = ModSource::installed_project(mods).await?;
+    if pins.is_pinned(&project.id) {
+        info!("{} is pinned, skipping update", mods.name);
+        return Ok(None);
+    }
+    let versions = project.source.versions(&project.id).await?;
+    Ok(latest_compatible(&versions, game_version, loader)
+        .filter(|x| !is_current(mods, x))
+        .cloned())
+}
+
+/// Looks for updates of every unpinned mod once per session, notifying about the mods that have one.
+///
+/// The mods of each platform are looked up together by their hashes.
+#[component]
+pub fn UpdateNotifier(collection_id: ReadOnlySignal<CollectionId>) -> Element {
+    let radio = collection_id().use_collection_radio();
+    let pins = use_collection_persisted::<PinnedMods>(collection_id);
+    // spawned so reading the collection doesn't subscribe, the check runs once
+    use_hook(move || {
+        spawn(async move {
+            if !NOTIFICATION_SETTINGS
+                .peek()
+                .is_enabled(NotificationKind::ModUpdates)
+            {
+                return;
+            }
+            let (mods, (game_version, loader), name) = {
+                let collection = radio.read();
+                (
+                    collection
+                        .mod_controller()
+                        .map(|x| x.manager.mods.clone())
+                        .unwrap_or_default(),
+                    game_target(&collection),
+                    collection.display_name().clone(),
+                )
+            };
+            // project id -> name, so a project installed twice is only mentioned once
+            let mut updates = BTreeMap::new();
+            let pins = pins.peek().clone();
+            for source in ModSource::iter() {
+                let files = mods
+                    .iter()
+                    .filter(|x| ModSource::of(x) == source)
+                    .filter_map(|x| Some((x.get_filepaths().into_iter().flatten().next()?, x)))
+                    .collect::<HashMap<_, _>>();
+                let paths = files.keys().cloned().collect::<Vec<_>>();
+                let latest = match source
+                    .latest_versions(&paths, &game_version, loader.as_deref())
+                    .await
+                {
+                    Ok(x) => x,
+                    Err(err) => {
+                        debug!("Failed to look for updates on {}: {err}", source.title());
+                        continue;
+                    }
+                };
+                for (path, version) in latest {
+                    let Some(mods) = files.get(&path) else {
+                        continue;
+                    };
+                    if !pins.is_pinned(&version.project_id) && !is_current(mods, &version) {
+                        updates.insert(version.project_id, mods.name.clone());
+                    }
+                }
+            }
+            if !updates.is_empty() {
+                notify(
+                    NotificationKind::ModUpdates,
+                    t!(
+                        "notification-mod-updates",
+                        count = updates.len(),
+                        name = name
+                    ),
+                    updates.into_values().collect::<Vec<_>>().join(", "),
+                );
+            }
+        })
+    });
+    rsx! {}
+}
+
+/// Installs the newest compatible version of a mod from its own platform, unless it is pinned.
+///
+/// Resolves to whether an update has been installed.
+pub fn use_update_checker(
+    collection_id: ReadOnlySignal<CollectionId>,
+    pins: Signal<PinnedMods>,
+) -> impl Fn(ModMetadata) -> LocalBoxFuture<'static, anyhow::Result<bool>> + Copy {
+    let radio = collection_id().use_collection_radio();
+    let install = use_version_installer(collection_id);
+    move |mods| {
+        Box::pin(async move {
+            let (game_version, loader) = game_target(&radio.read());
+            let pins = pins.peek().clone();
+            let Some(latest) =
+                available_update(&mods, &pins, &game_version, loader.as_deref()).await?
+            else {
+                return Ok(false);
+            };
+            install(mods, latest).await?;
+            Ok(true)
+        })
+    }
+}
+
+#[component]
+fn Changelog(version: ReadOnlySignal<RemoteVersion>, expanded: Signal<bool>) -> Element {
+    // fetched the first time the entry is expanded, and kept afterwards
+    let mut requested = use_signal(|| false);
+    use_effect(move || {
+        if expanded() {
+            requested.set(true);
+        }
+    });
+    let changelog = use_resource(move || async move {
+        if !requested() {
+            return None;
+        }
+        let version = version();
+        Some(version.source.changelog(&version).await)
+    });
+    rsx! {
+        match &*changelog.read() {
+            Some(Some(Ok(changelog))) if changelog.trim().is_empty() => rsx! {
+                div {
+                    class: "px-[30px] py-[10px] text-[15px] text-hint trim",
+                    {t!("mod-no-changelog")}
+                }
+            },
+            Some(Some(Ok(changelog))) => rsx! {
+                Description {
+                    source: changelog.clone(),
+                    bbcode: version.read().source == ModSource::Curseforge,
+                    translate: false,
+                }
+            },
+            Some(Some(Err(err))) => rsx! {
+                div {
+                    class: "px-[30px] py-[10px] text-[15px] text-red trim",
+                    {err.to_string()}
+                }
+            },
+            _ => rsx! {
+                div {
+                    class: "px-[30px] py-[10px] text-[15px] text-hint trim",
+                    {t!("loading")}
+                }
+            },
+        }
+    }
+}
+
+#[component]
+fn VersionEntry(
+    mods: ReadOnlySignal<ModMetadata>,
+    collection_id: ReadOnlySignal<CollectionId>,
+    active: Signal<bool>,
+    version: ReadOnlySignal<RemoteVersion>,
+    compatible: bool,
+) -> Element {
+    let install = use_version_installer(collection_id);
+    let mut installing = use_signal(|| false);
+    let expanded = use_signal(|| false);
+
+    let current = is_current(&mods.read(), &version.read());
+    let published = version.read().published.date_naive().format("%Y.%m.%d");
+    let targets = {
+        let version = version.read();
+        version
+            .loaders
+            .iter()
+            .chain(&version.game_versions)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    rsx! {
+        Foldable {
+            enabled: expanded,
+            title: rsx! {
+                div {
+                    class: "flex items-center gap-[15px] px-[20px] py-[15px] bg-deep-background rounded-[15px] cursor-pointer",
+                    div {
+                        class: "grow flex flex-col gap-[10px] min-w-0",
+                        div {
+                            class: "flex items-center gap-[10px]",
+                            div {
+                                class: "text-white text-[18px] font-english font-bold trim",
+                                {version.read().version_number.clone()}
+                            }
+                            if current {
+                                div {
+                                    class: "px-[10px] py-[5px] rounded-full bg-white text-black text-[13px] font-medium trim",
+                                    {t!("mod-current-version")}
+                                }
+                            }
+                            if !compatible {
+                                div {
+                                    class: "px-[10px] py-[5px] rounded-full bg-secondary-surface text-hint text-[13px] font-medium trim",
+                                    {t!("mod-incompatible")}
+                                }
+                            }
+                        }
+                        div {
+                            class: "text-secondary-text text-[13px] font-english truncate trim",
+                            "{published} / {targets}"
+                        }
+                    }
+                    if !current {
+                        button {
+                            class: "flex-none h-[45px] px-[20px] rounded-[15px] bg-background text-white text-[16px] font-medium aria-busy:opacity-50 trim",
+                            aria_busy: installing(),
+                            disabled: installing(),
+                            onclick: move |e| {
+                                e.stop_propagation();
+                                installing.set(true);
+                                spawn(async move {
+                                    match install(mods(), version()).await {
+                                        Ok(()) => active.set(false),
+                                        Err(err) => toast_error(err),
+                                    }
+                                    installing.set(false);
+                                });
+                            },
+                            {t!("mod-install")}
+                        }
+                    }
+                }
+            },
+            Changelog {
+                version,
+                expanded,
+            }
+        }
+    }
+}
+
+/// Every published version of a mod on one platform, newest first
+#[component]
+pub fn VersionHistory(
+    mods: ReadOnlySignal<ModMetadata>,
+    collection_id: ReadOnlySignal<CollectionId>,
+    active: Signal<bool>,
+    versions: Vec<RemoteVersion>,
+) -> Element {
+    let radio = collection_id().use_collection_radio();
+    let (game_version, loader) = game_target(&radio.read());
+    rsx! {
+        div {
+            class: "flex flex-col gap-[10px] px-[20px] py-[20px] bg-background",
+            div {
+                class: "text-white text-[20px] font-bold trim",
+                {t!("mod-version-history")}
+            }
+            for version in versions {
+                VersionEntry {
+                    key: "{version.id}",
+                    mods,
+                    collection_id,
+                    active,
+                    compatible: version.supports(&game_version, loader.as_deref()),
+                    version,
+                }
+            }
+        }
+    }
+}
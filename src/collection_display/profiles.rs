@@ -0,0 +1,266 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use dioxus::prelude::*;
+use dioxus_logger::tracing::{info, warn};
+use futures_util::future::LocalBoxFuture;
+use rust_lib::api::{
+    backend_exclusive::mod_management::mods::ModMetadata,
+    shared_resources::collection::CollectionId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    persistence::Persisted,
+    t,
+    BaseComponents::{molecules::context_menu::DropDown, organisms::toast::toast_error},
+};
+
+/// Which mods a profile turns on and off, mods added after it was saved are in neither set
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModProfile {
+    pub name: String,
+    /// File names, see [`profile_key`]
+    pub enabled: BTreeSet<String>,
+    pub disabled: BTreeSet<String>,
+}
+
+impl ModProfile {
+    /// Whether the profile wants `mods` enabled, `None` when it doesn't know the mod
+    #[must_use]
+    pub fn wants_enabled(&self, mods: &ModMetadata) -> Option<bool> {
+        let key = profile_key(mods)?;
+        if self.enabled.contains(&key) {
+            Some(true)
+        } else if self.disabled.contains(&key) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// What a profile knows a mod by: its file name without the `.disabled` suffix, which unlike its name can't
+/// be shared by two mods and stays the same when the mod is toggled or updated
+fn profile_key(mods: &ModMetadata) -> Option<String> {
+    let path = mods.get_filepaths().into_iter().flatten().next()?;
+    let name = path.file_name()?.to_string_lossy();
+    Some(name.strip_suffix(".disabled").unwrap_or(&name).to_owned())
+}
+
+/// Named snapshots of which mods are enabled in a collection
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModProfiles {
+    /// Keyed by an id that stays the same when the profile is saved over
+    pub profiles: BTreeMap<u64, ModProfile>,
+    /// The profile that will be applied before launching
+    pub selected: Option<u64>,
+    next_id: u64,
+}
+
+impl Persisted for ModProfiles {
+    const FILE_NAME: &'static str = "mod_profiles.json";
+}
+
+impl ModProfiles {
+    /// Stores the current state of the mods as `name`, overwriting a profile with the same name
+    pub fn snapshot(&mut self, name: String, mods: &[ModMetadata]) {
+        let (enabled, disabled) = mods.iter().partition::<Vec<_>, _>(|x| x.enabled);
+        let keys = |x: Vec<&ModMetadata>| x.into_iter().filter_map(profile_key).collect();
+        let profile = ModProfile {
+            enabled: keys(enabled),
+            disabled: keys(disabled),
+            name,
+        };
+        let id = self
+            .profiles
+            .iter()
+            .find(|(_, x)| x.name == profile.name)
+            .map_or_else(
+                || {
+                    self.next_id += 1;
+                    self.next_id
+                },
+                |(id, _)| *id,
+            );
+        self.profiles.insert(id, profile);
+        self.selected = Some(id);
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        self.profiles.remove(&id);
+        if self.selected == Some(id) {
+            self.selected = None;
+        }
+    }
+
+    #[must_use]
+    pub fn selected_profile(&self) -> Option<&ModProfile> {
+        self.selected.and_then(|x| self.profiles.get(&x))
+    }
+}
+
+/// Enables or disables every mod whose state differs from `profile`, mods it doesn't mention are left alone.
+///
+/// A mod that fails to toggle doesn't stop the others, returns whether any mod has been changed along with
+/// the failures.
+pub async fn apply_profile(
+    mods: &mut [ModMetadata],
+    profile: &ModProfile,
+) -> (bool, Vec<anyhow::Error>) {
+    let mut changed = false;
+    let mut failures = Vec::new();
+    for mod_metadata in mods {
+        let Some(should_enable) = profile.wants_enabled(mod_metadata) else {
+            continue;
+        };
+        if should_enable == mod_metadata.enabled {
+            continue;
+        }
+        let result = if should_enable {
+            info!("Profile enables mod {}", mod_metadata.name);
+            mod_metadata.enable().await
+        } else {
+            info!("Profile disables mod {}", mod_metadata.name);
+            mod_metadata.disable().await
+        };
+        match result {
+            Ok(()) => changed = true,
+            Err(err) => failures.push(
+                anyhow::Error::from(err).context(format!("Failed to toggle {}", mod_metadata.name)),
+            ),
+        }
+    }
+    (changed, failures)
+}
+
+/// Applies the selected profile of `profiles` onto the collection
+pub fn use_profile_applier(
+    collection_id: ReadOnlySignal<CollectionId>,
+    profiles: Signal<ModProfiles>,
+) -> impl Fn() -> LocalBoxFuture<'static, anyhow::Result<()>> + Copy {
+    let mut radio = collection_id().use_collection_radio();
+    move || {
+        Box::pin(async move {
+            let Some(profile) = profiles.peek().selected_profile().cloned() else {
+                return Ok(());
+            };
+            let collection = radio.read_owned();
+            let Some(mut controller) = collection.mod_controller.clone() else {
+                return Ok(());
+            };
+            let (changed, mut failures) =
+                apply_profile(&mut controller.manager.mods, &profile).await;
+            // the mods that did toggle are on disk already, so the radio has to follow even after a failure
+            if changed {
+                radio.with_mut(|x| x.mod_controller = Some(controller))?;
+            }
+            match failures.len() {
+                0 => Ok(()),
+                1 => Err(failures.remove(0)),
+                count => {
+                    for err in &failures {
+                        warn!("{err:?}");
+                    }
+                    anyhow::bail!(
+                        "{count} mods of the profile {} could not be toggled",
+                        profile.name
+                    )
+                }
+            }
+        })
+    }
+}
+
+#[component]
+pub fn ProfilePicker(
+    collection_id: ReadOnlySignal<CollectionId>,
+    profiles: Signal<ModProfiles>,
+) -> Element {
+    let radio = collection_id().use_collection_radio();
+    let apply = use_profile_applier(collection_id, profiles);
+    let mut selector_visibility = use_signal(|| false);
+    let mut new_name = use_signal(String::new);
+
+    let entries = profiles
+        .read()
+        .profiles
+        .iter()
+        .map(|(id, x)| (*id, x.name.clone()))
+        .collect::<Vec<_>>();
+
+    rsx! {
+        DropDown {
+            class: "min-w-[220px] max-w-[220px]",
+            base: rsx! {
+                div {
+                    class: "text-hint font-medium text-[18px] trim",
+                    {profiles.read().selected_profile().map_or_else(|| t!("profile-all-mods"), |x| x.name.clone())}
+                }
+            },
+            selector_visibility,
+            div {
+                class: "text-[18px] text-hint aria-selected:text-white trim",
+                aria_selected: profiles.read().selected.is_none(),
+                onclick: move |_| {
+                    profiles.write().selected = None;
+                    selector_visibility.set(false);
+                },
+                {t!("profile-all-mods")}
+            }
+            for (id, name) in entries {
+                div {
+                    class: "flex items-center justify-between gap-[10px]",
+                    div {
+                        class: "grow text-[18px] text-hint aria-selected:text-white trim",
+                        aria_selected: profiles.read().selected == Some(id),
+                        onclick: move |_| {
+                            profiles.write().selected = Some(id);
+                            selector_visibility.set(false);
+                            spawn(async move {
+                                if let Err(err) = apply().await {
+                                    toast_error(err);
+                                }
+                            });
+                        },
+                        {name}
+                    }
+                    button {
+                        class: "text-red text-[18px] trim",
+                        onclick: move |_| {
+                            profiles.write().remove(id);
+                        },
+                        {t!("profile-delete")}
+                    }
+                }
+            }
+            div {
+                class: "flex items-center gap-[10px]",
+                input {
+                    class: "grow min-w-0 bg-deep-background rounded-[10px] px-[10px] py-[5px] text-white text-[16px]",
+                    placeholder: t!("profile-new-name"),
+                    value: new_name(),
+                    oninput: move |x| new_name.set(x.value()),
+                }
+                button {
+                    class: "text-white text-[16px] trim",
+                    onclick: move |_| {
+                        let name = new_name().trim().to_owned();
+                        if name.is_empty() {
+                            return;
+                        }
+                        let collection = radio.read();
+                        let mods = collection
+                            .mod_controller()
+                            .map(|x| x.manager.mods.as_slice())
+                            .unwrap_or_default();
+                        profiles.write().snapshot(name, mods);
+                        new_name.set(String::new());
+                    },
+                    {t!("profile-save")}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,476 @@
+pub mod thumbnails;
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::{LazyLock, Mutex},
+    time::SystemTime,
+};
+
+use dioxus::{prelude::*, CapturedError};
+use rust_lib::api::shared_resources::collection::{CollectionId, ScreenShot};
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+use thumbnails::Thumbnail;
+
+use crate::{
+    download_progress::SizeFromMegaBytes,
+    persistence::{use_collection_persisted, Persisted},
+    svgs::{ARROW_DOWN, CLOSE_CROSS},
+    t,
+    BaseComponents::{
+        molecules::{context_menu::DropDown, search_bar::fuzzy_search},
+        organisms::{modal::Modal, toast::toast_error},
+    },
+};
+
+/// `image::image_dimensions` has to open the file, so it is done once per file and modification time
+static DIMENSIONS: LazyLock<Mutex<HashMap<(PathBuf, Option<SystemTime>), Option<(u32, u32)>>>> =
+    LazyLock::new(Mutex::default);
+
+/// Kept alive for the whole process, some platforms drop the clipboard content with its owner
+pub(crate) static CLIPBOARD: LazyLock<Mutex<Option<arboard::Clipboard>>> =
+    LazyLock::new(|| Mutex::new(arboard::Clipboard::new().ok()));
+
+fn dimensions(path: &Path, modified: Option<SystemTime>) -> Option<(u32, u32)> {
+    let Ok(mut cache) = DIMENSIONS.lock() else {
+        return image::image_dimensions(path).ok();
+    };
+    *cache
+        .entry((path.to_owned(), modified))
+        .or_insert_with(|| image::image_dimensions(path).ok())
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
+pub enum ScreenshotSort {
+    /// Newest first
+    #[default]
+    Date,
+    /// Largest first
+    Size,
+    /// Alphabetical
+    Name,
+}
+
+impl ScreenshotSort {
+    #[must_use]
+    pub fn title(self) -> String {
+        match self {
+            Self::Date => t!("screenshots-sort-date"),
+            Self::Size => t!("screenshots-sort-size"),
+            Self::Name => t!("screenshots-sort-name"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScreenshotSettings {
+    pub sort_by: ScreenshotSort,
+    /// Flips the natural order of `sort_by`
+    pub reversed: bool,
+}
+
+impl Persisted for ScreenshotSettings {
+    const FILE_NAME: &'static str = "screenshots.json";
+}
+
+impl ScreenshotSettings {
+    pub fn sort(&self, shots: &mut [ScreenshotInfo]) {
+        match self.sort_by {
+            ScreenshotSort::Date => shots.sort_by(|a, b| b.created.cmp(&a.created)),
+            ScreenshotSort::Size => shots.sort_by(|a, b| b.size.cmp(&a.size)),
+            ScreenshotSort::Name => {
+                shots.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+            }
+        }
+        if self.reversed {
+            shots.reverse();
+        }
+    }
+}
+
+/// Everything the gallery shows about a screenshot, read once when the screenshots are loaded
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScreenshotInfo {
+    pub path: PathBuf,
+    pub name: String,
+    /// unix timestamp
+    pub created: Option<i64>,
+    pub created_display: String,
+    /// in bytes
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    pub dimensions: Option<(u32, u32)>,
+}
+
+impl ScreenshotInfo {
+    #[must_use]
+    pub fn new(screenshot: &ScreenShot) -> Self {
+        let path = screenshot.path.clone();
+        let metadata = std::fs::metadata(&path).ok();
+        let modified = metadata.as_ref().and_then(|x| x.modified().ok());
+        let created = screenshot.get_creation_date().ok();
+        Self {
+            name: path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            created: created.as_ref().map(chrono::DateTime::timestamp),
+            created_display: created
+                .map(|x| x.format("%Y.%m.%d").to_string())
+                .unwrap_or_default(),
+            size: metadata.map(|x| x.len()).unwrap_or_default(),
+            dimensions: dimensions(&path, modified),
+            modified,
+            path,
+        }
+    }
+
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn size_display(&self) -> String {
+        (self.size as f64).display_size_from_megabytes()
+    }
+}
+
+fn copy_to_clipboard(path: &Path) -> anyhow::Result<()> {
+    let image = image::open(path)?.into_rgba8();
+    let (width, height) = image.dimensions();
+    let mut clipboard = CLIPBOARD
+        .lock()
+        .map_err(|_| anyhow::anyhow!("clipboard is poisoned"))?;
+    let Some(clipboard) = clipboard.as_mut() else {
+        anyhow::bail!("clipboard is unavailable");
+    };
+    clipboard.set_image(arboard::ImageData {
+        width: width as usize,
+        height: height as usize,
+        bytes: Cow::Owned(image.into_raw()),
+    })?;
+    Ok(())
+}
+
+#[component]
+fn LightboxAction(onclick: EventHandler, children: Element) -> Element {
+    rsx! {
+        button {
+            class: "h-[50px] px-[20px] rounded-[15px] bg-background text-white text-[16px] font-medium trim",
+            onclick: move |_| onclick(()),
+            {children}
+        }
+    }
+}
+
+#[component]
+fn Lightbox(
+    shots: ReadOnlySignal<Vec<ScreenshotInfo>>,
+    active: Signal<bool>,
+    index: Signal<usize>,
+    screenshots: Resource<Result<Vec<ScreenShot>, CapturedError>>,
+) -> Element {
+    let mut container = use_signal(|| None::<Rc<MountedData>>);
+    // deleting asks again, for the screenshot that is shown at the time
+    let mut confirming = use_signal(|| false);
+    use_effect(move || {
+        index();
+        confirming.set(false);
+    });
+
+    // arrow keys only reach the container while it is focused
+    use_effect(move || {
+        if active() {
+            if let Some(container) = container() {
+                spawn(async move {
+                    let _ = container.set_focus(true).await;
+                });
+            }
+        }
+    });
+
+    let len = shots.read().len();
+    let mut step = move |forward: bool| {
+        if len == 0 {
+            return;
+        }
+        let current = index().min(len - 1);
+        index.set(if forward {
+            (current + 1) % len
+        } else {
+            (current + len - 1) % len
+        });
+    };
+
+    let Some(shot) = shots
+        .read()
+        .get(index().min(len.saturating_sub(1)))
+        .cloned()
+    else {
+        return rsx! {};
+    };
+    let path = CopyValue::new(shot.path.clone());
+
+    rsx! {
+        Modal {
+            active,
+            div {
+                class: "w-screen h-screen flex flex-col items-center justify-center gap-[20px] p-[40px] outline-none",
+                tabindex: 0,
+                onmounted: move |e| container.set(Some(e.data())),
+                onkeydown: move |e| match e.key() {
+                    Key::ArrowLeft => step(false),
+                    Key::ArrowRight => step(true),
+                    _ => {}
+                },
+                div {
+                    class: "w-full flex items-center gap-[10px]",
+                    div {
+                        class: "grow text-white text-[20px] font-english font-bold trim",
+                        "{shot.name}"
+                    }
+                    div {
+                        class: "text-hint text-[16px] font-english trim",
+                        "{index().min(len - 1) + 1} / {len}"
+                    }
+                    LightboxAction {
+                        onclick: move |()| {
+                            spawn(async move {
+                                let path = path.cloned();
+                                let result = tokio::task::spawn_blocking(move || copy_to_clipboard(&path)).await;
+                                if let Err(err) = result.map_err(anyhow::Error::from).and_then(|x| x) {
+                                    toast_error(err);
+                                }
+                            });
+                        },
+                        {t!("screenshots-copy")}
+                    }
+                    LightboxAction {
+                        onclick: move |()| {
+                            if let Err(err) = opener::reveal(path.cloned()) {
+                                toast_error(err);
+                            }
+                        },
+                        {t!("screenshots-reveal")}
+                    }
+                    if confirming() {
+                        div {
+                            class: "text-red text-[16px] font-medium trim",
+                            {t!("screenshots-delete-prompt", name = shot.name.clone())}
+                        }
+                        LightboxAction {
+                            onclick: move |()| confirming.set(false),
+                            {t!("screenshots-delete-cancel")}
+                        }
+                        button {
+                            class: "h-[50px] px-[20px] rounded-[15px] bg-red text-white text-[16px] font-medium trim",
+                            onclick: move |_| {
+                                confirming.set(false);
+                                spawn(async move {
+                                    match tokio::fs::remove_file(path.cloned()).await {
+                                        Ok(()) => {
+                                            if len <= 1 {
+                                                active.set(false);
+                                            }
+                                            screenshots.restart();
+                                        }
+                                        Err(err) => toast_error(err),
+                                    }
+                                });
+                            },
+                            {t!("screenshots-delete-confirm")}
+                        }
+                    } else {
+                        LightboxAction {
+                            onclick: move |()| confirming.set(true),
+                            {t!("screenshots-delete")}
+                        }
+                    }
+                    button {
+                        onclick: move |_| active.set(false),
+                        CLOSE_CROSS {
+                            class: "[&_path]:fill-white",
+                        }
+                    }
+                }
+                div {
+                    class: "w-full grow min-h-0 flex items-center gap-[20px]",
+                    button {
+                        onclick: move |_| step(false),
+                        ARROW_DOWN {
+                            class: "rotate-90 [&_path]:fill-white",
+                        }
+                    }
+                    img {
+                        class: "grow min-w-0 max-h-full object-contain",
+                        src: "{shot.path.to_string_lossy()}",
+                    }
+                    button {
+                        onclick: move |_| step(true),
+                        ARROW_DOWN {
+                            class: "-rotate-90 [&_path]:fill-white",
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SortPicker(settings: Signal<ScreenshotSettings>) -> Element {
+    let selector_visibility = use_signal(|| false);
+    rsx! {
+        div {
+            class: "justify-self-end flex items-center gap-[5px]",
+            DropDown {
+                class: "min-w-[200px] max-w-[200px] h-[50px]",
+                base: rsx!({settings.read().sort_by.title()}),
+                selector_visibility,
+                for sort in ScreenshotSort::iter() {
+                    div {
+                        class: "text-[18px] text-hint aria-selected:text-white trim",
+                        aria_selected: settings.read().sort_by == sort,
+                        onclick: move |_| {
+                            settings.write().sort_by = sort;
+                        },
+                        {sort.title()}
+                    }
+                }
+            }
+            button {
+                onclick: move |_| {
+                    let mut settings = settings.write();
+                    settings.reversed = !settings.reversed;
+                },
+                ARROW_DOWN {
+                    class: if settings.read().reversed { "rotate-180" } else { "" },
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn Screenshots(
+    collection_id: ReadOnlySignal<CollectionId>,
+    screenshots: Resource<Result<Vec<ScreenShot>, CapturedError>>,
+    search: ReadOnlySignal<String>,
+    default: String,
+) -> Element {
+    if let Some(Err(err)) = &*screenshots.read() {
+        Err(err.clone())?;
+    }
+
+    let settings = use_collection_persisted::<ScreenshotSettings>(collection_id);
+    // reading the metadata and the image headers of every screenshot would stall the ui
+    let infos = use_resource(move || async move {
+        let screenshots = match &*screenshots.read() {
+            Some(Ok(x)) => x.clone(),
+            _ => return Vec::new(),
+        };
+        tokio::task::spawn_blocking(move || {
+            screenshots
+                .iter()
+                .map(ScreenshotInfo::new)
+                .collect::<Vec<_>>()
+        })
+        .await
+        .unwrap_or_default()
+    });
+    let shots = use_memo(move || {
+        let infos = infos.read().clone().unwrap_or_default();
+        let mut shots = fuzzy_search(&search.read(), &default, infos.into_iter(), |x| &x.name)
+            .collect::<Vec<_>>();
+        settings.read().sort(&mut shots);
+        shots
+    });
+
+    let mut lightbox = use_signal(|| false);
+    let mut index = use_signal(|| 0);
+
+    let block_height = "310px";
+    let block_width = "390px";
+
+    rsx! {
+        div {
+            class: "rounded-[30px] bg-background px-[30px] pb-[30px] flex flex-col gap-[20px] text-[18px] text-white",
+            div {
+                class: "grid grid-flow-col items-center justify-stretch gap-[10px] px-[20px] my-[10px] h-[70px]",
+                div {
+                    class: "justify-self-start flex items-center gap-[5px] grow w-full font-medium",
+                    div {
+                        class: "w-[100px]",
+                        {t!("screenshots-file-name")}
+                    }
+                    div {
+                        class: "w-full grow text-hint font-display",
+                        {t!("screenshots-details")}
+                    }
+                }
+                SortPicker {
+                    settings,
+                }
+            }
+            div {
+                class: "grid grid-flow-row gap-[5px]",
+                grid_auto_rows: "{block_height}",
+                grid_auto_columns: "{block_width}",
+                grid_template_columns: "repeat(auto-fill,{block_width})",
+                for (i, screenshot) in shots().into_iter().enumerate() {
+                    div {
+                        class: "flex flex-col items-start bg-deep-background rounded-[30px] cursor-pointer",
+                        onclick: move |_| {
+                            index.set(i);
+                            lightbox.set(true);
+                        },
+                        div {
+                            class: "text-white flex justify-start items-center p-[20px] text-[18px] font-english font-bold",
+                            height: "60px",
+                            div {
+                                class: "trim",
+                                "{screenshot.name}"
+                            }
+                        }
+                        Thumbnail {
+                            path: screenshot.path.clone(),
+                            modified: screenshot.modified,
+                            width: block_width,
+                            height: block_height,
+                        }
+                        div {
+                            class: "flex gap-[5px] items-center p-[20px] text-secondary font-english text-[15px] font-medium",
+                            height: "50px",
+                            div {
+                                class: "trim",
+                                "{screenshot.created_display}"
+                            }
+                            div {
+                                "/"
+                            }
+                            div {
+                                class: "trim",
+                                {screenshot.size_display()}
+                            }
+                            div {
+                                "/"
+                            }
+                            div {
+                                class: "trim",
+                                {screenshot.dimensions.map(|(x, y)| format!("{x}x{y}")).unwrap_or_default()}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Lightbox {
+            shots,
+            active: lightbox,
+            index,
+            screenshots,
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Once,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use dioxus::prelude::*;
+use dioxus_logger::tracing::{info, warn};
+use image::ImageFormat;
+use sha1::{Digest, Sha1};
+use snafu::prelude::*;
+use tokio::sync::Semaphore;
+
+use crate::persistence::cache_dir;
+
+/// Twice the size of a grid cell, so thumbnails stay sharp on hidpi screens
+const THUMBNAIL_WIDTH: u32 = 780;
+const THUMBNAIL_HEIGHT: u32 = 620;
+
+/// Thumbnails that haven't been shown for this long are deleted
+const UNUSED_FOR: Duration = Duration::from_secs(60 * 60 * 24 * 30);
+
+/// Decoding a screenshot takes a whole core and a lot of memory, so only a few run at once
+static GENERATORS: Semaphore = Semaphore::const_new(2);
+
+static PRUNE: Once = Once::new();
+
+#[derive(Snafu, Debug)]
+pub enum ThumbnailError {
+    #[snafu(display("Failed to decode {}", path.display()))]
+    Decode {
+        path: PathBuf,
+        source: image::ImageError,
+    },
+    #[snafu(display("Failed to write thumbnail to {}", path.display()))]
+    Write {
+        path: PathBuf,
+        source: image::ImageError,
+    },
+    #[snafu(display("Failed to create {}", path.display()))]
+    CreateDir {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Failed to move thumbnail to {}", path.display()))]
+    Rename {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Thumbnail generation panicked"))]
+    Join { source: tokio::task::JoinError },
+}
+
+/// Where the thumbnail of `path` is stored, a modified screenshot gets a new thumbnail
+fn thumbnail_path(path: &Path, modified: Option<SystemTime>) -> PathBuf {
+    let modified = modified
+        .and_then(|x| x.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |x| x.as_nanos());
+    let mut hasher = Sha1::new();
+    hasher.update(path.to_string_lossy().as_bytes());
+    hasher.update(modified.to_le_bytes());
+    cache_dir()
+        .join("thumbnails")
+        .join(hex::encode(hasher.finalize()))
+        .with_extension("jpg")
+}
+
+fn generate(path: &Path, thumbnail: &Path) -> Result<(), ThumbnailError> {
+    let image = image::open(path).context(DecodeSnafu { path })?;
+    if let Some(parent) = thumbnail.parent() {
+        std::fs::create_dir_all(parent).context(CreateDirSnafu { path: parent })?;
+    }
+    // written aside first, so a half written thumbnail is never picked up as cached
+    let temp = thumbnail.with_extension("jpg.tmp");
+    // jpeg has no alpha channel
+    image
+        .thumbnail(THUMBNAIL_WIDTH, THUMBNAIL_HEIGHT)
+        .into_rgb8()
+        .save_with_format(&temp, ImageFormat::Jpeg)
+        .context(WriteSnafu { path: &temp })?;
+    std::fs::rename(&temp, thumbnail).context(RenameSnafu { path: thumbnail })
+}
+
+/// Marks a cached thumbnail as used, so [`prune`] keeps it
+fn touch(thumbnail: &Path) {
+    let touched = std::fs::File::options()
+        .write(true)
+        .open(thumbnail)
+        .and_then(|x| x.set_modified(SystemTime::now()));
+    if let Err(err) = touched {
+        warn!("Failed to touch {}: {err}", thumbnail.display());
+    }
+}
+
+/// Deletes the thumbnails that haven't been used for [`UNUSED_FOR`], e.g. of deleted or modified screenshots
+fn prune() {
+    let Ok(entries) = std::fs::read_dir(cache_dir().join("thumbnails")) else {
+        return;
+    };
+    let now = SystemTime::now();
+    let mut pruned = 0;
+    for entry in entries.flatten() {
+        let unused = entry
+            .metadata()
+            .and_then(|x| x.modified())
+            .ok()
+            .and_then(|x| now.duration_since(x).ok())
+            .is_some_and(|x| x > UNUSED_FOR);
+        if unused && std::fs::remove_file(entry.path()).is_ok() {
+            pruned += 1;
+        }
+    }
+    if pruned > 0 {
+        info!("Pruned {pruned} unused thumbnails");
+    }
+}
+
+/// Returns the thumbnail of the image at `path`, generating it on the blocking pool if it isn't cached yet.
+///
+/// # Errors
+///
+/// This function will return an error if the image cannot be decoded or the thumbnail cannot be written.
+pub async fn thumbnail(
+    path: PathBuf,
+    modified: Option<SystemTime>,
+) -> Result<PathBuf, ThumbnailError> {
+    PRUNE.call_once(|| {
+        tokio::task::spawn_blocking(prune);
+    });
+    let thumbnail = thumbnail_path(&path, modified);
+    if tokio::fs::try_exists(&thumbnail).await.unwrap_or_default() {
+        let cached = thumbnail.clone();
+        let _ = tokio::task::spawn_blocking(move || touch(&cached)).await;
+        return Ok(thumbnail);
+    }
+    let _permit = GENERATORS.acquire().await;
+    // another screenshot entry might have generated it while waiting
+    if tokio::fs::try_exists(&thumbnail).await.unwrap_or_default() {
+        return Ok(thumbnail);
+    }
+    tokio::task::spawn_blocking(move || generate(&path, &thumbnail).map(|()| thumbnail))
+        .await
+        .context(JoinSnafu)?
+}
+
+/// A thumbnail of a screenshot, generated once it scrolls into view and falling back to the original if one
+/// cannot be generated
+#[component]
+pub fn Thumbnail(
+    path: ReadOnlySignal<PathBuf>,
+    modified: ReadOnlySignal<Option<SystemTime>>,
+    width: String,
+    height: String,
+) -> Element {
+    let mut visible = use_signal(|| false);
+    let source = use_resource(move || async move {
+        if !visible() {
+            return None;
+        }
+        let source = thumbnail(path(), modified())
+            .await
+            .inspect_err(|err| warn!("{err}"))
+            .unwrap_or_else(|_| path());
+        Some(source)
+    });
+    rsx! {
+        div {
+            onvisible: move |e| {
+                if e.is_intersecting().unwrap_or(true) && !visible() {
+                    visible.set(true);
+                }
+            },
+            if let Some(Some(source)) = &*source.read() {
+                img {
+                    src: "{source.to_string_lossy()}",
+                    width,
+                    height,
+                    object_fit: "cover",
+                }
+            } else {
+                div {
+                    class: "bg-background",
+                    width,
+                    height,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,868 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use chrono::{DateTime, Local};
+use dioxus::{prelude::*, CapturedError};
+use dioxus_logger::tracing::info;
+use futures_util::future::LocalBoxFuture;
+use rust_lib::api::shared_resources::collection::CollectionId;
+use serde::{Deserialize, Serialize};
+use snafu::prelude::*;
+use zip::{write::SimpleFileOptions, ZipArchive, ZipWriter};
+
+use crate::{
+    app_error::AppError,
+    download_progress::SizeFromMegaBytes,
+    import::{extract, import_file, plan, ImportError, ImportKind},
+    persistence::{collection_state_dir, use_collection_persisted, Persisted},
+    t,
+    watcher::{folder_changes, WatchedFolder},
+    BaseComponents::{
+        molecules::{file_input::FileInput, search_bar::fuzzy_search},
+        organisms::{modal::Modal, toast::toast_error},
+    },
+};
+
+#[derive(Snafu, Debug)]
+pub enum WorldError {
+    #[snafu(display("Failed to access {}", path.display()))]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Failed to walk {}", path.display()))]
+    Walk {
+        path: PathBuf,
+        source: walkdir::Error,
+    },
+    #[snafu(display("Failed to read or write the archive {}", path.display()))]
+    Archive {
+        path: PathBuf,
+        source: zip::result::ZipError,
+    },
+    #[snafu(display("Failed to extract {}", path.display()))]
+    Extract { path: PathBuf, source: ImportError },
+    #[snafu(display("{} is not a world", path.display()))]
+    NotAWorld { path: PathBuf },
+    #[snafu(display("{} is open in the game", path.display()))]
+    InUse { path: PathBuf },
+    #[snafu(display("World operation panicked"))]
+    Join { source: tokio::task::JoinError },
+}
+
+/// Backups are written into the collection's state folder, which isn't watched, so the list is refreshed
+/// through this instead
+static BACKUPS_REVISION: GlobalSignal<u64> = Signal::global(|| 0);
+
+/// Suffix of the backups taken before launching, only those are pruned
+const AUTOMATIC_SUFFIX: &str = "_auto";
+
+/// When worlds are backed up automatically, and how many backups of each world are kept
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupSettings {
+    pub backup_before_launch: bool,
+    /// Older automatic backups are removed, `0` keeps every backup
+    pub keep: usize,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        Self {
+            backup_before_launch: true,
+            keep: 5,
+        }
+    }
+}
+
+impl Persisted for BackupSettings {
+    const FILE_NAME: &'static str = "world_backups.json";
+}
+
+/// A folder in `saves`
+#[derive(Debug, Clone, PartialEq)]
+pub struct World {
+    pub name: String,
+    pub path: PathBuf,
+    /// in bytes
+    pub size: u64,
+    /// When `level.dat` was last written, i.e. when the world was last saved
+    pub last_played: Option<SystemTime>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backup {
+    pub world: String,
+    pub path: PathBuf,
+    pub created: DateTime<Local>,
+    /// in bytes
+    pub size: u64,
+    /// Taken before launching, rather than by hand or before a restore
+    pub automatic: bool,
+}
+
+/// Files a restore would change, relative to the world folder
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BackupDiff {
+    /// In the backup but not in the world
+    pub restored: Vec<String>,
+    /// In the world but not in the backup
+    pub removed: Vec<String>,
+    /// In both, with different sizes
+    pub changed: Vec<String>,
+}
+
+impl BackupDiff {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.restored.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[must_use]
+pub fn backups_dir(state_dir: &Path) -> PathBuf {
+    state_dir.join("backups")
+}
+
+fn format_date(time: Option<SystemTime>) -> String {
+    time.map(|x| {
+        DateTime::<Local>::from(x)
+            .format("%Y.%m.%d %H:%M")
+            .to_string()
+    })
+    .unwrap_or_default()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn display_size(size: u64) -> String {
+    (size as f64).display_size_from_megabytes()
+}
+
+/// Every file under `dir` relative to it, with its size
+fn files_of(dir: &Path) -> Result<BTreeMap<String, u64>, WorldError> {
+    let mut files = BTreeMap::new();
+    for entry in walkdir::WalkDir::new(dir) {
+        let entry = entry.context(WalkSnafu { path: dir })?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let size = entry.metadata().context(WalkSnafu { path })?.len();
+        let Ok(relative) = path.strip_prefix(dir) else {
+            continue;
+        };
+        // zip entries always use `/`
+        let relative = relative
+            .components()
+            .map(|x| x.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        files.insert(relative, size);
+    }
+    Ok(files)
+}
+
+/// Zips everything under `dir` into `destination`, with every entry placed under `prefix`
+fn zip_dir(dir: &Path, destination: &Path, prefix: &str) -> Result<(), WorldError> {
+    if let Some(parent) = destination.parent() {
+        std::fs::create_dir_all(parent).context(IoSnafu { path: parent })?;
+    }
+    let file = File::create(destination).context(IoSnafu { path: destination })?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for name in files_of(dir)?.into_keys() {
+        let path = dir.join(&name);
+        // the game holds a lock on it while the world is open
+        if name == "session.lock" {
+            continue;
+        }
+        zip.start_file(format!("{prefix}{name}"), options)
+            .context(ArchiveSnafu { path: destination })?;
+        let mut source = File::open(&path).context(IoSnafu { path: &path })?;
+        std::io::copy(&mut source, &mut zip).context(IoSnafu { path: destination })?;
+    }
+    zip.finish().context(ArchiveSnafu { path: destination })?;
+    Ok(())
+}
+
+fn list_worlds_blocking(saves: &Path) -> Result<Vec<World>, WorldError> {
+    let entries = match std::fs::read_dir(saves) {
+        Ok(x) => x,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => {
+            return Err(WorldError::Io {
+                path: saves.to_path_buf(),
+                source,
+            })
+        }
+    };
+    let mut worlds = Vec::new();
+    for entry in entries {
+        let path = entry.context(IoSnafu { path: saves })?.path();
+        let Ok(level) = std::fs::metadata(path.join("level.dat")) else {
+            continue;
+        };
+        worlds.push(World {
+            name: path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            size: files_of(&path)?.into_values().sum(),
+            last_played: level.modified().ok(),
+            path,
+        });
+    }
+    worlds.sort_by(|a, b| b.last_played.cmp(&a.last_played));
+    Ok(worlds)
+}
+
+/// Lists the worlds in `saves`, most recently played first
+///
+/// # Errors
+///
+/// This function will return an error if the folder cannot be read.
+pub async fn list_worlds(saves: PathBuf) -> Result<Vec<World>, WorldError> {
+    tokio::task::spawn_blocking(move || list_worlds_blocking(&saves))
+        .await
+        .context(JoinSnafu)?
+}
+
+fn list_backups_blocking(dir: &Path) -> Result<Vec<Backup>, WorldError> {
+    let mut backups = Vec::new();
+    let worlds = match std::fs::read_dir(dir) {
+        Ok(x) => x,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(backups),
+        Err(source) => {
+            return Err(WorldError::Io {
+                path: dir.to_path_buf(),
+                source,
+            })
+        }
+    };
+    for world in worlds {
+        let world = world.context(IoSnafu { path: dir })?.path();
+        let Ok(entries) = std::fs::read_dir(&world) else {
+            continue;
+        };
+        for entry in entries {
+            let path = entry.context(IoSnafu { path: &world })?.path();
+            if path.extension().map_or(true, |x| x != "zip") {
+                continue;
+            }
+            let metadata = std::fs::metadata(&path).context(IoSnafu { path: &path })?;
+            backups.push(Backup {
+                automatic: path
+                    .file_stem()
+                    .is_some_and(|x| x.to_string_lossy().ends_with(AUTOMATIC_SUFFIX)),
+                world: world
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string(),
+                created: metadata
+                    .modified()
+                    .map_or_else(|_| Local::now(), DateTime::<Local>::from),
+                size: metadata.len(),
+                path,
+            });
+        }
+    }
+    backups.sort_by(|a, b| b.created.cmp(&a.created));
+    Ok(backups)
+}
+
+/// Lists the backups of every world, newest first
+///
+/// # Errors
+///
+/// This function will return an error if the backup folder cannot be read.
+pub async fn list_backups(dir: PathBuf) -> Result<Vec<Backup>, WorldError> {
+    tokio::task::spawn_blocking(move || list_backups_blocking(&dir))
+        .await
+        .context(JoinSnafu)?
+}
+
+/// Zips `world` into `dir`, the backup is named after the world and the current time.
+///
+/// `automatic` backups are tagged, so pruning leaves the others alone.
+///
+/// # Errors
+///
+/// This function will return an error if the world cannot be read or the zip cannot be written.
+pub async fn backup_world(
+    world: PathBuf,
+    dir: PathBuf,
+    automatic: bool,
+) -> Result<PathBuf, WorldError> {
+    tokio::task::spawn_blocking(move || {
+        let name = world.file_name().unwrap_or_default().to_string_lossy();
+        let suffix = if automatic { AUTOMATIC_SUFFIX } else { "" };
+        let destination = dir.join(&*name).join(format!(
+            "{name}_{}{suffix}.zip",
+            Local::now().format("%Y-%m-%d_%H-%M-%S")
+        ));
+        zip_dir(&world, &destination, "")?;
+        info!("Backed up {} to {}", world.display(), destination.display());
+        Ok(destination)
+    })
+    .await
+    .context(JoinSnafu)?
+}
+
+/// Compares the files of `backup` to the current state of `world`
+///
+/// # Errors
+///
+/// This function will return an error if the world or the backup cannot be read.
+pub async fn restore_diff(backup: PathBuf, world: PathBuf) -> Result<BackupDiff, WorldError> {
+    tokio::task::spawn_blocking(move || {
+        let current = if world.exists() {
+            files_of(&world)?
+        } else {
+            BTreeMap::new()
+        };
+        let file = File::open(&backup).context(IoSnafu { path: &backup })?;
+        let mut zip = ZipArchive::new(file).context(ArchiveSnafu { path: &backup })?;
+        let mut backed_up = BTreeMap::new();
+        for i in 0..zip.len() {
+            let entry = zip.by_index(i).context(ArchiveSnafu { path: &backup })?;
+            if !entry.is_dir() {
+                backed_up.insert(entry.name().to_owned(), entry.size());
+            }
+        }
+        let mut diff = BackupDiff::default();
+        for (name, size) in &backed_up {
+            match current.get(name) {
+                None => diff.restored.push(name.clone()),
+                Some(current) if current != size => diff.changed.push(name.clone()),
+                Some(_) => {}
+            }
+        }
+        diff.removed = current
+            .into_keys()
+            .filter(|x| !backed_up.contains_key(x) && x != "session.lock")
+            .collect();
+        Ok(diff)
+    })
+    .await
+    .context(JoinSnafu)?
+}
+
+/// Whether the game has `world` open, it holds a lock on `session.lock` meanwhile
+fn world_in_use(world: &Path) -> bool {
+    let lock = world.join("session.lock");
+    #[cfg(target_os = "linux")]
+    {
+        // the game takes an fcntl lock, which `File::try_lock` can't see here, so look for a process
+        // that has the file open instead
+        let Ok(lock) = lock.canonicalize() else {
+            return false;
+        };
+        std::fs::read_dir("/proc")
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|x| std::fs::read_dir(x.path().join("fd")).ok())
+            .flatten()
+            .flatten()
+            .any(|x| std::fs::read_link(x.path()).is_ok_and(|x| x == lock))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        File::options()
+            .write(true)
+            .open(&lock)
+            .is_ok_and(|x| matches!(x.try_lock(), Err(std::fs::TryLockError::WouldBlock)))
+    }
+}
+
+/// Replaces `world` with the content of `backup`, backing up its current state into `dir` first
+///
+/// # Errors
+///
+/// This function will return an error if the world is open in the game, or backing up, removing or
+/// extracting fails.
+pub async fn restore_backup(
+    backup: PathBuf,
+    world: PathBuf,
+    dir: PathBuf,
+) -> Result<(), WorldError> {
+    let in_use = {
+        let world = world.clone();
+        tokio::task::spawn_blocking(move || world_in_use(&world))
+            .await
+            .context(JoinSnafu)?
+    };
+    ensure!(!in_use, InUseSnafu { path: world });
+    if world.exists() {
+        backup_world(world.clone(), dir, false).await?;
+        tokio::fs::remove_dir_all(&world)
+            .await
+            .context(IoSnafu { path: &world })?;
+    }
+    info!("Restoring {} from {}", world.display(), backup.display());
+    tokio::task::spawn_blocking(move || {
+        extract(&backup, "", &world).context(ExtractSnafu { path: &backup })
+    })
+    .await
+    .context(JoinSnafu)?
+}
+
+/// Zips `world` into the downloads folder, wrapped in a folder so it can be dropped into any `saves`.
+///
+/// An earlier export of the same world is kept, the new one gets a numbered name.
+///
+/// # Errors
+///
+/// This function will return an error if the world cannot be read or the zip cannot be written.
+pub async fn export_world(world: PathBuf) -> Result<PathBuf, WorldError> {
+    tokio::task::spawn_blocking(move || {
+        let name = world
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let downloads = dirs::download_dir().unwrap_or_else(std::env::temp_dir);
+        let destination = std::iter::once(format!("{name}.zip"))
+            .chain((1..).map(|i| format!("{name} ({i}).zip")))
+            .map(|x| downloads.join(x))
+            .find(|x| !x.exists())
+            .unwrap_or_default();
+        zip_dir(&world, &destination, &format!("{name}/"))?;
+        Ok(destination)
+    })
+    .await
+    .context(JoinSnafu)?
+}
+
+/// Backs up every world that changed since its last backup, then prunes the oldest automatic backups.
+///
+/// Does nothing when automatic backups are turned off.
+pub fn use_world_backup(
+    collection_id: ReadOnlySignal<CollectionId>,
+) -> impl Fn() -> LocalBoxFuture<'static, anyhow::Result<()>> + Copy {
+    let radio = collection_id().use_collection_radio();
+    let settings = use_collection_persisted::<BackupSettings>(collection_id);
+    move || {
+        Box::pin(async move {
+            let settings = settings.peek().clone();
+            if !settings.backup_before_launch {
+                return Ok(());
+            }
+            let (saves, dir) = {
+                let collection = radio.read();
+                (
+                    collection.game_directory().join("saves"),
+                    backups_dir(&collection_state_dir(&collection)),
+                )
+            };
+            let backups = list_backups(dir.clone()).await?;
+            for world in list_worlds(saves).await? {
+                let mut existing = backups.iter().filter(|x| x.world == world.name);
+                let up_to_date = existing.next().is_some_and(|newest| {
+                    world
+                        .last_played
+                        .is_some_and(|x| DateTime::<Local>::from(x) <= newest.created)
+                });
+                if up_to_date {
+                    continue;
+                }
+                backup_world(world.path.clone(), dir.clone(), true).await?;
+                *BACKUPS_REVISION.write() += 1;
+                if settings.keep == 0 {
+                    continue;
+                }
+                // the new backup is not in `backups`, so one less of the old ones is kept
+                for old in backups
+                    .iter()
+                    .filter(|x| x.world == world.name && x.automatic)
+                    .skip(settings.keep.saturating_sub(1))
+                {
+                    tokio::fs::remove_file(&old.path).await?;
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+#[component]
+fn WorldAction(onclick: EventHandler, busy: bool, children: Element) -> Element {
+    rsx! {
+        button {
+            class: "flex-none h-[50px] px-[20px] rounded-[15px] bg-background text-white text-[16px] font-medium aria-busy:opacity-50 trim",
+            aria_busy: busy,
+            disabled: busy,
+            onclick: move |_| onclick(()),
+            {children}
+        }
+    }
+}
+
+#[component]
+fn WorldRow(collection_id: ReadOnlySignal<CollectionId>, world: ReadOnlySignal<World>) -> Element {
+    let radio = collection_id().use_collection_radio();
+    let mut busy = use_signal(|| false);
+    let mut run = move |f: LocalBoxFuture<'static, Result<(), WorldError>>| {
+        busy.set(true);
+        spawn(async move {
+            if let Err(err) = f.await {
+                toast_error(err);
+            }
+            busy.set(false);
+        });
+    };
+    let world_read = world.read();
+    rsx! {
+        div {
+            class: "flex items-center gap-[20px] bg-deep-background rounded-[20px] p-[20px]",
+            div {
+                class: "grow min-w-0 flex flex-col gap-[15px]",
+                div {
+                    class: "text-white text-[28px] font-bold font-english truncate trim",
+                    {world_read.name.clone()}
+                }
+                div {
+                    class: "text-[15px] text-secondary-text font-english trim",
+                    "{format_date(world_read.last_played)} / {display_size(world_read.size)}"
+                }
+            }
+            WorldAction {
+                busy: busy(),
+                onclick: move |()| {
+                    let dir = backups_dir(&collection_state_dir(&radio.read()));
+                    let path = world.read().path.clone();
+                    run(Box::pin(async move {
+                        backup_world(path, dir, false).await?;
+                        *BACKUPS_REVISION.write() += 1;
+                        Ok(())
+                    }));
+                },
+                {t!("world-backup")}
+            }
+            WorldAction {
+                busy: busy(),
+                onclick: move |()| {
+                    let path = world.read().path.clone();
+                    run(Box::pin(async move {
+                        let path = export_world(path).await?;
+                        if let Err(err) = opener::reveal(&path) {
+                            info!("Exported to {}, but failed to reveal it: {err}", path.display());
+                        }
+                        Ok(())
+                    }));
+                },
+                {t!("world-export")}
+            }
+        }
+    }
+}
+
+/// The worlds of a collection, with backup, export and import
+#[component]
+pub fn WorldViewer(
+    collection_id: ReadOnlySignal<CollectionId>,
+    search: ReadOnlySignal<String>,
+    default: String,
+) -> Element {
+    let radio = collection_id().use_collection_radio();
+    let mut filename = use_signal(|| None::<String>);
+
+    let worlds = use_resource(move || async move {
+        folder_changes(collection_id(), WatchedFolder::Saves);
+        let path = radio
+            .read()
+            .game_directory()
+            .join(WatchedFolder::Saves.dir_name());
+        list_worlds(path.clone()).await.map_err(|err| {
+            let source = Box::new(AppError::Other { source: err.into() });
+            CapturedError::from(AppError::Folder { path, source })
+        })
+    });
+
+    use_effect(move || {
+        let Some(path) = filename() else {
+            return;
+        };
+        let game_directory = radio.peek().game_directory().to_path_buf();
+        let collection_id = collection_id.peek().clone();
+        spawn(async move {
+            let result = async {
+                let path = PathBuf::from(path);
+                let planned = plan(vec![path.clone()]).await?;
+                let world = planned
+                    .into_iter()
+                    .find(|x| matches!(x.kind, Some(ImportKind::World { .. })))
+                    .context(NotAWorldSnafu { path })?;
+                import_file(&collection_id, &world, &game_directory).await?;
+                anyhow::Ok(())
+            };
+            if let Err(err) = result.await {
+                toast_error(err);
+            }
+        });
+        filename.set(None);
+    });
+
+    let worlds = match &*worlds.read() {
+        Some(Ok(x)) => fuzzy_search(&search.read(), &default, x.clone().into_iter(), |x| &x.name)
+            .collect::<Vec<_>>(),
+        Some(Err(err)) => Err(err.clone())?,
+        None => Vec::new(),
+    };
+
+    rsx! {
+        div {
+            class: "bg-background flex flex-col gap-[20px] rounded-t-[30px] p-[30px] h-full overflow-x-hidden",
+            div {
+                class: "flex items-center justify-between px-[20px]",
+                div {
+                    class: "text-white text-[20px] font-bold trim",
+                    {t!("world-count", count = worlds.len())}
+                }
+                FileInput {
+                    class: "h-[50px] px-[20px] rounded-[15px] bg-deep-background text-white text-[16px] font-medium flex items-center trim",
+                    accept: ".zip",
+                    filename,
+                    {t!("world-import")}
+                }
+            }
+            div {
+                class: "flex flex-col gap-[5px]",
+                for world in worlds {
+                    WorldRow {
+                        key: "{world.path.display()}",
+                        collection_id,
+                        world,
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn RestoreDialog(
+    collection_id: ReadOnlySignal<CollectionId>,
+    backup: ReadOnlySignal<Backup>,
+    active: Signal<bool>,
+) -> Element {
+    let radio = collection_id().use_collection_radio();
+    let mut restoring = use_signal(|| false);
+    let world_path = use_memo(move || {
+        radio
+            .read()
+            .game_directory()
+            .join("saves")
+            .join(&backup.read().world)
+    });
+    let diff = use_resource(move || async move {
+        if !active() {
+            return None;
+        }
+        let path = backup.read().path.clone();
+        Some(restore_diff(path, world_path()).await)
+    });
+
+    let section = |title: String, files: &[String]| {
+        let files = files.to_vec();
+        rsx! {
+            if !files.is_empty() {
+                div {
+                    class: "flex flex-col gap-[5px]",
+                    div {
+                        class: "text-white text-[18px] font-medium trim",
+                        "{title} ({files.len()})"
+                    }
+                    for file in files {
+                        div {
+                            class: "text-secondary-text text-[13px] font-english truncate trim",
+                            {file}
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    rsx! {
+        Modal {
+            active,
+            div {
+                class: "w-full h-full flex items-center justify-center",
+                div {
+                    class: "w-[800px] max-h-[80vh] flex flex-col gap-[20px] p-[30px] rounded-[30px] bg-background",
+                    div {
+                        class: "text-white text-[28px] font-bold trim",
+                        {t!("world-restore-title", world = backup.read().world.clone())}
+                    }
+                    div {
+                        class: "grow min-h-0 overflow-y-scroll flex flex-col gap-[20px]",
+                        match &*diff.read() {
+                            Some(Some(Ok(diff))) if diff.is_empty() => rsx! {
+                                div {
+                                    class: "text-hint text-[15px] trim",
+                                    {t!("world-restore-no-changes")}
+                                }
+                            },
+                            Some(Some(Ok(diff))) => rsx! {
+                                {section(t!("world-restore-restored"), &diff.restored)}
+                                {section(t!("world-restore-changed"), &diff.changed)}
+                                {section(t!("world-restore-removed"), &diff.removed)}
+                            },
+                            Some(Some(Err(err))) => rsx! {
+                                div {
+                                    class: "text-red text-[15px] trim",
+                                    {err.to_string()}
+                                }
+                            },
+                            _ => rsx! {
+                                div {
+                                    class: "text-hint text-[15px] trim",
+                                    {t!("loading")}
+                                }
+                            },
+                        }
+                    }
+                    div {
+                        class: "flex justify-end gap-[10px]",
+                        button {
+                            class: "h-[50px] px-[20px] rounded-[15px] bg-deep-background text-white text-[16px] font-medium trim",
+                            onclick: move |_| active.set(false),
+                            {t!("world-restore-cancel")}
+                        }
+                        button {
+                            class: "h-[50px] px-[20px] rounded-[15px] bg-white text-black text-[16px] font-medium aria-busy:opacity-50 trim",
+                            aria_busy: restoring(),
+                            disabled: restoring(),
+                            onclick: move |_| {
+                                restoring.set(true);
+                                let dir = backups_dir(&collection_state_dir(&radio.read()));
+                                let path = backup.read().path.clone();
+                                spawn(async move {
+                                    if let Err(err) = restore_backup(path, world_path(), dir).await {
+                                        toast_error(err);
+                                    }
+                                    restoring.set(false);
+                                    active.set(false);
+                                    *BACKUPS_REVISION.write() += 1;
+                                });
+                            },
+                            {t!("world-restore-confirm")}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn BackupRow(
+    collection_id: ReadOnlySignal<CollectionId>,
+    backup: ReadOnlySignal<Backup>,
+) -> Element {
+    let mut restore = use_signal(|| false);
+    let backup_read = backup.read();
+    rsx! {
+        RestoreDialog {
+            collection_id,
+            backup,
+            active: restore,
+        }
+        div {
+            class: "flex items-center gap-[20px] bg-deep-background rounded-[20px] p-[20px]",
+            div {
+                class: "grow min-w-0 flex flex-col gap-[15px]",
+                div {
+                    class: "flex items-center gap-[7px] min-w-0",
+                    div {
+                        class: "text-white text-[20px] font-bold font-english truncate trim",
+                        {backup_read.world.clone()}
+                    }
+                    if backup_read.automatic {
+                        div {
+                            class: "flex-none px-[10px] py-[5px] rounded-full bg-secondary-surface text-hint text-[13px] font-medium trim",
+                            {t!("world-backup-automatic")}
+                        }
+                    }
+                }
+                div {
+                    class: "text-[15px] text-secondary-text font-english trim",
+                    "{backup_read.created.format(\"%Y.%m.%d %H:%M\")} / {display_size(backup_read.size)}"
+                }
+            }
+            WorldAction {
+                busy: false,
+                onclick: move |()| restore.set(true),
+                {t!("world-restore")}
+            }
+        }
+    }
+}
+
+/// Every world backup of a collection, newest first
+#[component]
+pub fn BackupList(collection_id: ReadOnlySignal<CollectionId>) -> Element {
+    let radio = collection_id().use_collection_radio();
+    let mut settings = use_collection_persisted::<BackupSettings>(collection_id);
+    let backups = use_resource(move || async move {
+        BACKUPS_REVISION();
+        folder_changes(collection_id(), WatchedFolder::Saves);
+        list_backups(backups_dir(&collection_state_dir(&radio.read()))).await
+    });
+    let total = match &*backups.read() {
+        Some(Ok(x)) => x.iter().map(|x| x.size).sum(),
+        _ => 0,
+    };
+    rsx! {
+        div {
+            class: "flex flex-col gap-[10px]",
+            div {
+                class: "flex items-center justify-between px-[20px]",
+                div {
+                    class: "text-white text-[20px] font-bold trim",
+                    {t!("world-backups", total = display_size(total))}
+                }
+                button {
+                    class: "h-[50px] px-[20px] rounded-[15px] bg-deep-background text-white text-[16px] font-medium aria-selected:bg-white aria-selected:text-black trim",
+                    aria_selected: settings.read().backup_before_launch,
+                    onclick: move |_| {
+                        let mut settings = settings.write();
+                        settings.backup_before_launch = !settings.backup_before_launch;
+                    },
+                    {t!("world-backup-before-launch")}
+                }
+            }
+            match &*backups.read() {
+                Some(Ok(backups)) if backups.is_empty() => rsx! {
+                    div {
+                        class: "px-[20px] text-hint text-[15px] trim",
+                        {t!("world-no-backups")}
+                    }
+                },
+                Some(Ok(backups)) => rsx! {
+                    for backup in backups.clone() {
+                        BackupRow {
+                            key: "{backup.path.display()}",
+                            collection_id,
+                            backup,
+                        }
+                    }
+                },
+                Some(Err(err)) => rsx! {
+                    div {
+                        class: "px-[20px] text-red text-[15px] trim",
+                        {err.to_string()}
+                    }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
@@ -8,11 +8,13 @@ use sidebar::EditSidebar;
 use strum::{EnumIter, IntoEnumIterator};
 
 use crate::{
-    collection_display::GAME_CONTROLLER,
+    app_error::ScopedErrorBoundary,
+    collection_display::{worlds::BackupList, GAME_CONTROLLER},
+    diagnostics::DiagnosticReportButton,
     impl_context_switcher, impl_optional_state_switcher,
     pages::Pages,
     scrollable::Scrollable,
-    use_error_handler,
+    t, use_error_handler,
     BaseComponents::{
         atoms::button::{Button, Roundness},
         molecules::switcher::Comparison,
@@ -59,8 +61,12 @@ pub fn CollectionEditContainer() -> Element {
                 class: "absolute inset-0 z-0 min-w-full min-h-full",
                 ..Pages::collection_edit(collection_id).flyer_attributes(HISTORY.read()),
                 if Pages::collection_edit(collection_id).should_render() {
-                    CollectionEdit {
+                    ScopedErrorBoundary {
+                        class: "m-[30px]",
                         collection_id,
+                        CollectionEdit {
+                            collection_id,
+                        }
                     }
                 }
             }
@@ -153,9 +159,9 @@ fn DataLog(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                     string_placements: vec![
                         Contents::new(
                                 vec![
-                                    ContentType::text("收藏紀錄")
+                                    ContentType::text(t!("edit-data-log"))
                                         .css("font-black text-white text-[40px]"),
-                                    ContentType::hint("查看這個收藏的資訊"),
+                                    ContentType::hint(t!("edit-data-log-hint")),
                                 ],
                                 Alignment::Left,
                             )
@@ -164,6 +170,9 @@ fn DataLog(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                     ],
                 }
             },
+            BackupList {
+                collection_id,
+            }
         }
     }
 }
@@ -179,8 +188,8 @@ fn Export(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                     string_placements: vec![
                         Contents::new(
                                 vec![
-                                    ContentType::text("分享").css("font-black text-white text-[40px]"),
-                                    ContentType::hint("分享你的收藏或是將它匯出至電腦"),
+                                    ContentType::text(t!("edit-share")).css("font-black text-white text-[40px]"),
+                                    ContentType::hint(t!("edit-share-hint")),
                                 ],
                                 Alignment::Left,
                             )
@@ -204,9 +213,9 @@ fn Advanced(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                     string_placements: vec![
                         Contents::new(
                                 vec![
-                                    ContentType::text("進階選項")
+                                    ContentType::text(t!("edit-advanced"))
                                         .css("font-black text-white text-[40px]"),
-                                    ContentType::hint("單獨修改此收藏的進階選項"),
+                                    ContentType::hint(t!("edit-advanced-hint")),
                                 ],
                                 Alignment::Left,
                             )
@@ -215,6 +224,20 @@ fn Advanced(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                     ],
                 }
             },
+            div {
+                class: "flex flex-col gap-[15px] p-[30px] rounded-[20px] bg-deep-background",
+                div {
+                    class: "text-white text-[20px] font-bold trim",
+                    {t!("diagnostic-title")}
+                }
+                div {
+                    class: "text-hint text-[16px] font-medium",
+                    {t!("diagnostic-hint")}
+                }
+                DiagnosticReportButton {
+                    collection_id: collection_id(),
+                }
+            }
         }
     }
 }
@@ -11,10 +11,17 @@ use tailwind_fuse::tw_merge;
 use crate::{
     collection_display::GAME_CONTROLLER,
     collection_edit::EditTemplate,
+    cover_palette::{
+        accent_override, preview_accent_override, set_accent_override, use_collection_accent,
+        use_cover_palette,
+    },
+    t,
+    theme::{current_theme, Color},
     use_error_handler,
     BaseComponents::{
         atoms::button::{Button, Roundness},
         molecules::file_input::FileInput,
+        organisms::toast::toast_error,
         string_placements::{Alignment, ContentType, Contents},
     },
     ThrowResource, COLLECTION_PICS,
@@ -32,9 +39,9 @@ pub fn Personalization(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                     string_placements: vec![
                         Contents::new(
                                 vec![
-                                    ContentType::text("風格化")
+                                    ContentType::text(t!("edit-personalization"))
                                         .css("font-black text-white text-[40px]"),
-                                    ContentType::hint("自訂你的收藏樣式"),
+                                    ContentType::hint(t!("edit-personalization-hint")),
                                 ],
                                 Alignment::Left,
                             )
@@ -45,6 +52,7 @@ pub fn Personalization(collection_id: ReadOnlySignal<CollectionId>) -> Element {
             },
             ModifyName { collection_id }
             ModifyPicture { collection_id }
+            ModifyAccent { collection_id }
         }
     }
 }
@@ -61,10 +69,8 @@ fn ModifyName(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                 string_placements: vec![
                     Contents::new(
                             vec![
-                                ContentType::text("更改名稱"),
-                                ContentType::hint(
-                                    "名稱將會套用至此收藏的所有顯示位置",
-                                ),
+                                ContentType::text(t!("edit-rename")),
+                                ContentType::hint(t!("edit-rename-hint")),
                             ],
                             Alignment::Left,
                         )
@@ -167,10 +173,8 @@ fn ModifyPicture(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                 string_placements: vec![
                     Contents::new(
                             vec![
-                                ContentType::text("封面與圖示圖片"),
-                                ContentType::hint(
-                                    "預覽即將套用的圖片，建議使用辨識度較高的圖片",
-                                ),
+                                ContentType::text(t!("edit-cover")),
+                                ContentType::hint(t!("edit-cover-hint")),
                             ],
                             Alignment::Left,
                         )
@@ -193,8 +197,8 @@ fn ModifyPicture(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                                     {ContentType::svg(HALLWAY).css("svg-[35px]").align_left()}
                                     div {
                                         class: "flex flex-col gap-[10px]",
-                                        div { class: "text-xl trim", "預設封面圖片" }
-                                        div { class: "text-[13px] text-secondary-text trim", "使用Era Connect提供的預設圖片" }
+                                        div { class: "text-xl trim", {t!("edit-default-cover")} }
+                                        div { class: "text-[13px] text-secondary-text trim", {t!("edit-default-cover-hint")} }
                                     }
                                 }
                             }).align_left(),
@@ -251,8 +255,8 @@ fn ModifyPicture(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                                     {ContentType::svg(PHOTO_LIBRARY).css("svg-[35px]").align_left()}
                                     div {
                                         class: "flex flex-col gap-[10px]",
-                                        div { class: "text-xl trim", "從電腦尋找" }
-                                        div { class: "text-[13px] text-secondary-text trim", "使用你電腦中的圖片" }
+                                        div { class: "text-xl trim", {t!("edit-cover-from-computer")} }
+                                        div { class: "text-[13px] text-secondary-text trim", {t!("edit-cover-from-computer-hint")} }
                                     }
                                 }
                             }).align_left(),
@@ -272,3 +276,77 @@ fn ModifyPicture(collection_id: ReadOnlySignal<CollectionId>) -> Element {
         }
     }
 }
+
+#[component]
+fn ModifyAccent(collection_id: ReadOnlySignal<CollectionId>) -> Element {
+    let radio = collection_id().use_collection_radio();
+    let palette = use_cover_palette(collection_id);
+    let accent = use_collection_accent(collection_id);
+    let set_accent = move |accent: Option<Color>| {
+        if let Err(err) = set_accent_override(&radio.read(), accent) {
+            toast_error(err);
+        }
+    };
+    let swatches = (*palette.read()).flatten().map_or_else(Vec::new, |x| {
+        vec![
+            (x.vibrant, t!("edit-accent-vibrant")),
+            (x.dominant, t!("edit-accent-dominant")),
+        ]
+    });
+    let value = accent().unwrap_or_else(|| current_theme().colors.accent);
+    rsx! {
+        div {
+            class: "flex flex-col gap-[3px] w-full",
+            Button {
+                roundness: Roundness::Top,
+                clickable: false,
+                extended_css_class: "p-[25px]",
+                string_placements: vec![
+                    Contents::new(
+                            vec![
+                                ContentType::text(t!("edit-accent")),
+                                ContentType::hint(t!("edit-accent-hint")),
+                            ],
+                            Alignment::Left,
+                        )
+                        .css("flex flex-col gap-[15px]"),
+                ]
+            }
+            div {
+                class: "flex flex-wrap items-center gap-[10px] p-[20px] bg-deep-background rounded-b-3xl",
+                input {
+                    class: "w-[60px] h-[40px] rounded-[15px] cursor-pointer",
+                    r#type: "color",
+                    value: value.to_string(),
+                    // only previewed while the picker is dragged, saved once it's closed
+                    oninput: move |x| {
+                        if let Ok(accent) = x.value().parse() {
+                            preview_accent_override(collection_id(), Some(accent));
+                        }
+                    },
+                    onchange: move |x| {
+                        if let Ok(accent) = x.value().parse() {
+                            set_accent(Some(accent));
+                        }
+                    },
+                }
+                for (color, title) in swatches {
+                    button {
+                        class: "size-[40px] rounded-full border-2 border-background aria-selected:border-white",
+                        title,
+                        background_color: "{color}",
+                        aria_selected: accent() == Some(color),
+                        onclick: move |_| set_accent(Some(color)),
+                    }
+                }
+                if accent_override(collection_id()).is_some() {
+                    button {
+                        class: "h-[40px] px-[15px] rounded-[15px] bg-background text-white text-[14px] font-medium trim",
+                        onclick: move |_| set_accent(None),
+                        {t!("edit-accent-from-cover")}
+                    }
+                }
+            }
+        }
+    }
+}
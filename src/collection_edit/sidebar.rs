@@ -4,8 +4,10 @@ use rust_lib::api::shared_resources::collection::CollectionId;
 use crate::{
     collection_display::{DISPLAY_BACKGROUND, UNDO},
     collection_edit::EditState,
+    cover_palette::{accent_channels, use_collection_accent},
     main_page::ARROW_LEFT,
     pages::Pages,
+    t,
     text_scroller::use_text_scroller,
     BaseComponents::{
         atoms::button::{Button, FillMode, Roundness},
@@ -31,7 +33,7 @@ pub fn EditSidebar(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                     focus_color_change: true,
                     switcher: EditState::Personalization,
                     string_placements: vec![
-                        ContentType::text("風格化").align_left(),
+                        ContentType::text(t!("edit-personalization")).align_left(),
                         ContentType::svg(ARROW_RIGHT).css("svg-[30px]").align_right(),
                     ],
                 }
@@ -42,7 +44,7 @@ pub fn EditSidebar(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                     focus_color_change: true,
                     switcher: EditState::DataLog,
                     string_placements: vec![
-                        ContentType::text("收藏紀錄").align_left(),
+                        ContentType::text(t!("edit-data-log")).align_left(),
                         ContentType::svg(ARROW_RIGHT).css("svg-[30px]").align_right(),
                     ],
                 }
@@ -53,7 +55,7 @@ pub fn EditSidebar(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                     focus_color_change: true,
                     switcher: EditState::Export,
                     string_placements: vec![
-                        ContentType::text("分享&匯出").align_left(),
+                        ContentType::text(t!("edit-share-export")).align_left(),
                         ContentType::svg(ARROW_RIGHT).css("svg-[30px]").align_right(),
                     ],
                 }
@@ -64,7 +66,7 @@ pub fn EditSidebar(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                     focus_color_change: true,
                     switcher: EditState::Advanced,
                     string_placements: vec![
-                        ContentType::text("進階選項").align_left(),
+                        ContentType::text(t!("edit-advanced")).align_left(),
                         ContentType::svg(ARROW_RIGHT).css("svg-[30px]").align_right(),
                     ],
                 }
@@ -79,7 +81,7 @@ pub fn EditSidebar(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                     extended_css_class: "flex w-auto min-w-auto justify-center items-center bg-background gap-[15px] pl-[20px] pr-[30px]",
                     string_placements: vec![
                         ContentType::svg(UNDO).css("svg-[35px]").align_center(),
-                        ContentType::text("返回頁面").align_center(),
+                        ContentType::text(t!("edit-back")).align_center(),
                     ],
                 }
                 Button {
@@ -87,7 +89,7 @@ pub fn EditSidebar(collection_id: ReadOnlySignal<CollectionId>) -> Element {
                     extended_css_class: "flex w-auto min-w-auto items-center bg-background gap-[15px] pl-[20px] pr-[30px]",
                     string_placements: vec![
                         ContentType::svg(ARROW_LEFT).align_center(),
-                        ContentType::text("返回頁面").align_center(),
+                        ContentType::text(t!("edit-back")).align_center(),
                     ],
                 }
             }
@@ -99,6 +101,7 @@ pub fn EditSidebar(collection_id: ReadOnlySignal<CollectionId>) -> Element {
 fn EditSidebarInfographic(collection_id: ReadOnlySignal<CollectionId>) -> Element {
     let (onmounted, status, style) = use_text_scroller();
     let radio = collection_id().use_collection_radio();
+    let accent = use_collection_accent(collection_id);
     rsx! {
         div {
             class: "group flex flex-col w-full",
@@ -106,7 +109,8 @@ fn EditSidebarInfographic(collection_id: ReadOnlySignal<CollectionId>) -> Elemen
             div {
                 class: "flex flex-col p-5 justify-end rounded-t-[50px] w-full min-h-[250px]",
                 background: format!(
-                    "radial-gradient(171.48% 102.52% at 0% 100%, #000 0%, rgba(0, 0, 0, 0.00) 100%), url(\"{}\") lightgray 50% / cover no-repeat",
+                    "radial-gradient(171.48% 102.52% at 0% 100%, rgb(var(--color-deep-background)) 0%, rgb({} / 0.25) 100%), url(\"{}\") lightgray 50% / cover no-repeat",
+                    accent_channels(accent()),
                     DISPLAY_BACKGROUND,
                 ),
                 {
@@ -129,7 +133,7 @@ fn EditSidebarInfographic(collection_id: ReadOnlySignal<CollectionId>) -> Elemen
                                     .css(
                                         "text-3xl font-black w-full group-hover:group-aria-selected:animate-scroll-left overflow-x-clip text-nowrap",
                                     ),
-                                ContentType::hint("由我建立•18 分鐘•不久前開啟")
+                                ContentType::hint(t!("edit-sidebar-summary"))
                                     .css("font-medium text-[15px]"),
                             ],
                             Alignment::Left,
@@ -2,6 +2,7 @@ use crate::{
     builder::component::BuildCollection,
     main_page::CollectionBlock,
     svgs::{CREATE_COLLECTION, GRASS},
+    t,
     BaseComponents::{
         atoms::{
             button::{Button, FillMode, Roundness, Size},
@@ -56,56 +57,56 @@ pub fn CollectionContext(#[props(default)] class: String) -> Element {
                     extended_css_class,
                     string_placements: vec![
                         ContentType::text("H").align_left(),
-                        ContentType::hint("開始遊戲").css(text).align_left()
+                        ContentType::hint(t!("collection-menu-launch")).css(text).align_left()
                     ]
                 }
                 Button {
                     extended_css_class,
                     string_placements: vec![
                         ContentType::text("H").align_left(),
-                        ContentType::hint("更改資料夾").css(text).align_left(),
+                        ContentType::hint(t!("collection-menu-change-folder")).css(text).align_left(),
                     ]
                 }
                 Button {
                     extended_css_class,
                     string_placements: vec![
                         ContentType::text("H").align_left(),
-                        ContentType::hint("檢視詳情").css(text).align_left()
+                        ContentType::hint(t!("collection-menu-details")).css(text).align_left()
                     ]
                 }
                 Button {
                     extended_css_class,
                     string_placements: vec![
                         ContentType::text("H").align_left(),
-                        ContentType::hint("風格化選項").css(text).align_left()
+                        ContentType::hint(t!("collection-menu-personalize")).css(text).align_left()
                     ]
                 }
                 Button {
                     extended_css_class,
                     string_placements: vec![
                         ContentType::text("H").align_left(),
-                        ContentType::hint("瀏覽本機資料夾").css(text).align_left()
+                        ContentType::hint(t!("collection-menu-browse-folder")).css(text).align_left()
                     ]
                 }
                 Button {
                     extended_css_class,
                     string_placements: vec![
                         ContentType::text("H").align_left(),
-                        ContentType::hint("匯出/分享合集").css(text).align_left()
+                        ContentType::hint(t!("collection-menu-export")).css(text).align_left()
                     ]
                 }
                 Button {
                     extended_css_class,
                     string_placements: vec![
                         ContentType::text("H").align_left(),
-                        ContentType::hint("複製合集").css(text).align_left()
+                        ContentType::hint(t!("collection-menu-duplicate")).css(text).align_left()
                     ]
                 }
                 Button {
                     extended_css_class,
                     string_placements: vec![
                         ContentType::text("H").align_left(),
-                        ContentType::hint("刪除合集").css(text).align_left()
+                        ContentType::hint(t!("collection-menu-delete")).css(text).align_left()
                     ]
                 }
             }
@@ -147,13 +148,13 @@ pub fn Collections() -> Element {
                     }
                     div {
                         class: "grow text-hint trim",
-                        "選擇資料夾"
+                        {t!("collections-choose-folder")}
                     }
                     {ContentType::svg(ARROW_DOWN).css("svg-[40px] flex-0")}
                 }
                 SearchBar {
                     search,
-                    default: "搜尋合集"
+                    default: t!("collections-search")
                 }
                 Button {
                     roundness: Roundness::Squircle,
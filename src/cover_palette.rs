@@ -0,0 +1,244 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use dioxus::prelude::*;
+use dioxus_logger::tracing::warn;
+use rust_lib::api::shared_resources::collection::{Collection, CollectionId};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use snafu::prelude::*;
+
+use crate::{
+    persistence::{cache_dir, collection_state_dir, Persisted, PersistenceError},
+    theme::Color,
+};
+
+/// The cover is shrunk to this before counting colours, the palette barely changes and it's much faster
+const SAMPLE_SIZE: u32 = 64;
+/// Colours are grouped by their top bits per channel, so near identical pixels count together
+const BUCKET_BITS: u8 = 4;
+
+/// Accents picked in `Personalization`, loaded from each collection's state directory on first use
+static ACCENT_OVERRIDES: GlobalSignal<HashMap<CollectionId, Option<Color>>> =
+    Signal::global(HashMap::new);
+
+#[derive(Snafu, Debug)]
+pub enum CoverPaletteError {
+    #[snafu(display("Failed to read {}", path.display()))]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Failed to decode {}", path.display()))]
+    Decode {
+        path: PathBuf,
+        source: image::ImageError,
+    },
+    #[snafu(display("Palette extraction panicked"))]
+    Join { source: tokio::task::JoinError },
+}
+
+/// Colours picked out of a collection's cover
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoverPalette {
+    /// The most common colour
+    pub dominant: Color,
+    /// The most common saturated colour, the dominant one when the cover is mostly grey
+    pub vibrant: Color,
+}
+
+/// Frontend-only look of a collection
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CollectionAppearance {
+    /// Replaces the colour taken from the cover
+    pub accent: Option<Color>,
+}
+
+impl Persisted for CollectionAppearance {
+    const FILE_NAME: &'static str = "appearance.json";
+}
+
+#[derive(Default, Clone, Copy)]
+struct Bucket {
+    count: u32,
+    r: u32,
+    g: u32,
+    b: u32,
+}
+
+impl Bucket {
+    fn color(self) -> Color {
+        let average = |x: u32| u8::try_from(x / self.count.max(1)).unwrap_or(u8::MAX);
+        Color::new(average(self.r), average(self.g), average(self.b))
+    }
+
+    /// HSV saturation and value of the average colour, both in `0.0..=1.0`
+    fn saturation_value(self) -> (f32, f32) {
+        let Color { r, g, b } = self.color();
+        let max = f32::from(r.max(g).max(b)) / 255.;
+        let min = f32::from(r.min(g).min(b)) / 255.;
+        let saturation = if max == 0. { 0. } else { (max - min) / max };
+        (saturation, max)
+    }
+}
+
+fn extract(image: &image::DynamicImage) -> CoverPalette {
+    let shift = 8 - BUCKET_BITS;
+    let mut buckets = HashMap::<(u8, u8, u8), Bucket>::new();
+    for pixel in image
+        .thumbnail(SAMPLE_SIZE, SAMPLE_SIZE)
+        .to_rgba8()
+        .pixels()
+    {
+        let [r, g, b, a] = pixel.0;
+        // transparent pixels show whatever is behind the cover, not the cover
+        if a < 128 {
+            continue;
+        }
+        let bucket = buckets
+            .entry((r >> shift, g >> shift, b >> shift))
+            .or_default();
+        bucket.count += 1;
+        bucket.r += u32::from(r);
+        bucket.g += u32::from(g);
+        bucket.b += u32::from(b);
+    }
+    let dominant = buckets
+        .values()
+        .max_by_key(|x| x.count)
+        .copied()
+        .unwrap_or_default();
+    #[allow(clippy::cast_precision_loss)]
+    let vibrant = buckets
+        .values()
+        .filter(|x| {
+            let (saturation, value) = x.saturation_value();
+            // too dark or too washed out colours get lost against the background
+            saturation > 0.3 && (0.35..=0.95).contains(&value)
+        })
+        .max_by(|a, b| {
+            let score = |x: &Bucket| x.count as f32 * x.saturation_value().0.powi(2);
+            score(a).total_cmp(&score(b))
+        })
+        .copied()
+        .unwrap_or(dominant);
+    CoverPalette {
+        dominant: dominant.color(),
+        vibrant: vibrant.color(),
+    }
+}
+
+fn palette_path(hash: &str) -> PathBuf {
+    cache_dir()
+        .join("palettes")
+        .join(hash)
+        .with_extension("json")
+}
+
+/// Reads the palette cached for the content of `path`, extracting and caching it when there is none
+fn load(path: &Path) -> Result<CoverPalette, CoverPaletteError> {
+    let bytes = std::fs::read(path).context(ReadSnafu { path })?;
+    let cached = palette_path(&hex::encode(Sha1::digest(&bytes)));
+    if let Some(palette) = std::fs::read_to_string(&cached)
+        .ok()
+        .and_then(|x| serde_json::from_str(&x).ok())
+    {
+        return Ok(palette);
+    }
+    let image = image::load_from_memory(&bytes).context(DecodeSnafu { path })?;
+    let palette = extract(&image);
+    if let Err(err) = save(&cached, palette) {
+        warn!("Failed to cache palette to {}: {err}", cached.display());
+    }
+    Ok(palette)
+}
+
+fn save(path: &Path, palette: CoverPalette) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string(&palette)?)?;
+    Ok(())
+}
+
+/// Returns the palette of the image at `path`, cached by the hash of its content so a replaced cover
+/// gets a new one.
+///
+/// # Errors
+///
+/// This function will return an error if the image cannot be read or decoded.
+pub async fn cover_palette(path: PathBuf) -> Result<CoverPalette, CoverPaletteError> {
+    tokio::task::spawn_blocking(move || load(&path))
+        .await
+        .context(JoinSnafu)?
+}
+
+/// Saves the accent picked for `collection`, `None` goes back to the one taken from its cover
+///
+/// # Errors
+///
+/// This function will return an error if the collection's state directory cannot be written.
+pub fn set_accent_override(
+    collection: &Collection,
+    accent: Option<Color>,
+) -> Result<(), PersistenceError> {
+    CollectionAppearance { accent }.save_to(&collection_state_dir(collection))?;
+    preview_accent_override(collection.get_collection_id(), accent);
+    Ok(())
+}
+
+/// Shows `accent` on the collection without saving it, e.g. while it's being dragged around in a colour
+/// picker, [`set_accent_override`] keeps it
+pub fn preview_accent_override(collection_id: CollectionId, accent: Option<Color>) {
+    ACCENT_OVERRIDES.write().insert(collection_id, accent);
+}
+
+/// The accent picked for the collection in `Personalization`, if it has been loaded and there is one
+#[must_use]
+pub fn accent_override(collection_id: CollectionId) -> Option<Color> {
+    ACCENT_OVERRIDES
+        .read()
+        .get(&collection_id)
+        .copied()
+        .flatten()
+}
+
+/// The palette of the collection's cover, `None` while it's extracted or when the cover can't be read
+pub fn use_cover_palette(
+    collection_id: ReadOnlySignal<CollectionId>,
+) -> Resource<Option<CoverPalette>> {
+    let radio = collection_id().use_collection_radio();
+    let picture_path = use_memo(move || radio.read().picture_path().to_path_buf());
+    use_resource(move || async move {
+        cover_palette(picture_path())
+            .await
+            .inspect_err(|err| warn!("{err}"))
+            .ok()
+    })
+}
+
+/// Accent of a collection: the one picked in `Personalization`, otherwise the vibrant colour of its
+/// cover, `None` leaves the theme's accent
+pub fn use_collection_accent(collection_id: ReadOnlySignal<CollectionId>) -> Memo<Option<Color>> {
+    let radio = collection_id().use_collection_radio();
+    let palette = use_cover_palette(collection_id);
+    use_effect(move || {
+        let id = collection_id();
+        if !ACCENT_OVERRIDES.peek().contains_key(&id) {
+            let appearance = CollectionAppearance::load_from(&collection_state_dir(&radio.read()));
+            ACCENT_OVERRIDES.write().insert(id, appearance.accent);
+        }
+    });
+    use_memo(move || {
+        accent_override(collection_id()).or_else(|| (*palette.read()).flatten().map(|x| x.vibrant))
+    })
+}
+
+/// Channels of `accent` for `rgb({} / <alpha>)`, the theme's accent when there is none
+#[must_use]
+pub fn accent_channels(accent: Option<Color>) -> String {
+    accent.map_or_else(|| String::from("var(--color-accent)"), Color::channels)
+}
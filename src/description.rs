@@ -0,0 +1,329 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::PathBuf,
+    sync::{Arc, LazyLock, Mutex},
+};
+
+use dioxus::prelude::*;
+use dioxus_logger::tracing::warn;
+use document::eval;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use snafu::prelude::*;
+
+use crate::{
+    persistence::{cache_dir, data_dir, Persisted},
+    translation::{map_prose, translate_markdown, TRANSLATION_CONFIG},
+    BaseComponents::{
+        atoms::events_to_html, organisms::markdown_renderer::RenderTranslatedMarkdown,
+    },
+};
+
+pub static ALLOWLIST: GlobalSignal<HtmlAllowlist> =
+    Signal::global(|| HtmlAllowlist::load_or_create(&data_dir()));
+
+#[derive(Snafu, Debug)]
+pub enum DescriptionError {
+    #[snafu(display("Failed to fetch image {url}"))]
+    FetchImage { url: String, source: reqwest::Error },
+    #[snafu(display("Failed to cache image at {}", path.display()))]
+    CacheImage {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// Html allowed in descriptions, on top of ammonia's defaults
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HtmlAllowlist {
+    pub extra_tags: BTreeSet<String>,
+    /// Attributes allowed on every tag
+    pub extra_generic_attributes: BTreeSet<String>,
+    /// tag -> attributes allowed on that tag
+    pub extra_tag_attributes: BTreeMap<String, BTreeSet<String>>,
+    /// Tags removed from ammonia's defaults
+    pub denied_tags: BTreeSet<String>,
+}
+
+impl Default for HtmlAllowlist {
+    /// The subset CurseForge descriptions are written in
+    fn default() -> Self {
+        let set = |x: &[&str]| x.iter().map(ToString::to_string).collect::<BTreeSet<_>>();
+        Self {
+            // `center` is what `[center]` turns into, see [`bbcode_to_html`]
+            extra_tags: set(&["center", "font"]),
+            extra_generic_attributes: set(&["align"]),
+            extra_tag_attributes: BTreeMap::from([
+                (String::from("font"), set(&["color", "size"])),
+                (String::from("td"), set(&["width"])),
+            ]),
+            denied_tags: BTreeSet::new(),
+        }
+    }
+}
+
+impl Persisted for HtmlAllowlist {
+    const FILE_NAME: &'static str = "description_allowlist.json";
+}
+
+impl HtmlAllowlist {
+    fn builder(&self) -> ammonia::Builder<'_> {
+        let mut builder = ammonia::Builder::default();
+        builder
+            .add_tags(self.extra_tags.iter().map(String::as_str))
+            .rm_tags(self.denied_tags.iter().map(String::as_str))
+            .add_generic_attributes(self.extra_generic_attributes.iter().map(String::as_str))
+            .link_rel(Some("noopener noreferrer"));
+        for (tag, attributes) in &self.extra_tag_attributes {
+            builder.add_tag_attributes(tag.as_str(), attributes.iter().map(String::as_str));
+        }
+        builder
+    }
+}
+
+static BBCODE: LazyLock<[(Regex, &str); 10]> = LazyLock::new(|| {
+    #[allow(clippy::unwrap_used)]
+    let regex = |x: &str| Regex::new(x).unwrap();
+    [
+        (
+            regex(r"(?is)\[url=([^\]]+)\](.*?)\[/url\]"),
+            r#"<a href="$1">$2</a>"#,
+        ),
+        (
+            regex(r"(?is)\[url\](.*?)\[/url\]"),
+            r#"<a href="$1">$1</a>"#,
+        ),
+        (regex(r"(?is)\[img\](.*?)\[/img\]"), r#"<img src="$1">"#),
+        (regex(r"(?i)\[color=([#\w]+)\]"), r#"<font color="$1">"#),
+        (regex(r"(?i)\[size=(\d)\]"), r#"<font size="$1">"#),
+        (regex(r"(?i)\[/(color|size)\]"), "</font>"),
+        (regex(r"(?i)\[(/?)(b|i|u|s|center|code)\]"), "<$1$2>"),
+        (regex(r"(?i)\[(/?)quote\]"), "<${1}blockquote>"),
+        (regex(r"(?i)\[(/?)list\]"), "<${1}ul>"),
+        (regex(r"\[\*\]"), "<li>"),
+    ]
+});
+
+/// Turns the BBCode CurseForge descriptions sometimes contain into html
+#[must_use]
+pub fn bbcode_to_html(s: &str) -> String {
+    BBCODE.iter().fold(s.to_owned(), |s, (regex, replacement)| {
+        regex.replace_all(&s, *replacement).into_owned()
+    })
+}
+
+/// Sanitizes a markdown or html description, BBCode is only looked for with `bbcode`.
+///
+/// Images found in `cached` point to their local copy, the remaining remote images are returned alongside the html.
+/// Prose found in `translations` is replaced by its translation.
+#[must_use]
+pub fn render_description(
+    source: &str,
+    bbcode: bool,
+    allowlist: &HtmlAllowlist,
+    cached: &HashMap<String, Option<PathBuf>>,
+    translations: Option<&HashMap<String, String>>,
+) -> (String, Vec<String>) {
+    let uncached = Arc::new(Mutex::new(Vec::new()));
+    let mut builder = allowlist.builder();
+    {
+        let cached = cached.clone();
+        let uncached = uncached.clone();
+        builder.attribute_filter(move |element, attribute, value| {
+            if element != "img" || attribute != "src" || !value.starts_with("http") {
+                return Some(value.into());
+            }
+            match cached.get(value) {
+                Some(Some(path)) => Some(path.to_string_lossy().into_owned().into()),
+                Some(None) => Some(value.into()),
+                None => {
+                    if let Ok(mut uncached) = uncached.lock() {
+                        uncached.push(value.to_owned());
+                    }
+                    Some(value.into())
+                }
+            }
+        });
+    }
+    let source = if bbcode {
+        bbcode_to_html(source)
+    } else {
+        source.to_owned()
+    };
+    let events = map_prose(&source, |x| {
+        translations.and_then(|translations| translations.get(x).cloned())
+    });
+    let html = events_to_html(events.into_iter(), &builder);
+    drop(builder);
+    let uncached = Arc::try_unwrap(uncached)
+        .ok()
+        .and_then(|x| x.into_inner().ok())
+        .unwrap_or_default();
+    (html, uncached)
+}
+
+fn image_cache_path(url: &str) -> PathBuf {
+    let hash = hex::encode(Sha1::digest(url.as_bytes()));
+    let extension = url
+        .rsplit('/')
+        .next()
+        .and_then(|x| x.split(['?', '#']).next())
+        .and_then(|x| x.rsplit_once('.'))
+        .map(|(_, extension)| extension)
+        .filter(|x| x.len() <= 4 && x.chars().all(|x| x.is_ascii_alphanumeric()));
+    let path = cache_dir().join("images").join(hash);
+    match extension {
+        Some(extension) => path.with_extension(extension),
+        None => path,
+    }
+}
+
+/// Downloads the image at `url` into the cache directory, unless it has been cached before.
+///
+/// # Errors
+///
+/// This function will return an error if the image cannot be fetched or written.
+pub async fn cache_image(url: &str) -> Result<PathBuf, DescriptionError> {
+    let path = image_cache_path(url);
+    if tokio::fs::try_exists(&path).await.unwrap_or_default() {
+        return Ok(path);
+    }
+    let bytes = reqwest::get(url)
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .context(FetchImageSnafu { url })?
+        .bytes()
+        .await
+        .context(FetchImageSnafu { url })?;
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .context(CacheImageSnafu { path: parent })?;
+    }
+    // written aside first, so an interrupted download is never mistaken for a cached image
+    let mut temp = path.clone().into_os_string();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+    tokio::fs::write(&temp, bytes)
+        .await
+        .context(CacheImageSnafu { path: &temp })?;
+    tokio::fs::rename(&temp, &path)
+        .await
+        .context(CacheImageSnafu { path: &path })?;
+    Ok(path)
+}
+
+/// Renders a mod description or changelog.
+///
+/// Remote images are swapped for cached copies as they arrive, and links open in the system browser.
+/// With `translate` the prose is machine translated with the configured backend. Only CurseForge writes
+/// BBCode, markdown from elsewhere can contain brackets that merely look like it, so it's converted with
+/// `bbcode` alone.
+#[component]
+pub fn Description(
+    source: ReadOnlySignal<String>,
+    bbcode: ReadOnlySignal<bool>,
+    translate: ReadOnlySignal<bool>,
+) -> Element {
+    let id = format!("description-{}", current_scope_id()?.0);
+    let mut cached = use_signal(HashMap::<String, Option<PathBuf>>::new);
+
+    let translations = use_resource(move || async move {
+        if !translate() {
+            return None;
+        }
+        let source = if bbcode() {
+            bbcode_to_html(&source.read())
+        } else {
+            source.read().clone()
+        };
+        let config = TRANSLATION_CONFIG.read().clone();
+        let translator = config.backend.translator()?;
+        Some(translate_markdown(&source, translator.as_ref(), &config.target_language).await)
+    });
+
+    let rendered = use_memo(move || {
+        let translations = translations.read();
+        let translations = match &*translations {
+            Some(Some(Ok(x))) => Some(x),
+            _ => None,
+        };
+        render_description(
+            &source.read(),
+            bbcode(),
+            &ALLOWLIST.read(),
+            &cached.read(),
+            translations,
+        )
+    });
+
+    use_resource(move || async move {
+        let uncached = rendered.read().1.clone();
+        let mut fetched = HashMap::new();
+        for url in uncached {
+            let path = cache_image(&url)
+                .await
+                .inspect_err(|err| warn!("{err}"))
+                .ok();
+            fetched.insert(url, path);
+        }
+        if !fetched.is_empty() {
+            cached.write().extend(fetched);
+        }
+    });
+
+    // the listener is kept on `window` under the id of the description, so it can be removed on drop
+    let link_id = id.clone();
+    use_future(move || {
+        let id = link_id.clone();
+        async move {
+            let mut links = eval(&format!(
+                r#"
+                window["{id}"] = (e) => {{
+                    const link = e.target.closest("#{id} a[href]");
+                    if (link) {{
+                        e.preventDefault();
+                        dioxus.send(link.href);
+                    }}
+                }};
+                document.addEventListener("click", window["{id}"]);
+                "#
+            ));
+            while let Ok(href) = links.recv::<String>().await {
+                if !href.starts_with("http") {
+                    continue;
+                }
+                if let Err(err) = webbrowser::open(&href) {
+                    warn!("Failed to open {href}: {err}");
+                }
+            }
+        }
+    });
+
+    let drop_id = id.clone();
+    use_drop(move || {
+        eval(&format!(
+            r#"
+            document.removeEventListener("click", window["{drop_id}"]);
+            delete window["{drop_id}"];
+            "#
+        ));
+    });
+
+    rsx! {
+        div {
+            id,
+            if let Some(Some(Err(err))) = &*translations.read() {
+                div {
+                    class: "px-[30px] py-[10px] text-[15px] text-red trim",
+                    {err.to_string()}
+                }
+            }
+            RenderTranslatedMarkdown {
+                html: rendered.read().0.clone(),
+            }
+        }
+    }
+}
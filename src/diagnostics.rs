@@ -0,0 +1,391 @@
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use chrono::Local;
+use dioxus::prelude::*;
+use dioxus_logger::tracing::info;
+use rust_lib::api::shared_resources::collection::{Collection, CollectionId};
+use snafu::prelude::*;
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+use crate::{
+    logging,
+    persistence::log_dir,
+    settings::SETTINGS,
+    t,
+    BaseComponents::organisms::{
+        modal::Modal,
+        toast::{toast_error, Toast},
+    },
+};
+
+/// Only the end of long logs is kept
+const MAX_LOG_LINES: usize = 2000;
+
+#[derive(Snafu, Debug)]
+pub enum DiagnosticError {
+    #[snafu(display("Failed to write the report {}", path.display()))]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Failed to write the archive {}", path.display()))]
+    Archive {
+        path: PathBuf,
+        source: zip::result::ZipError,
+    },
+    #[snafu(display("Collecting the report panicked"))]
+    Join { source: tokio::task::JoinError },
+}
+
+/// A file of the report, named as it appears in the zip
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportSection {
+    pub name: String,
+    pub contents: String,
+}
+
+/// What a collection adds to a report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectionInfo {
+    pub summary: String,
+    pub game_directory: PathBuf,
+}
+
+impl CollectionInfo {
+    #[must_use]
+    pub fn of(collection: &Collection) -> Self {
+        let mut summary = format!(
+            "name: {}\nminecraft: {}\nmod loader: {}\ngame directory: {}\n",
+            collection.display_name(),
+            collection.minecraft_version().id,
+            collection
+                .mod_loader()
+                .map_or_else(|| String::from("none"), ToString::to_string),
+            collection.game_directory().display(),
+        );
+        if let Some(controller) = collection.mod_controller() {
+            let mods = &controller.manager.mods;
+            summary.push_str(&format!("\nmods ({}):\n", mods.len()));
+            for metadata in mods {
+                let files = metadata
+                    .get_filepaths()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|x| x.file_name().map(|x| x.to_string_lossy().to_string()))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let enabled = if metadata.enabled { "x" } else { " " };
+                summary.push_str(&format!("[{enabled}] {} ({files})\n", metadata.name));
+            }
+        }
+        Self {
+            summary,
+            game_directory: collection.game_directory().to_path_buf(),
+        }
+    }
+}
+
+/// Everything a bug report needs, with paths anonymized, ready to be looked over before it's saved
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DiagnosticReport {
+    pub sections: Vec<ReportSection>,
+}
+
+impl DiagnosticReport {
+    /// Gathers the report, this reads files and runs `java`, so it blocks
+    #[must_use]
+    pub fn collect(error: Option<String>, collection: Option<CollectionInfo>, java: &Path) -> Self {
+        let mut report = Self::default();
+        if let Some(error) = error {
+            report.push("error.txt", error);
+        }
+        report.push("system.txt", system_info(java));
+        // the file logger may not have written anything yet, the viewer's records are kept regardless
+        if let Some(log) = newest_file(&log_dir())
+            .and_then(|x| read_tail(&x))
+            .or_else(recorded_log)
+        {
+            report.push("app.log", log);
+        }
+        if let Some(collection) = collection {
+            report.push("collection.txt", collection.summary);
+            let game_directory = collection.game_directory;
+            if let Some(log) = read_tail(&game_directory.join("logs").join("latest.log")) {
+                report.push("latest.log", log);
+            }
+            if let Some(crash) =
+                newest_file(&game_directory.join("crash-reports")).and_then(|x| read_tail(&x))
+            {
+                report.push("crash-report.txt", crash);
+            }
+        }
+        report
+    }
+
+    fn push(&mut self, name: &str, contents: String) {
+        self.sections.push(ReportSection {
+            name: name.to_owned(),
+            contents: anonymize(&contents),
+        });
+    }
+
+    /// Zips the report into the downloads folder
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the zip cannot be written.
+    pub async fn save(self) -> Result<PathBuf, DiagnosticError> {
+        tokio::task::spawn_blocking(move || {
+            let destination = dirs::download_dir()
+                .unwrap_or_else(std::env::temp_dir)
+                .join(format!(
+                    "prag-portal-diagnostics-{}.zip",
+                    Local::now().format("%Y%m%d-%H%M%S")
+                ));
+            let file = File::create(&destination).context(IoSnafu { path: &destination })?;
+            let mut zip = ZipWriter::new(file);
+            let options =
+                SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+            for section in &self.sections {
+                zip.start_file(section.name.as_str(), options)
+                    .context(ArchiveSnafu { path: &destination })?;
+                zip.write_all(section.contents.as_bytes())
+                    .context(IoSnafu { path: &destination })?;
+            }
+            zip.finish().context(ArchiveSnafu { path: &destination })?;
+            info!("Saved diagnostic report to {}", destination.display());
+            Ok(destination)
+        })
+        .await
+        .context(JoinSnafu)?
+    }
+}
+
+fn system_info(java: &Path) -> String {
+    let java = Command::new(java).arg("-version").output().map_or_else(
+        |err| format!("unavailable: {err}"),
+        // `java -version` prints to stderr
+        |x| String::from_utf8_lossy(&x.stderr).trim().to_owned(),
+    );
+    format!(
+        "app: {}\nos: {} {}\njava:\n{java}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+    )
+}
+
+/// The most recently modified file directly in `dir`
+fn newest_file(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter(|x| x.file_type().is_ok_and(|x| x.is_file()))
+        .max_by_key(|x| x.metadata().and_then(|x| x.modified()).ok())
+        .map(|x| x.path())
+}
+
+/// The last [`MAX_LOG_LINES`] lines of `path`
+fn read_tail(path: &Path) -> Option<String> {
+    let content = std::fs::read(path).ok()?;
+    let content = String::from_utf8_lossy(&content);
+    let lines = content.lines().collect::<Vec<_>>();
+    Some(lines[lines.len().saturating_sub(MAX_LOG_LINES)..].join("\n"))
+}
+
+/// The last [`MAX_LOG_LINES`] records kept for the log viewer, `None` when there are none
+fn recorded_log() -> Option<String> {
+    let (_, records) = logging::records();
+    if records.is_empty() {
+        return None;
+    }
+    let lines = records[records.len().saturating_sub(MAX_LOG_LINES)..]
+        .iter()
+        .map(|x| {
+            format!(
+                "{} {} {}: {}",
+                x.time.format("%Y-%m-%d %H:%M:%S%.3f"),
+                x.level.title(),
+                x.target,
+                x.message
+            )
+        })
+        .collect::<Vec<_>>();
+    Some(lines.join("\n"))
+}
+
+/// Replaces the home directory and the user name, which tend to be someone's real name
+fn anonymize(text: &str) -> String {
+    let mut text = text.to_owned();
+    if let Some(home) = dirs::home_dir() {
+        let home = home.display().to_string();
+        if !home.is_empty() {
+            text = text.replace(&home, "~");
+        }
+    }
+    let user = std::env::var("USER").or_else(|_| std::env::var("USERNAME"));
+    if let Ok(user) = user.as_deref() {
+        // too short names would mangle unrelated words
+        if user.len() > 2 {
+            text = text.replace(user, "<user>");
+        }
+    }
+    text
+}
+
+/// Creates a diagnostic report, with a preview of its content before it's saved
+#[component]
+pub fn DiagnosticReportButton(
+    /// Technical detail of the error being reported, if any
+    error: Option<String>,
+    collection_id: Option<CollectionId>,
+) -> Element {
+    match collection_id {
+        Some(collection_id) => rsx! {
+            CollectionReportButton {
+                error,
+                collection_id,
+            }
+        },
+        None => rsx! {
+            ReportButton {
+                error,
+                collection: None,
+            }
+        },
+    }
+}
+
+#[component]
+fn CollectionReportButton(error: Option<String>, collection_id: CollectionId) -> Element {
+    let radio = collection_id.use_collection_radio();
+    let collection = use_memo(move || CollectionInfo::of(&radio.read()));
+    rsx! {
+        ReportButton {
+            error,
+            collection: Some(collection()),
+        }
+    }
+}
+
+#[component]
+fn ReportButton(error: Option<String>, collection: Option<CollectionInfo>) -> Element {
+    let mut report = use_signal(|| None);
+    let mut active = use_signal(|| false);
+    let mut collecting = use_signal(|| false);
+    rsx! {
+        button {
+            class: "self-start h-[40px] px-[15px] rounded-[15px] bg-deep-background text-white text-[14px] font-medium aria-busy:opacity-50 trim",
+            aria_busy: collecting(),
+            disabled: collecting(),
+            onclick: move |_| {
+                let error = error.clone();
+                let collection = collection.clone();
+                let java = SETTINGS.peek().java();
+                collecting.set(true);
+                spawn(async move {
+                    match tokio::task::spawn_blocking(move || DiagnosticReport::collect(error, collection, &java)).await {
+                        Ok(x) => {
+                            report.set(Some(x));
+                            active.set(true);
+                        }
+                        Err(source) => toast_error(DiagnosticError::Join { source }),
+                    }
+                    collecting.set(false);
+                });
+            },
+            {t!("diagnostic-create")}
+        }
+        if let Some(report) = report() {
+            ReportPreview {
+                report,
+                active,
+            }
+        }
+    }
+}
+
+#[component]
+fn ReportPreview(report: DiagnosticReport, active: Signal<bool>) -> Element {
+    let mut saving = use_signal(|| false);
+    let sections = report.sections.clone();
+    rsx! {
+        Modal {
+            active,
+            div {
+                class: "w-full h-full flex items-center justify-center",
+                div {
+                    class: "w-[900px] max-h-[80vh] flex flex-col gap-[20px] p-[30px] rounded-[30px] bg-background",
+                    div {
+                        class: "flex flex-col gap-[15px]",
+                        div {
+                            class: "text-white text-[30px] font-black trim",
+                            {t!("diagnostic-preview-title")}
+                        }
+                        div {
+                            class: "text-hint text-[16px] font-medium",
+                            {t!("diagnostic-preview-hint")}
+                        }
+                    }
+                    div {
+                        class: "flex flex-col gap-[15px] overflow-y-scroll",
+                        for section in sections {
+                            div {
+                                key: "{section.name}",
+                                class: "flex flex-col gap-[10px] p-[20px] rounded-[20px] bg-deep-background",
+                                div {
+                                    class: "text-white text-[18px] font-bold trim",
+                                    {section.name}
+                                }
+                                pre {
+                                    class: "max-h-[300px] overflow-scroll text-hint text-[13px] whitespace-pre-wrap",
+                                    {section.contents}
+                                }
+                            }
+                        }
+                    }
+                    div {
+                        class: "flex justify-end gap-[15px]",
+                        button {
+                            class: "h-[50px] px-[20px] rounded-[15px] bg-deep-background text-white text-[16px] font-medium trim",
+                            onclick: move |_| active.set(false),
+                            {t!("diagnostic-cancel")}
+                        }
+                        button {
+                            class: "h-[50px] px-[20px] rounded-[15px] bg-white text-black text-[16px] font-medium aria-busy:opacity-50 trim",
+                            aria_busy: saving(),
+                            disabled: saving(),
+                            onclick: move |_| {
+                                let report = report.clone();
+                                saving.set(true);
+                                spawn(async move {
+                                    match report.save().await {
+                                        Ok(path) => {
+                                            Toast::success(t!("diagnostic-saved"))
+                                                .detail(path.display().to_string())
+                                                .action(t!("diagnostic-reveal"), move || {
+                                                    if let Err(err) = opener::reveal(&path) {
+                                                        toast_error(err);
+                                                    }
+                                                })
+                                                .show();
+                                            active.set(false);
+                                        }
+                                        Err(err) => toast_error(err),
+                                    }
+                                    saving.set(false);
+                                });
+                            },
+                            {t!("diagnostic-save")}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use chrono::{DateTime, Local};
+use dioxus::prelude::*;
+use rust_lib::api::shared_resources::collection::CollectionId;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    download_queue::FollowUp,
+    persistence::{data_dir, Persisted},
+};
+
+/// Finished and failed downloads, newest first
+pub static DOWNLOAD_HISTORY: GlobalSignal<DownloadHistory> =
+    Signal::global(|| DownloadHistory::load_or_create(&data_dir()));
+
+/// Older entries are dropped once the history grows past this
+const MAX_ENTRIES: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outcome {
+    Completed,
+    Failed { reason: String },
+}
+
+/// Follow-ups of the downloads that failed this session, by history entry id
+pub static FOLLOW_UPS: GlobalSignal<HashMap<u64, FollowUp>> = Signal::global(HashMap::new);
+
+/// Where a download came from, so a failed one can be queued again
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryTarget {
+    pub url: String,
+    pub destination: PathBuf,
+    #[serde(default)]
+    pub sha1: Option<String>,
+    /// Whether something was done with the file after downloading it, a retry has to do it again
+    #[serde(default)]
+    pub follow_up: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: u64,
+    /// Only known for downloads of this session
+    #[serde(skip)]
+    pub collection_id: Option<CollectionId>,
+    pub label: String,
+    /// `None` for downloads driven by the backend, which can't be retried from here
+    pub retry: Option<RetryTarget>,
+    pub started: DateTime<Local>,
+    pub finished: DateTime<Local>,
+    /// in bytes
+    pub total_size: f64,
+    pub outcome: Outcome,
+}
+
+impl HistoryEntry {
+    #[must_use]
+    pub fn new(
+        collection_id: Option<CollectionId>,
+        label: String,
+        retry: Option<RetryTarget>,
+        started: DateTime<Local>,
+        total_size: f64,
+        outcome: Outcome,
+    ) -> Self {
+        let finished = Local::now();
+        Self {
+            id: finished
+                .timestamp_nanos_opt()
+                .unwrap_or_default()
+                .unsigned_abs(),
+            collection_id,
+            label,
+            retry,
+            started,
+            finished,
+            total_size,
+            outcome,
+        }
+    }
+
+    /// Bytes per second over the whole download, pauses included
+    #[must_use]
+    pub fn average_speed(&self) -> f64 {
+        let elapsed = (self.finished - self.started).to_std().unwrap_or_default();
+        self.total_size / elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Whether the download can be queued again, with its follow-up when it has one
+    #[must_use]
+    pub fn retryable(&self) -> bool {
+        matches!(self.outcome, Outcome::Failed { .. })
+            && self
+                .retry
+                .as_ref()
+                .is_some_and(|x| !x.follow_up || FOLLOW_UPS.read().contains_key(&self.id))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DownloadHistory {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl Persisted for DownloadHistory {
+    const FILE_NAME: &'static str = "download_history.json";
+}
+
+impl DownloadHistory {
+    pub fn record(&mut self, entry: HistoryEntry) {
+        self.entries.insert(0, entry);
+        self.entries.truncate(MAX_ENTRIES);
+    }
+
+    pub fn remove(&mut self, id: u64) -> Option<HistoryEntry> {
+        let index = self.entries.iter().position(|x| x.id == id)?;
+        Some(self.entries.remove(index))
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
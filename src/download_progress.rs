@@ -1,73 +1,164 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    time::Duration,
+};
+
+use chrono::{DateTime, Local};
 use dioxus::prelude::*;
 
 use rust_lib::api::backend_exclusive::download::Progress;
-use rust_lib::api::shared_resources::collection::CollectionId;
+use rust_lib::api::shared_resources::collection::{use_collections_radio, CollectionId};
 
+use crate::cover_palette::{accent_channels, use_collection_accent};
+use crate::download_history::{HistoryEntry, Outcome, DOWNLOAD_HISTORY, FOLLOW_UPS};
+use crate::download_queue::{
+    self, DownloadCategory, DownloadJob, DownloadQueue, JobSource, JobState, DOWNLOAD_QUEUE,
+    QUEUE_SETTINGS,
+};
 use crate::impl_context_switcher;
+use crate::notifications::{notify, NotificationKind};
+use crate::persistence::{data_dir, Persisted};
+use crate::t;
 use crate::BaseComponents::{
     atoms::button::{Button, FillMode, Roundness},
-    molecules::switcher::{StateSwitcherSelectionBar, ToClass},
+    molecules::switcher::{Comparison, StateSwitcherSelectionBar, ToClass},
+    organisms::toast::toast_error,
     string_placements::{ContentType, Image, StringPlacements},
 };
 use crate::DRAG_INDICATOR;
 use rust_lib::api::shared_resources::entry::DOWNLOAD_PROGRESS;
 use strum::EnumIter;
 
+/// Pause or resume, and cancel, of a queued job, as far as the job allows them.
+///
+/// Not buttons, since backend downloads render them inside one
+#[component]
+fn JobControls(job: DownloadJob) -> Element {
+    let DownloadJob { id, state, .. } = job.clone();
+    rsx! {
+        if state == JobState::Paused {
+            div {
+                class: "text-white text-[16px] trim",
+                role: "button",
+                onclick: move |_| DOWNLOAD_QUEUE.write().resume(id),
+                {t!("download-resume")}
+            }
+        } else if job.can_pause() {
+            div {
+                class: "text-white text-[16px] trim",
+                role: "button",
+                onclick: move |_| DOWNLOAD_QUEUE.write().pause(id),
+                {t!("download-pause")}
+            }
+        }
+        if job.can_cancel() {
+            div {
+                class: "text-red text-[16px] trim",
+                role: "button",
+                onclick: move |_| DOWNLOAD_QUEUE.write().cancel(id),
+                {t!("download-cancel")}
+            }
+        }
+    }
+}
+
+/// A download run by the backend, controlled and reordered through its job in the queue
 #[component]
-fn ListItem(collection_id: ReadOnlySignal<CollectionId>, progress: Progress) -> Element {
+fn ListItem(
+    collection_id: ReadOnlySignal<CollectionId>,
+    progress: Progress,
+    mut dragging: Signal<Option<u64>>,
+) -> Element {
     let radio = collection_id().use_collection_radio();
+    let accent = use_collection_accent(collection_id);
+    let job = use_memo(move || {
+        DOWNLOAD_QUEUE
+            .read()
+            .backend_job(&collection_id.read())
+            .cloned()
+    });
+    let eta = progress.eta();
     rsx! {
-        Button {
-            roundness: Roundness::Pill,
-            string_placements: rsx! {
-                div {
-                    class: "justify-self-center w-full flex gap-[15px]",
-                    {ContentType::svg(DRAG_INDICATOR).css("self-center svg-[30px]")}
+        div {
+            class: "aria-busy:opacity-50",
+            aria_busy: job().is_some_and(|x| dragging() == Some(x.id)),
+            draggable: job().is_some(),
+            ondragstart: move |_| dragging.set(job().map(|x| x.id)),
+            ondragend: move |_| dragging.set(None),
+            ondragover: move |e| e.prevent_default(),
+            ondrop: move |e| {
+                e.prevent_default();
+                if let (Some(from), Some(job)) = (dragging.take(), job()) {
+                    DOWNLOAD_QUEUE.write().move_before(from, job.id);
+                }
+            },
+            Button {
+                roundness: Roundness::Pill,
+                string_placements: rsx! {
                     div {
-                        class: "w-full flex gap-[20px]",
-                        Image {
-                            css: "bg-cover bg-white w-[80px] h-[80px] rounded-[10px]",
-                            {radio.read().picture_path().to_string_lossy().to_string()}
-                        }
+                        class: "justify-self-center w-full flex gap-[15px]",
+                        {ContentType::svg(DRAG_INDICATOR).css("self-center svg-[30px]")}
                         div {
-                            class: "w-full flex flex-col justify-start gap-[10px]",
-                            div {
-                                class: "text-[25px] fond-bold trim",
-                                {radio.read().display_name().clone()}
+                            class: "w-full flex gap-[20px]",
+                            Image {
+                                css: "bg-cover bg-white w-[80px] h-[80px] rounded-[10px]",
+                                {radio.read().picture_path().to_string_lossy().to_string()}
                             }
                             div {
-                                class: "flex gap-[4px]",
+                                class: "w-full flex flex-col justify-start gap-[10px]",
                                 div {
-                                    class: "text-base font-semibold text-hint trim",
-                                    {
-                                        format!(
-                                            "{} / {} |",
-                                            progress.current_size.unwrap_or_default().display_size_from_megabytes(),
-                                            progress.total_size.unwrap_or_default().display_size_from_megabytes(),
-                                        )
+                                    class: "flex gap-[10px]",
+                                    div {
+                                        class: "grow text-[25px] fond-bold trim",
+                                        {radio.read().display_name().clone()}
+                                    }
+                                    if let Some(job) = job() {
+                                        JobControls {
+                                            job,
+                                        }
                                     }
                                 }
                                 div {
-                                    class: "text-base font-semibold trim",
-                                    "{progress.speed.unwrap_or_default().display_size_from_megabytes()}"
+                                    class: "flex gap-[4px]",
+                                    div {
+                                        class: "text-base font-semibold text-hint trim",
+                                        {
+                                            format!(
+                                                "{} / {} |",
+                                                progress.current_size.unwrap_or_default().display_size_from_megabytes(),
+                                                progress.total_size.unwrap_or_default().display_size_from_megabytes(),
+                                            )
+                                        }
+                                    }
+                                    div {
+                                        class: "text-base font-semibold trim",
+                                        "{progress.speed.unwrap_or_default().display_size_from_megabytes()}"
+                                    }
+                                    if let Some(eta) = eta {
+                                        div {
+                                            class: "text-base font-semibold text-hint trim",
+                                            {t!("download-eta", time = eta)}
+                                        }
+                                    }
                                 }
-                            }
-                            div {
-                                class: "w-full h-full flex items-end",
                                 div {
-                                    class: "rounded-[50px] w-full h-[7px] bg-zinc-800",
+                                    class: "w-full h-full flex items-end",
                                     div {
-                                        class: "transition-all rounded-[50px] bg-white h-[7px]",
-                                        width: format!("{}%", progress.percentages),
+                                        class: "rounded-[50px] w-full h-[7px] bg-zinc-800",
+                                        div {
+                                            class: "transition-all rounded-[50px] h-[7px]",
+                                            background_color: format!("rgb({})", accent_channels(accent())),
+                                            width: format!("{}%", progress.percentages),
+                                        }
                                     }
                                 }
                             }
                         }
                     }
-                }
-            },
-            extended_css_class: "rounded-[5px]",
-            fill_mode: FillMode::Fill,
+                },
+                extended_css_class: "rounded-[5px]",
+                fill_mode: FillMode::Fill,
+            }
         }
     }
 }
@@ -75,11 +166,30 @@ fn ListItem(collection_id: ReadOnlySignal<CollectionId>, progress: Progress) ->
 #[component]
 fn FirstProgressView(collection_id: ReadOnlySignal<CollectionId>, progress: Progress) -> Element {
     let radio = collection_id().use_collection_radio();
+    let mut samples = use_signal(VecDeque::<f64>::new);
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            let speed = DOWNLOAD_PROGRESS
+                .peek()
+                .0
+                .iter()
+                .find(|(id, _)| id.collection_id == *collection_id.peek())
+                .and_then(|(_, x)| x.speed)
+                .unwrap_or_default();
+            let mut samples = samples.write();
+            samples.push_back(speed);
+            if samples.len() > SPARKLINE_SAMPLES {
+                samples.pop_front();
+            }
+        }
+    });
+    let eta = progress.eta();
     rsx! {
         div {
             class: "w-full h-[350px] p-[30px] rounded-[20px]",
             background: format!(
-                "linear-gradient(88deg, #0E0E0E 14.88%, rgba(14, 14, 14, 0.70) 100%), url('{}') lightgray 50% / cover no-repeat",
+                "linear-gradient(88deg, rgb(var(--color-deep-background)) 14.88%, rgb(var(--color-deep-background) / 0.70) 100%), url('{}') lightgray 50% / cover no-repeat",
                 radio.read().picture_path().to_string_lossy().to_string(),
             ),
             div {
@@ -93,23 +203,35 @@ fn FirstProgressView(collection_id: ReadOnlySignal<CollectionId>, progress: Prog
                     div {
                         class: "font-medium text-hint trim",
                         {
-                            format!(
-                                "總計 {}/已下載 {}",
-                                progress.total_size.unwrap_or_default().display_size_from_megabytes(),
-                                progress.current_size.unwrap_or_default().display_size_from_megabytes(),
+                            t!(
+                                "download-total",
+                                total = progress.total_size.unwrap_or_default().display_size_from_megabytes(),
+                                downloaded = progress.current_size.unwrap_or_default().display_size_from_megabytes(),
                             )
                         }
                     }
+                    if let Some(eta) = eta {
+                        div {
+                            class: "font-medium text-hint trim",
+                            {t!("download-eta", time = eta)}
+                        }
+                    }
                 }
                 div {
-                    class: "justify-self-end flex",
+                    class: "justify-self-end flex flex-col items-end gap-[20px]",
                     div {
-                        class: "text-[50px] font-bold text-white trim",
-                        "{progress.speed.unwrap_or_default().display_size_from_megabytes()}"
+                        class: "flex",
+                        div {
+                            class: "text-[50px] font-bold text-white trim",
+                            "{progress.speed.unwrap_or_default().display_size_from_megabytes()}"
+                        }
+                        div {
+                            class: "text-[50px] font-bold trim",
+                            "/s"
+                        }
                     }
-                    div {
-                        class: "text-[50px] font-bold trim",
-                        "/s"
+                    Sparkline {
+                        samples: samples.read().iter().copied().collect::<Vec<_>>(),
                     }
                 }
             }
@@ -117,6 +239,75 @@ fn FirstProgressView(collection_id: ReadOnlySignal<CollectionId>, progress: Prog
     }
 }
 
+/// How many seconds of speed the sparkline spans
+const SPARKLINE_SAMPLES: usize = 60;
+
+/// Recent download speed, the newest sample on the right
+#[component]
+fn Sparkline(samples: Vec<f64>) -> Element {
+    let max = samples.iter().copied().fold(f64::EPSILON, f64::max);
+    let offset = SPARKLINE_SAMPLES.saturating_sub(samples.len());
+    #[allow(clippy::cast_precision_loss)]
+    let points = samples
+        .iter()
+        .enumerate()
+        .map(|(i, x)| {
+            let x_axis = (offset + i) as f64 / (SPARKLINE_SAMPLES - 1) as f64 * 100.;
+            format!("{x_axis:.2},{:.2}", 30. - x / max * 30.)
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    rsx! {
+        svg {
+            class: "w-[300px] h-[60px]",
+            "viewBox": "0 0 100 30",
+            "preserveAspectRatio": "none",
+            fill: "none",
+            polyline {
+                "points": "{points}",
+                stroke: "white",
+                "stroke-width": "1.5",
+                "vector-effect": "non-scaling-stroke",
+            }
+        }
+    }
+}
+
+/// Time left for `remaining` at `speed`, e.g. `3m 20s`, `None` while stalled
+fn display_eta(remaining: f64, speed: f64) -> Option<String> {
+    if speed <= 0. || remaining <= 0. {
+        return None;
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let seconds = (remaining / speed).ceil() as u64;
+    Some(match seconds {
+        s if s < 60 => format!("{s}s"),
+        s if s < 3_600 => format!("{}m {}s", s / 60, s % 60),
+        s => format!("{}h {}m", s / 3_600, s % 3_600 / 60),
+    })
+}
+
+trait Eta {
+    fn eta(&self) -> Option<String>;
+}
+
+impl Eta for Progress {
+    fn eta(&self) -> Option<String> {
+        display_eta(
+            self.total_size.unwrap_or_default() - self.current_size.unwrap_or_default(),
+            self.speed.unwrap_or_default(),
+        )
+    }
+}
+
+impl Eta for DownloadJob {
+    fn eta(&self) -> Option<String> {
+        #[allow(clippy::cast_precision_loss)]
+        let remaining = self.total?.saturating_sub(self.downloaded) as f64;
+        display_eta(remaining, self.speed)
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Copy, Hash, EnumIter, Debug)]
 pub enum ProgressState {
     Running,
@@ -128,11 +319,11 @@ impl From<ProgressState> for StringPlacements {
         match value {
             ProgressState::Running => vec![
                 ContentType::text("K").align_left(),
-                ContentType::text("正在進行").align_right(),
+                ContentType::text(t!("download-running")).align_right(),
             ],
             ProgressState::Finished => vec![
                 ContentType::text("K").align_left(),
-                ContentType::text("已完成").align_right(),
+                ContentType::text(t!("download-finished")).align_right(),
             ],
         }
         .into()
@@ -143,48 +334,619 @@ impl ToClass for ProgressState {}
 
 impl_context_switcher!(ProgressState);
 
+/// Concurrency and bandwidth limits of the download queue
+#[component]
+pub(crate) fn QueueLimits() -> Element {
+    use_effect(move || {
+        if let Err(err) = QUEUE_SETTINGS.read().save_to(&data_dir()) {
+            toast_error(err);
+        }
+    });
+    let bandwidth_limit = QUEUE_SETTINGS
+        .read()
+        .bandwidth_limit
+        .map(|x| (x / 1_000).to_string())
+        .unwrap_or_default();
+    rsx! {
+        div {
+            class: "flex items-center gap-[15px] text-hint text-[16px] font-medium",
+            label {
+                class: "flex items-center gap-[10px] trim",
+                {t!("download-concurrency")}
+                input {
+                    class: "w-[60px] bg-deep-background rounded-[10px] px-[10px] py-[5px] text-white",
+                    r#type: "number",
+                    min: "1",
+                    value: QUEUE_SETTINGS.read().max_concurrent.to_string(),
+                    oninput: move |x| {
+                        if let Ok(max_concurrent) = x.value().parse::<usize>() {
+                            QUEUE_SETTINGS.write().max_concurrent = max_concurrent.max(1);
+                        }
+                    },
+                }
+            }
+            label {
+                class: "flex items-center gap-[10px] trim",
+                {t!("download-bandwidth-limit")}
+                input {
+                    class: "w-[100px] bg-deep-background rounded-[10px] px-[10px] py-[5px] text-white",
+                    r#type: "number",
+                    min: "0",
+                    placeholder: t!("download-unlimited"),
+                    value: bandwidth_limit,
+                    oninput: move |x| {
+                        let limit = x.value().parse::<u64>().ok().filter(|x| *x > 0);
+                        QUEUE_SETTINGS.write().bandwidth_limit = limit.map(|x| x * 1_000);
+                    },
+                }
+            }
+        }
+    }
+}
+
 #[component]
-fn ProgressStateBar() -> Element {
+fn ProgressStateBar(state: Signal<Comparison<ProgressState>>) -> Element {
     rsx! {
         div {
             class: "w-full grid grid-flow-col justify-stretch",
             StateSwitcherSelectionBar {
                 default_state: ProgressState::Running,
+                signal: state,
             }
             div {
                 class: "justify-self-end",
-                "go fuck yourself"
+                QueueLimits {}
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CategoryProgress {
+    jobs: usize,
+    finished: usize,
+    downloaded: f64,
+    total: f64,
+    speed: f64,
+}
+
+/// The downloads of a collection by category, and the files that failed to download.
+///
+/// Game files are fetched by the backend, which reports them by kind, the rest comes from the queue.
+fn collection_breakdown(
+    collection_id: &CollectionId,
+) -> (
+    BTreeMap<DownloadCategory, CategoryProgress>,
+    Vec<(String, String)>,
+) {
+    let mut categories = BTreeMap::<DownloadCategory, CategoryProgress>::new();
+    let mut failures = Vec::new();
+    let queue = DOWNLOAD_QUEUE.read();
+    for job in queue
+        .jobs
+        .iter()
+        .filter(|x| x.collection_id.as_ref() == Some(collection_id))
+    {
+        match &job.state {
+            JobState::Cancelled => continue,
+            JobState::Failed(reason) => failures.push((job.label.clone(), reason.clone())),
+            _ => {}
+        }
+        if job.source == JobSource::Backend {
+            continue;
+        }
+        let category = categories.entry(job.category()).or_default();
+        category.jobs += 1;
+        if job.state == JobState::Finished {
+            category.finished += 1;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let (downloaded, total) = (
+            job.downloaded as f64,
+            job.total.unwrap_or(job.downloaded) as f64,
+        );
+        category.downloaded += downloaded;
+        category.total += total;
+        category.speed += job.speed;
+    }
+    for (id, progress) in DOWNLOAD_PROGRESS
+        .read()
+        .0
+        .iter()
+        .filter(|(x, _)| x.collection_id == *collection_id)
+    {
+        let category = categories
+            .entry(DownloadCategory::of(&id.download_type))
+            .or_default();
+        category.jobs += 1;
+        if progress.percentages >= 100. {
+            category.finished += 1;
+        }
+        category.downloaded += progress.current_size.unwrap_or_default();
+        category.total += progress.total_size.unwrap_or_default();
+        category.speed += progress.speed.unwrap_or_default();
+    }
+    (categories, failures)
+}
+
+/// Pauses, resumes or cancels every queued download of a collection at once,
+/// with the progress of each category and the files that failed
+#[component]
+fn CollectionQueueControls(collection_id: ReadOnlySignal<CollectionId>) -> Element {
+    let radio = collection_id().use_collection_radio();
+    let jobs = use_memo(move || DOWNLOAD_QUEUE.read().collection_jobs(&collection_id.read()));
+    let breakdown = use_memo(move || collection_breakdown(&collection_id.read()));
+    let mut show_failures = use_signal(|| false);
+    let for_each = move |f: fn(&mut DownloadQueue, u64)| {
+        let mut queue = DOWNLOAD_QUEUE.write();
+        for id in jobs() {
+            f(&mut queue, id);
+        }
+    };
+    let (categories, failures) = breakdown();
+    let eta = display_eta(
+        categories
+            .values()
+            .map(|x| (x.total - x.downloaded).max(0.))
+            .sum(),
+        categories.values().map(|x| x.speed).sum(),
+    );
+    rsx! {
+        div {
+            class: "flex flex-col gap-[10px] px-[20px] py-[10px] rounded-[15px] bg-deep-background",
+            div {
+                class: "flex items-center gap-[15px]",
+                div {
+                    class: "grow text-white text-[18px] font-bold trim",
+                    {radio.read().display_name().clone()}
+                }
+                if let Some(eta) = eta {
+                    div {
+                        class: "text-hint text-[16px] font-medium trim",
+                        {t!("download-eta", time = eta)}
+                    }
+                }
+                button {
+                    class: "text-white text-[16px] trim",
+                    onclick: move |_| for_each(|queue, id| queue.pause(id)),
+                    {t!("download-pause")}
+                }
+                button {
+                    class: "text-white text-[16px] trim",
+                    onclick: move |_| for_each(|queue, id| queue.resume(id)),
+                    {t!("download-resume")}
+                }
+                button {
+                    class: "text-red text-[16px] trim",
+                    onclick: move |_| for_each(|queue, id| queue.cancel(id)),
+                    {t!("download-cancel")}
+                }
+            }
+            for (category , progress) in categories {
+                CategoryRow {
+                    key: "{category:?}",
+                    category,
+                    progress,
+                }
+            }
+            if !failures.is_empty() {
+                button {
+                    class: "self-start text-red text-[16px] font-medium trim",
+                    onclick: move |_| show_failures.toggle(),
+                    {t!("download-failures", count = failures.len())}
+                }
+                if show_failures() {
+                    for (label , reason) in failures {
+                        div {
+                            class: "flex gap-[10px] text-[14px] font-medium",
+                            div {
+                                class: "flex-none text-white trim",
+                                {label}
+                            }
+                            div {
+                                class: "text-hint trim",
+                                {reason}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn CategoryRow(category: DownloadCategory, progress: CategoryProgress) -> Element {
+    let (downloaded, total) = (progress.downloaded, progress.total);
+    let width = if total > 0. {
+        downloaded / total * 100.
+    } else {
+        0.
+    };
+    rsx! {
+        div {
+            class: "flex items-center gap-[15px] text-[14px] font-medium",
+            div {
+                class: "w-[120px] flex-none text-white trim",
+                {category.title()}
+            }
+            div {
+                class: "w-[60px] flex-none text-hint trim",
+                "{progress.finished} / {progress.jobs}"
+            }
+            div {
+                class: "grow rounded-[50px] h-[5px] bg-zinc-800",
+                div {
+                    class: "transition-all rounded-[50px] bg-white h-[5px]",
+                    width: format!("{width}%"),
+                }
+            }
+            div {
+                class: "flex-none text-hint trim",
+                {
+                    format!(
+                        "{} / {}",
+                        downloaded.display_size_from_megabytes(),
+                        total.display_size_from_megabytes(),
+                    )
+                }
+            }
+        }
+    }
+}
+
+/// A download started by the frontend, dragged around to change its priority
+#[component]
+fn QueueItem(job: DownloadJob, mut dragging: Signal<Option<u64>>) -> Element {
+    let id = job.id;
+    #[allow(clippy::cast_precision_loss)]
+    let (downloaded, total) = (job.downloaded as f64, job.total.map(|x| x as f64));
+    let width = total
+        .filter(|x| *x > 0.)
+        .map_or(0., |total| downloaded / total * 100.);
+    let status = match &job.state {
+        JobState::Queued => t!("download-queued"),
+        JobState::Paused => t!("download-paused"),
+        JobState::Failed(err) => t!("download-failed", error = err.clone()),
+        JobState::Running => job.eta().map_or_else(
+            || format!("{}/s", job.speed.display_size_from_megabytes()),
+            |eta| {
+                format!(
+                    "{}/s | {}",
+                    job.speed.display_size_from_megabytes(),
+                    t!("download-eta", time = eta)
+                )
+            },
+        ),
+        JobState::Finished | JobState::Cancelled => {
+            format!("{}/s", job.speed.display_size_from_megabytes())
+        }
+    };
+    rsx! {
+        div {
+            class: "flex gap-[15px] p-[15px] rounded-[20px] bg-background aria-busy:opacity-50",
+            aria_busy: dragging() == Some(id),
+            draggable: !job.state.is_done(),
+            ondragstart: move |_| dragging.set(Some(id)),
+            ondragend: move |_| dragging.set(None),
+            ondragover: move |e| e.prevent_default(),
+            ondrop: move |e| {
+                e.prevent_default();
+                if let Some(from) = dragging.take() {
+                    DOWNLOAD_QUEUE.write().move_before(from, id);
+                }
+            },
+            {ContentType::svg(DRAG_INDICATOR).css("self-center svg-[30px]")}
+            div {
+                class: "w-full flex flex-col gap-[10px]",
+                div {
+                    class: "flex gap-[10px]",
+                    div {
+                        class: "grow text-[20px] font-bold trim",
+                        {job.label.clone()}
+                    }
+                    JobControls {
+                        job: job.clone(),
+                    }
+                }
+                div {
+                    class: "flex gap-[4px]",
+                    div {
+                        class: "text-base font-semibold text-hint trim",
+                        {
+                            format!(
+                                "{} / {} |",
+                                downloaded.display_size_from_megabytes(),
+                                total.unwrap_or_default().display_size_from_megabytes(),
+                            )
+                        }
+                    }
+                    div {
+                        class: "text-base font-semibold trim",
+                        {status}
+                    }
+                }
+                div {
+                    class: "rounded-[50px] w-full h-[7px] bg-zinc-800",
+                    div {
+                        class: "transition-all rounded-[50px] bg-white h-[7px]",
+                        width: format!("{width}%"),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Downloads waiting for their turn, in the order they will run.
+///
+/// Backend downloads that already report progress are listed with it instead.
+#[component]
+fn DownloadQueueView(
+    dragging: Signal<Option<u64>>,
+    progressing: ReadOnlySignal<Vec<CollectionId>>,
+) -> Element {
+    let collections = use_memo(move || {
+        let mut collections = Vec::new();
+        for collection_id in DOWNLOAD_QUEUE
+            .read()
+            .jobs
+            .iter()
+            .filter(|x| !x.state.is_done() || matches!(x.state, JobState::Failed(_)))
+            .filter_map(|x| x.collection_id.clone())
+        {
+            if !collections.contains(&collection_id) {
+                collections.push(collection_id);
+            }
+        }
+        collections
+    });
+    let jobs = DOWNLOAD_QUEUE
+        .read()
+        .jobs
+        .iter()
+        .filter(|x| !x.state.is_done())
+        .filter(|x| {
+            x.source != JobSource::Backend
+                || x.collection_id
+                    .as_ref()
+                    .map_or(true, |x| !progressing.read().contains(x))
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    rsx! {
+        div {
+            class: "flex flex-col gap-[10px]",
+            for collection_id in collections() {
+                CollectionQueueControls {
+                    key: "{collection_id:?}",
+                    collection_id,
+                }
+            }
+            for job in jobs {
+                QueueItem {
+                    key: "{job.id}",
+                    job,
+                    dragging,
+                }
             }
         }
     }
 }
 
+/// A finished or failed download, failed ones coming from the queue can be retried
+#[component]
+fn HistoryItem(entry: HistoryEntry) -> Element {
+    let id = entry.id;
+    let retry = entry.retry.clone();
+    let retryable = entry.retryable();
+    let (status, failed) = match &entry.outcome {
+        Outcome::Completed => (
+            t!(
+                "download-average-speed",
+                speed = entry.average_speed().display_size_from_megabytes()
+            ),
+            false,
+        ),
+        Outcome::Failed { reason } => (t!("download-failed", error = reason.clone()), true),
+    };
+    rsx! {
+        div {
+            class: "flex gap-[15px] p-[15px] rounded-[20px] bg-background",
+            div {
+                class: "w-full flex flex-col gap-[10px]",
+                div {
+                    class: "flex gap-[10px]",
+                    div {
+                        class: "grow text-[20px] font-bold trim",
+                        {entry.label.clone()}
+                    }
+                    if let Some(retry) = retry.filter(|_| failed) {
+                        button {
+                            class: "text-white text-[16px] trim disabled:opacity-30",
+                            // the follow-up only lives as long as the session, or belongs to a whole import
+                            disabled: !retryable,
+                            title: if retryable { String::new() } else { t!("download-retry-unavailable") },
+                            onclick: move |_| {
+                                let Some(entry) = DOWNLOAD_HISTORY.write().remove(id) else {
+                                    return;
+                                };
+                                let follow_up = FOLLOW_UPS.write().remove(&id).unwrap_or_default();
+                                let retry = retry.clone();
+                                // the outcome of the retry lands in the history again
+                                spawn(async move {
+                                    let _ = download_queue::download(
+                                        entry.collection_id,
+                                        entry.label,
+                                        retry.url,
+                                        retry.destination,
+                                        retry.sha1,
+                                        follow_up,
+                                    )
+                                    .await;
+                                });
+                            },
+                            {t!("download-retry")}
+                        }
+                    }
+                    button {
+                        class: "text-hint text-[16px] trim",
+                        onclick: move |_| {
+                            DOWNLOAD_HISTORY.write().remove(id);
+                            FOLLOW_UPS.write().remove(&id);
+                        },
+                        {t!("download-remove")}
+                    }
+                }
+                div {
+                    class: "flex gap-[4px]",
+                    div {
+                        class: "text-base font-semibold text-hint trim",
+                        {
+                            format!(
+                                "{} | {} |",
+                                entry.finished.format("%Y.%m.%d %H:%M"),
+                                entry.total_size.display_size_from_megabytes(),
+                            )
+                        }
+                    }
+                    div {
+                        class: "text-base font-semibold trim aria-invalid:text-red",
+                        aria_invalid: failed,
+                        {status}
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn DownloadHistoryView() -> Element {
+    let entries = DOWNLOAD_HISTORY.read().entries.clone();
+    rsx! {
+        div {
+            class: "flex flex-col gap-[10px]",
+            if entries.is_empty() {
+                div {
+                    class: "text-hint text-[16px] font-medium trim",
+                    {t!("download-history-empty")}
+                }
+            } else {
+                button {
+                    class: "self-end text-white text-[16px] trim",
+                    onclick: move |_| {
+                        DOWNLOAD_HISTORY.write().clear();
+                        FOLLOW_UPS.write().clear();
+                    },
+                    {t!("download-history-clear")}
+                }
+            }
+            for entry in entries {
+                HistoryItem {
+                    key: "{entry.id}",
+                    entry,
+                }
+            }
+        }
+    }
+}
+
+/// Records backend downloads into the history once they reach 100%, and persists the history
+fn use_history_recorder() {
+    let collections = use_collections_radio();
+    let mut tracked = use_hook(|| CopyValue::new(HashMap::<CollectionId, DateTime<Local>>::new()));
+    use_effect(move || {
+        let progress = DOWNLOAD_PROGRESS();
+        let mut tracked = tracked.write();
+        for (id, progress) in progress.0 {
+            let collection_id = id.collection_id;
+            if progress.percentages < 100. {
+                tracked.entry(collection_id).or_insert_with(Local::now);
+            } else if let Some(started) = tracked.remove(&collection_id) {
+                let label = collections
+                    .read()
+                    .0
+                    .get(&collection_id)
+                    .map(|x| x.display_name().clone())
+                    .unwrap_or_default();
+                notify(
+                    NotificationKind::DownloadFinished,
+                    t!("notification-collection-downloaded"),
+                    label.clone(),
+                );
+                DOWNLOAD_HISTORY.write().record(HistoryEntry::new(
+                    Some(collection_id),
+                    label,
+                    None,
+                    started,
+                    progress.total_size.unwrap_or_default(),
+                    Outcome::Completed,
+                ));
+            }
+        }
+    });
+
+    use_effect(move || {
+        if let Err(err) = DOWNLOAD_HISTORY.read().save_to(&data_dir()) {
+            toast_error(err);
+        }
+    });
+}
+
 #[component]
 pub fn DownloadProgress() -> Element {
-    let progress = DOWNLOAD_PROGRESS()
+    use_history_recorder();
+    let state = use_signal(|| (ProgressState::Running, None));
+    let running = state.read().0 == ProgressState::Running;
+    let dragging = use_signal(|| None::<u64>);
+    let mut progress = DOWNLOAD_PROGRESS()
         .0
         .into_iter()
         .filter(|(_, x)| x.percentages < 100.)
         .map(|(id, progress)| (id.collection_id, progress))
-        .enumerate();
+        .collect::<Vec<_>>();
+    // the backend reports in no particular order, the queue decides which runs first
+    let position = |collection_id: &CollectionId| {
+        DOWNLOAD_QUEUE
+            .read()
+            .jobs
+            .iter()
+            .position(|x| {
+                x.source == JobSource::Backend && x.collection_id.as_ref() == Some(collection_id)
+            })
+            .unwrap_or(usize::MAX)
+    };
+    progress.sort_by_cached_key(|(collection_id, _)| position(collection_id));
+    let progressing = progress
+        .iter()
+        .map(|(collection_id, _)| collection_id.clone())
+        .collect::<Vec<_>>();
     rsx! {
         div {
             class: "flex flex-col gap-[20px]",
-            for (u , (collection_id , progress)) in progress {
-                if u == 0 {
-                    FirstProgressView {
-                        collection_id: collection_id.clone(),
-                        progress: progress.clone(),
-                    }
-                    ProgressStateBar {
-                    
-
+            if let Some((collection_id, progress)) = progress.first().filter(|_| running) {
+                FirstProgressView {
+                    key: "{collection_id:?}",
+                    collection_id: collection_id.clone(),
+                    progress: progress.clone(),
+                }
+            }
+            ProgressStateBar { state }
+            if running {
+                for (collection_id , progress) in progress {
+                    ListItem {
+                        collection_id,
+                        progress,
+                        dragging,
                     }
                 }
-                ListItem {
-                    collection_id,
-                    progress,
+                DownloadQueueView {
+                    dragging,
+                    progressing,
                 }
+            } else {
+                DownloadHistoryView {}
             }
         }
     }
@@ -0,0 +1,1056 @@
+use std::{
+    fmt::Display,
+    future::Future,
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+
+use chrono::Local;
+use dioxus::prelude::*;
+use dioxus_logger::tracing::info;
+use futures_util::future::LocalBoxFuture;
+use rust_lib::api::{
+    backend_exclusive::download::DownloadType,
+    shared_resources::{collection::CollectionId, entry::DOWNLOAD_PROGRESS},
+};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use snafu::prelude::*;
+use tokio::{io::AsyncWriteExt, sync::Notify};
+
+use crate::{
+    download_history::{HistoryEntry, Outcome, RetryTarget, DOWNLOAD_HISTORY, FOLLOW_UPS},
+    notifications::{notify, NotificationKind},
+    persistence::{data_dir, Persisted},
+    platform_api, t,
+};
+
+/// Downloads started by the frontend, e.g. mod installs, in priority order
+pub static DOWNLOAD_QUEUE: GlobalSignal<DownloadQueue> = Signal::global(DownloadQueue::default);
+
+pub static QUEUE_SETTINGS: GlobalSignal<QueueSettings> =
+    Signal::global(|| QueueSettings::load_or_create(&data_dir()));
+
+/// Woken whenever a job changes state, so waiting jobs can check whether they may run
+static CHANGED: LazyLock<Notify> = LazyLock::new(Notify::new);
+
+static THROTTLE: LazyLock<Mutex<Throttle>> = LazyLock::new(|| {
+    Mutex::new(Throttle {
+        window_start: Instant::now(),
+        bytes: 0,
+    })
+});
+
+#[derive(Snafu, Debug)]
+pub enum DownloadError {
+    #[snafu(display("Failed to request {url}"))]
+    Request { url: String, source: reqwest::Error },
+    #[snafu(display("Failed to write {}", path.display()))]
+    Write {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("{url} doesn't match its published hash"))]
+    HashMismatch { url: String },
+    #[snafu(display("Failed to set up {} after downloading it: {source}", path.display()))]
+    FollowUp {
+        path: PathBuf,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[snafu(display("The download of {url} has been cancelled"))]
+    Cancelled { url: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QueueSettings {
+    /// How many jobs download at once
+    pub max_concurrent: usize,
+    /// Bytes per second shared by every job, `None` is unlimited
+    pub bandwidth_limit: Option<u64>,
+}
+
+impl Default for QueueSettings {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 3,
+            bandwidth_limit: None,
+        }
+    }
+}
+
+impl Persisted for QueueSettings {
+    const FILE_NAME: &'static str = "download_queue.json";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobState {
+    Queued,
+    Running,
+    Paused,
+    Finished,
+    Failed(String),
+    Cancelled,
+}
+
+impl JobState {
+    #[must_use]
+    pub const fn is_done(&self) -> bool {
+        matches!(self, Self::Finished | Self::Failed(_) | Self::Cancelled)
+    }
+}
+
+/// What a download fetches, as the backend reports it for the game files of a collection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DownloadCategory {
+    Assets,
+    Libraries,
+    Loader,
+    Mods,
+    Other,
+}
+
+impl DownloadCategory {
+    #[must_use]
+    pub const fn of(download_type: &DownloadType) -> Self {
+        match download_type {
+            DownloadType::GameAsset => Self::Assets,
+            DownloadType::GameLibrary => Self::Libraries,
+            DownloadType::ModLoader => Self::Loader,
+            DownloadType::ModDownload => Self::Mods,
+            // e.g. the java runtime
+            _ => Self::Other,
+        }
+    }
+
+    /// Downloads of the queue are told apart by the folder they're written into
+    #[must_use]
+    pub fn from_destination(destination: &Path) -> Self {
+        let folder = destination
+            .parent()
+            .and_then(Path::file_name)
+            .and_then(|x| x.to_str());
+        match folder {
+            Some("mods") => Self::Mods,
+            _ => Self::Other,
+        }
+    }
+
+    #[must_use]
+    pub fn title(self) -> String {
+        match self {
+            Self::Assets => t!("download-category-assets"),
+            Self::Libraries => t!("download-category-libraries"),
+            Self::Loader => t!("download-category-loader"),
+            Self::Mods => t!("download-category-mods"),
+            Self::Other => t!("download-category-other"),
+        }
+    }
+}
+
+/// What happens to a file once it's downloaded
+#[derive(Clone, Default)]
+pub enum FollowUp {
+    #[default]
+    Nothing,
+    /// Run once the file is in place, and again when the download is retried from the history
+    Run(Rc<dyn Fn(PathBuf) -> LocalBoxFuture<'static, anyhow::Result<()>>>),
+    /// Done by the caller together with other files, so the download can't be retried on its own
+    Caller,
+}
+
+/// Who does the downloading of a job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobSource {
+    /// Streamed by the queue itself
+    Url {
+        url: String,
+        destination: PathBuf,
+        /// Published hash the file is checked against
+        sha1: Option<String>,
+    },
+    /// Files the backend fetches for a collection, e.g. before launching it, its progress is in
+    /// `DOWNLOAD_PROGRESS`.
+    ///
+    /// The backend downloads and launches in one go, so once running these can't be paused or cancelled.
+    Backend,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DownloadJob {
+    pub id: u64,
+    pub collection_id: Option<CollectionId>,
+    pub label: String,
+    pub source: JobSource,
+    /// in bytes, `None` until the server tells
+    pub total: Option<u64>,
+    pub downloaded: u64,
+    /// bytes per second
+    pub speed: f64,
+    pub state: JobState,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DownloadQueue {
+    pub jobs: Vec<DownloadJob>,
+    next_id: u64,
+    /// Jobs finished since the queue was last empty
+    finished: usize,
+}
+
+impl DownloadJob {
+    #[must_use]
+    pub fn can_pause(&self) -> bool {
+        matches!(self.source, JobSource::Url { .. }) && !self.state.is_done()
+    }
+
+    /// Backend jobs only until they start, see [`JobSource::Backend`]
+    #[must_use]
+    pub fn can_cancel(&self) -> bool {
+        match self.source {
+            JobSource::Url { .. } => !self.state.is_done(),
+            JobSource::Backend => self.state == JobState::Queued,
+        }
+    }
+
+    #[must_use]
+    pub fn category(&self) -> DownloadCategory {
+        match &self.source {
+            JobSource::Url { destination, .. } => DownloadCategory::from_destination(destination),
+            JobSource::Backend => DownloadCategory::Other,
+        }
+    }
+}
+
+impl DownloadQueue {
+    fn push(
+        &mut self,
+        collection_id: Option<CollectionId>,
+        label: String,
+        source: JobSource,
+    ) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.jobs.push(DownloadJob {
+            id,
+            collection_id,
+            label,
+            source,
+            total: None,
+            downloaded: 0,
+            speed: 0.,
+            state: JobState::Queued,
+        });
+        CHANGED.notify_waiters();
+        id
+    }
+
+    fn job_mut(&mut self, id: u64) -> Option<&mut DownloadJob> {
+        self.jobs.iter_mut().find(|x| x.id == id)
+    }
+
+    #[must_use]
+    pub fn job(&self, id: u64) -> Option<&DownloadJob> {
+        self.jobs.iter().find(|x| x.id == id)
+    }
+
+    /// Whether job `id` is among the first [`QueueSettings::max_concurrent`] jobs that want to run
+    fn may_run(&self, id: u64, max_concurrent: usize) -> bool {
+        self.jobs
+            .iter()
+            .filter(|x| matches!(x.state, JobState::Queued | JobState::Running))
+            .take(max_concurrent.max(1))
+            .any(|x| x.id == id)
+    }
+
+    /// Moves job `id` in front of job `before`, giving it its priority
+    pub fn move_before(&mut self, id: u64, before: u64) {
+        let Some(from) = self.jobs.iter().position(|x| x.id == id) else {
+            return;
+        };
+        let job = self.jobs.remove(from);
+        let to = self
+            .jobs
+            .iter()
+            .position(|x| x.id == before)
+            .unwrap_or(self.jobs.len());
+        self.jobs.insert(to, job);
+        CHANGED.notify_waiters();
+    }
+
+    fn set_state(&mut self, id: u64, state: JobState) {
+        if let Some(job) = self.job_mut(id) {
+            if !job.state.is_done() {
+                job.state = state;
+            }
+        }
+        CHANGED.notify_waiters();
+    }
+
+    pub fn pause(&mut self, id: u64) {
+        if self.job(id).is_some_and(DownloadJob::can_pause) {
+            self.set_state(id, JobState::Paused);
+        }
+    }
+
+    pub fn resume(&mut self, id: u64) {
+        if self.job(id).is_some_and(|x| x.state == JobState::Paused) {
+            self.set_state(id, JobState::Queued);
+        }
+    }
+
+    pub fn cancel(&mut self, id: u64) {
+        if self.job(id).is_some_and(DownloadJob::can_cancel) {
+            self.set_state(id, JobState::Cancelled);
+        }
+    }
+
+    /// Ids of the unfinished jobs belonging to `collection_id`
+    #[must_use]
+    pub fn collection_jobs(&self, collection_id: &CollectionId) -> Vec<u64> {
+        self.jobs
+            .iter()
+            .filter(|x| x.collection_id.as_ref() == Some(collection_id) && !x.state.is_done())
+            .map(|x| x.id)
+            .collect()
+    }
+
+    /// The unfinished backend job of `collection_id`
+    #[must_use]
+    pub fn backend_job(&self, collection_id: &CollectionId) -> Option<&DownloadJob> {
+        self.jobs.iter().find(|x| {
+            x.source == JobSource::Backend
+                && x.collection_id.as_ref() == Some(collection_id)
+                && !x.state.is_done()
+        })
+    }
+
+    /// Removes every finished, failed and cancelled job
+    pub fn clear_done(&mut self) {
+        self.jobs.retain(|x| !x.state.is_done());
+    }
+}
+
+struct Throttle {
+    window_start: Instant,
+    bytes: u64,
+}
+
+/// Waits long enough for `bytes` to fit in the bandwidth limit shared by every job
+async fn throttle(bytes: u64) {
+    let Some(limit) = QUEUE_SETTINGS.peek().bandwidth_limit.filter(|x| *x > 0) else {
+        return;
+    };
+    let wait = {
+        let Ok(mut throttle) = THROTTLE.lock() else {
+            return;
+        };
+        let elapsed = throttle.window_start.elapsed();
+        if elapsed >= Duration::from_secs(1) {
+            throttle.window_start = Instant::now();
+            throttle.bytes = 0;
+        }
+        throttle.bytes += bytes;
+        if throttle.bytes <= limit {
+            return;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let over = throttle.bytes as f64 / limit as f64;
+        Duration::from_secs_f64(over).saturating_sub(elapsed)
+    };
+    tokio::time::sleep(wait).await;
+}
+
+/// Waits until job `id` may run, returning `false` if it has been cancelled meanwhile
+async fn wait_for_turn(id: u64) -> bool {
+    loop {
+        let notified = CHANGED.notified();
+        let max_concurrent = QUEUE_SETTINGS.peek().max_concurrent;
+        let (state, may_run) = {
+            let queue = DOWNLOAD_QUEUE.peek();
+            (
+                queue.job(id).map(|x| x.state.clone()),
+                queue.may_run(id, max_concurrent),
+            )
+        };
+        match state {
+            Some(JobState::Cancelled) | None => return false,
+            Some(JobState::Queued | JobState::Running) if may_run => {
+                if let Some(job) = DOWNLOAD_QUEUE.write().job_mut(id) {
+                    job.state = JobState::Running;
+                }
+                return true;
+            }
+            _ => {}
+        }
+        notified.await;
+    }
+}
+
+enum Interrupted {
+    Finished,
+    Paused,
+    Cancelled,
+}
+
+/// Streams `url` into `part`, continuing from what's already there
+async fn transfer(id: u64, url: &str, part: &Path) -> Result<Interrupted, DownloadError> {
+    let offset = tokio::fs::metadata(part).await.map_or(0, |x| x.len());
+    let mut request = platform_api::client().get(url);
+    if offset > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={offset}-"));
+    }
+    let mut response = request
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .context(RequestSnafu { url })?;
+
+    // servers that ignore ranges send the whole file again
+    let resumed = response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    let mut downloaded = if resumed { offset } else { 0 };
+    let total = response.content_length().map(|x| x + downloaded);
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(part)
+        .await
+        .context(WriteSnafu { path: part })?;
+
+    let mut last = (Instant::now(), downloaded);
+    let mut report = |downloaded: u64, force: bool| {
+        let elapsed = last.0.elapsed();
+        if !force && elapsed < Duration::from_millis(250) {
+            return;
+        }
+        if let Some(job) = DOWNLOAD_QUEUE.write().job_mut(id) {
+            job.total = total;
+            job.downloaded = downloaded;
+            #[allow(clippy::cast_precision_loss)]
+            let speed = (downloaded - last.1) as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+            job.speed = speed;
+        }
+        last = (Instant::now(), downloaded);
+    };
+    while let Some(chunk) = response.chunk().await.context(RequestSnafu { url })? {
+        file.write_all(&chunk)
+            .await
+            .context(WriteSnafu { path: part })?;
+        downloaded += chunk.len() as u64;
+        throttle(chunk.len() as u64).await;
+
+        let state = DOWNLOAD_QUEUE.peek().job(id).map(|x| x.state.clone());
+        match state {
+            Some(JobState::Paused) => {
+                report(downloaded, true);
+                return Ok(Interrupted::Paused);
+            }
+            Some(JobState::Cancelled) | None => return Ok(Interrupted::Cancelled),
+            _ => report(downloaded, false),
+        }
+    }
+    report(downloaded, true);
+    file.flush().await.context(WriteSnafu { path: part })?;
+    Ok(Interrupted::Finished)
+}
+
+/// Whether the file at `path` has the hex encoded `sha1`
+async fn matches_sha1(path: &Path, sha1: &str) -> Result<bool, DownloadError> {
+    let bytes = tokio::fs::read(path).await.context(WriteSnafu { path })?;
+    Ok(hex::encode(Sha1::digest(&bytes)).eq_ignore_ascii_case(sha1))
+}
+
+/// Downloads `url` to `destination` through the queue, resolving once the file is complete.
+///
+/// The job can be paused, resumed, cancelled and reordered from the downloads page meanwhile.
+/// With `sha1` the file is only put in place once it matches, then `follow_up` runs on it.
+///
+/// # Errors
+///
+/// This function will return an error if the request fails, the file cannot be written, doesn't match
+/// `sha1`, the follow-up fails or the job gets cancelled.
+pub async fn download(
+    collection_id: Option<CollectionId>,
+    label: String,
+    url: String,
+    destination: PathBuf,
+    sha1: Option<String>,
+    follow_up: FollowUp,
+) -> Result<PathBuf, DownloadError> {
+    let mut part = destination.clone().into_os_string();
+    part.push(".part");
+    let part = PathBuf::from(part);
+    if let Some(parent) = destination.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .context(WriteSnafu { path: parent })?;
+    }
+
+    let id = DOWNLOAD_QUEUE.write().push(
+        collection_id,
+        label,
+        JobSource::Url {
+            url: url.clone(),
+            destination: destination.clone(),
+            sha1: sha1.clone(),
+        },
+    );
+    info!("Queued download of {url}");
+
+    let mut started = None;
+    let result = loop {
+        if !wait_for_turn(id).await {
+            break Err(DownloadError::Cancelled { url: url.clone() });
+        }
+        started.get_or_insert_with(Local::now);
+        match transfer(id, &url, &part).await {
+            Ok(Interrupted::Finished) => {
+                if let Some(sha1) = &sha1 {
+                    match matches_sha1(&part, sha1).await {
+                        Ok(true) => {}
+                        Ok(false) => break Err(DownloadError::HashMismatch { url: url.clone() }),
+                        Err(err) => break Err(err),
+                    }
+                }
+                if let Err(err) = tokio::fs::rename(&part, &destination).await {
+                    break Err(err).context(WriteSnafu {
+                        path: destination.clone(),
+                    });
+                }
+                if let FollowUp::Run(follow_up) = &follow_up {
+                    if let Err(err) = follow_up(destination.clone()).await {
+                        break Err(DownloadError::FollowUp {
+                            path: destination.clone(),
+                            source: err.into(),
+                        });
+                    }
+                }
+                break Ok(destination.clone());
+            }
+            Ok(Interrupted::Paused) => {
+                if let Some(job) = DOWNLOAD_QUEUE.write().job_mut(id) {
+                    job.speed = 0.;
+                }
+                CHANGED.notify_waiters();
+            }
+            Ok(Interrupted::Cancelled) => break Err(DownloadError::Cancelled { url: url.clone() }),
+            Err(err) => break Err(err),
+        }
+    };
+
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(&part).await;
+    }
+    let (job, drained) = {
+        let mut queue = DOWNLOAD_QUEUE.write();
+        let job = queue.job_mut(id).map(|job| {
+            job.speed = 0.;
+            job.state = match &result {
+                Ok(_) => JobState::Finished,
+                Err(DownloadError::Cancelled { .. }) => JobState::Cancelled,
+                Err(err) => JobState::Failed(err.to_string()),
+            };
+            job.clone()
+        });
+        if result.is_ok() {
+            queue.finished += 1;
+        }
+        let drained = (queue.finished > 0 && queue.jobs.iter().all(|x| x.state.is_done()))
+            .then(|| std::mem::take(&mut queue.finished));
+        (job, drained)
+    };
+    if let Some(count) = drained {
+        notify(
+            NotificationKind::DownloadFinished,
+            t!("notification-downloads-finished"),
+            t!("notification-downloads-finished-body", count = count),
+        );
+    }
+    if let (Some(job), Some(started)) = (job, started) {
+        let outcome = match &result {
+            Ok(_) => Some(Outcome::Completed),
+            Err(DownloadError::Cancelled { .. }) => None,
+            Err(err) => Some(Outcome::Failed {
+                reason: err.to_string(),
+            }),
+        };
+        if let Some(outcome) = outcome {
+            #[allow(clippy::cast_precision_loss)]
+            let total_size = job.downloaded as f64;
+            let failed = outcome != Outcome::Completed;
+            let entry = HistoryEntry::new(
+                job.collection_id,
+                job.label,
+                Some(RetryTarget {
+                    url,
+                    destination,
+                    sha1,
+                    follow_up: !matches!(follow_up, FollowUp::Nothing),
+                }),
+                started,
+                total_size,
+                outcome,
+            );
+            if failed && matches!(follow_up, FollowUp::Run(_)) {
+                FOLLOW_UPS.write().insert(entry.id, follow_up);
+            }
+            DOWNLOAD_HISTORY.write().record(entry);
+        }
+    }
+    CHANGED.notify_waiters();
+    result
+}
+
+/// Runs `task`, in which the backend downloads files for `collection_id`, as a job of the queue.
+///
+/// The task waits for its turn like any other job and isn't started when cancelled meanwhile. Once it
+/// runs it goes on until it's done, since it launches the game too, see [`JobSource::Backend`]. Its
+/// progress is reported by the backend, a failure before it completes is recorded in the history.
+///
+/// # Errors
+///
+/// This function will return an error if the job gets cancelled before it starts.
+pub async fn run_backend<T, E: Display>(
+    collection_id: CollectionId,
+    label: String,
+    task: impl Future<Output = Result<T, E>>,
+) -> Result<Result<T, E>, DownloadError> {
+    let id = DOWNLOAD_QUEUE.write().push(
+        Some(collection_id.clone()),
+        label.clone(),
+        JobSource::Backend,
+    );
+    let started = wait_for_turn(id).await.then(Local::now);
+    let result = match started {
+        Some(_) => Ok(task.await),
+        None => Err(DownloadError::Cancelled { url: label.clone() }),
+    };
+    let state = match &result {
+        Ok(Ok(_)) => JobState::Finished,
+        Ok(Err(err)) => JobState::Failed(err.to_string()),
+        Err(_) => JobState::Cancelled,
+    };
+    if let (JobState::Failed(reason), Some(started)) = (&state, started) {
+        // a launch can also fail once everything is downloaded, which is no download failure
+        let progress = DOWNLOAD_PROGRESS
+            .peek()
+            .0
+            .iter()
+            .filter(|(x, _)| x.collection_id == collection_id)
+            .map(|(_, x)| (x.percentages, x.current_size.unwrap_or_default()))
+            .collect::<Vec<_>>();
+        if progress.is_empty() || progress.iter().any(|(percentages, _)| *percentages < 100.) {
+            DOWNLOAD_HISTORY.write().record(HistoryEntry::new(
+                Some(collection_id),
+                label,
+                None,
+                started,
+                progress.iter().map(|(_, size)| size).sum(),
+                Outcome::Failed {
+                    reason: reason.clone(),
+                },
+            ));
+        }
+    }
+    DOWNLOAD_QUEUE.write().set_state(id, state);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, pin::pin, sync::Arc};
+
+    use futures_util::future::{select, Either};
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+    use crate::test_support::block_on_in_dom;
+
+    /// How the test server splits and paces the body, slow enough to pause and cancel halfway
+    const CHUNK: usize = 1_024;
+    const CHUNK_DELAY: Duration = Duration::from_millis(20);
+
+    /// Serves a single file over http, a chunk at a time, honoring `Range: bytes=N-`
+    struct Server {
+        url: String,
+        /// Offset asked for by each request, in order
+        ranges: Arc<Mutex<Vec<u64>>>,
+    }
+
+    async fn serve(body: Vec<u8>) -> Server {
+        serve_paced(body, CHUNK_DELAY).await
+    }
+
+    /// [`serve`] with `delay` between the chunks
+    async fn serve_paced(body: Vec<u8>, delay: Duration) -> Server {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("a local port is free");
+        let url = format!(
+            "http://{}/file.jar",
+            listener.local_addr().expect("the listener is bound")
+        );
+        let ranges = Arc::new(Mutex::new(Vec::new()));
+        let recorded = ranges.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let body = body.clone();
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buffer = [0; 1_024];
+                    while !request.windows(4).any(|x| x == b"\r\n\r\n") {
+                        match stream.read(&mut buffer).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => request.extend_from_slice(&buffer[..n]),
+                        }
+                    }
+                    let offset = String::from_utf8_lossy(&request)
+                        .lines()
+                        .find_map(|x| {
+                            x.to_ascii_lowercase()
+                                .strip_prefix("range: bytes=")
+                                .and_then(|x| x.trim_end_matches('-').parse::<u64>().ok())
+                        })
+                        .unwrap_or(0);
+                    recorded.lock().expect("not poisoned").push(offset);
+                    #[allow(clippy::cast_possible_truncation)]
+                    let rest = &body[offset as usize..];
+                    let head = if offset > 0 {
+                        format!(
+                            "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {offset}-{}/{}\r\nConnection: close\r\n\r\n",
+                            rest.len(),
+                            body.len() - 1,
+                            body.len(),
+                        )
+                    } else {
+                        format!(
+                            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                            rest.len(),
+                        )
+                    };
+                    if stream.write_all(head.as_bytes()).await.is_err() {
+                        return;
+                    }
+                    for chunk in rest.chunks(CHUNK) {
+                        if stream.write_all(chunk).await.is_err() {
+                            return;
+                        }
+                        tokio::time::sleep(delay).await;
+                    }
+                });
+            }
+        });
+        Server { url, ranges }
+    }
+
+    fn destination(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "prag-portal-download-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join("mods").join("file.jar")
+    }
+
+    fn part_of(destination: &Path) -> PathBuf {
+        let mut part = destination.as_os_str().to_owned();
+        part.push(".part");
+        PathBuf::from(part)
+    }
+
+    fn body() -> Vec<u8> {
+        body_of(64 * CHUNK)
+    }
+
+    fn body_of(len: usize) -> Vec<u8> {
+        #[allow(clippy::cast_possible_truncation)]
+        (0..len).map(|x| (x % 251) as u8).collect()
+    }
+
+    /// Waits until some of the file has been written, so the job can be interrupted halfway
+    async fn started(part: &Path) {
+        while std::fs::metadata(part).map_or(true, |x| x.len() == 0) {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    }
+
+    fn first_job() -> u64 {
+        DOWNLOAD_QUEUE.peek().jobs[0].id
+    }
+
+    #[test]
+    fn completes_into_place() {
+        let body = body();
+        let sha1 = hex::encode(Sha1::digest(&body));
+        let destination = destination("complete");
+        let result = block_on_in_dom(async {
+            let server = serve(body.clone()).await;
+            download(
+                None,
+                String::from("file"),
+                server.url,
+                destination.clone(),
+                Some(sha1),
+                FollowUp::Nothing,
+            )
+            .await
+        });
+        assert_eq!(result.expect("the download succeeds"), destination);
+        assert_eq!(std::fs::read(&destination).expect("the file exists"), body);
+        assert!(!part_of(&destination).exists());
+    }
+
+    #[test]
+    fn rejects_a_hash_mismatch() {
+        let destination = destination("mismatch");
+        let result = block_on_in_dom(async {
+            let server = serve(body()).await;
+            download(
+                None,
+                String::from("file"),
+                server.url,
+                destination.clone(),
+                Some("0".repeat(40)),
+                FollowUp::Nothing,
+            )
+            .await
+        });
+        assert!(matches!(result, Err(DownloadError::HashMismatch { .. })));
+        assert!(!destination.exists());
+        assert!(!part_of(&destination).exists());
+    }
+
+    #[test]
+    fn resumes_with_a_range_request() {
+        let body = body();
+        let destination = destination("resume");
+        let part = part_of(&destination);
+        let (result, ranges) = block_on_in_dom(async {
+            let server = serve(body.clone()).await;
+            let control = async {
+                started(&part).await;
+                DOWNLOAD_QUEUE.write().pause(first_job());
+                // long enough for the transfer to notice and let go of the connection
+                tokio::time::sleep(CHUNK_DELAY * 5).await;
+                DOWNLOAD_QUEUE.write().resume(first_job());
+            };
+            let (result, ()) = futures_util::future::join(
+                download(
+                    None,
+                    String::from("file"),
+                    server.url,
+                    destination.clone(),
+                    None,
+                    FollowUp::Nothing,
+                ),
+                control,
+            )
+            .await;
+            let ranges = server.ranges.lock().expect("not poisoned").clone();
+            (result, ranges)
+        });
+        result.expect("the download succeeds");
+        assert_eq!(std::fs::read(&destination).expect("the file exists"), body);
+        assert_eq!(ranges.len(), 2, "{ranges:?}");
+        assert_eq!(ranges[0], 0);
+        assert!(ranges[1] > 0);
+    }
+
+    #[test]
+    fn cancelling_removes_the_partial_file() {
+        let destination = destination("cancel");
+        let part = part_of(&destination);
+        let result = block_on_in_dom(async {
+            let server = serve(body()).await;
+            let control = async {
+                started(&part).await;
+                DOWNLOAD_QUEUE.write().cancel(first_job());
+            };
+            let (result, ()) = futures_util::future::join(
+                download(
+                    None,
+                    String::from("file"),
+                    server.url,
+                    destination.clone(),
+                    None,
+                    FollowUp::Nothing,
+                ),
+                control,
+            )
+            .await;
+            result
+        });
+        assert!(matches!(result, Err(DownloadError::Cancelled { .. })));
+        assert!(!destination.exists());
+        assert!(!part.exists());
+    }
+
+    #[test]
+    fn runs_the_follow_up_on_the_file_in_place() {
+        let body = body();
+        let destination = destination("follow-up");
+        let seen = Rc::new(RefCell::new(None));
+        let recorded = seen.clone();
+        let follow_up = move |path: PathBuf| -> LocalBoxFuture<'static, anyhow::Result<()>> {
+            let recorded = recorded.clone();
+            Box::pin(async move {
+                *recorded.borrow_mut() = Some(std::fs::read(&path)?);
+                Ok(())
+            })
+        };
+        let result = block_on_in_dom(async {
+            let server = serve(body.clone()).await;
+            download(
+                None,
+                String::from("file"),
+                server.url,
+                destination.clone(),
+                None,
+                FollowUp::Run(Rc::new(follow_up)),
+            )
+            .await
+        });
+        result.expect("the download succeeds");
+        assert_eq!(*seen.borrow(), Some(body));
+    }
+
+    #[test]
+    fn a_failing_follow_up_fails_the_download() {
+        let destination = destination("failing-follow-up");
+        let follow_up = |_: PathBuf| -> LocalBoxFuture<'static, anyhow::Result<()>> {
+            Box::pin(async { anyhow::bail!("the mod manager is gone") })
+        };
+        let result = block_on_in_dom(async {
+            let server = serve(body()).await;
+            download(
+                None,
+                String::from("file"),
+                server.url,
+                destination.clone(),
+                None,
+                FollowUp::Run(Rc::new(follow_up)),
+            )
+            .await
+        });
+        assert!(matches!(result, Err(DownloadError::FollowUp { .. })));
+    }
+
+    #[test]
+    fn stays_within_the_bandwidth_and_concurrency_limits() {
+        const LIMIT: u64 = 256 * 1_024;
+        const MAX_CONCURRENT: usize = 2;
+        const JOBS: usize = 3;
+        let body = body_of(256 * CHUNK);
+        let destinations = (0..JOBS)
+            .map(|x| destination(&format!("limits-{x}")))
+            .collect::<Vec<_>>();
+        let (results, elapsed, most_running) = block_on_in_dom(async {
+            *QUEUE_SETTINGS.write() = QueueSettings {
+                max_concurrent: MAX_CONCURRENT,
+                bandwidth_limit: Some(LIMIT),
+            };
+            // served as fast as it goes, so only the queue holds it back
+            let server = serve_paced(body.clone(), Duration::ZERO).await;
+            let started = Instant::now();
+            let downloads = futures_util::future::join_all(destinations.iter().map(|x| {
+                download(
+                    None,
+                    String::from("file"),
+                    server.url.clone(),
+                    x.clone(),
+                    None,
+                    FollowUp::Nothing,
+                )
+            }));
+            let most_running = RefCell::new(0);
+            let watch = async {
+                loop {
+                    let running = DOWNLOAD_QUEUE
+                        .peek()
+                        .jobs
+                        .iter()
+                        .filter(|x| x.state == JobState::Running)
+                        .count();
+                    most_running.replace_with(|x| running.max(*x));
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                }
+            };
+            let results = match select(pin!(downloads), pin!(watch)).await {
+                Either::Left((results, _)) => results,
+                Either::Right(((), _)) => unreachable!("the watch never ends"),
+            };
+            (results, started.elapsed(), most_running.into_inner())
+        });
+        for (result, destination) in results.into_iter().zip(&destinations) {
+            result.expect("the download succeeds");
+            assert_eq!(std::fs::read(destination).expect("the file exists"), body);
+        }
+        assert_eq!(most_running, MAX_CONCURRENT);
+        // the first second may use up the whole limit at once, the rest has to wait for it
+        let total = (JOBS * body.len()) as u64;
+        #[allow(clippy::cast_precision_loss)]
+        let throughput = (total - LIMIT) as f64 / elapsed.as_secs_f64();
+        #[allow(clippy::cast_precision_loss)]
+        let limit = LIMIT as f64;
+        assert!(
+            throughput <= limit * 1.1,
+            "{throughput} bytes/s over {elapsed:?}"
+        );
+    }
+
+    #[test]
+    fn backend_jobs_can_only_be_cancelled_before_they_run() {
+        let mut queue = DownloadQueue::default();
+        let queued = queue.push(None, String::from("queued"), JobSource::Backend);
+        let running = queue.push(None, String::from("running"), JobSource::Backend);
+        queue.set_state(running, JobState::Running);
+        queue.pause(queued);
+        queue.pause(running);
+        queue.cancel(running);
+        assert_eq!(queue.job(queued).map(|x| &x.state), Some(&JobState::Queued));
+        assert_eq!(
+            queue.job(running).map(|x| &x.state),
+            Some(&JobState::Running)
+        );
+        queue.cancel(queued);
+        assert_eq!(
+            queue.job(queued).map(|x| &x.state),
+            Some(&JobState::Cancelled)
+        );
+    }
+
+    fn url_job(queue: &mut DownloadQueue, name: &str) -> u64 {
+        queue.push(
+            None,
+            name.to_owned(),
+            JobSource::Url {
+                url: format!("http://localhost/{name}.jar"),
+                destination: PathBuf::from(format!("{name}.jar")),
+                sha1: None,
+            },
+        )
+    }
+
+    #[test]
+    fn moving_a_job_changes_which_may_run() {
+        let mut queue = DownloadQueue::default();
+        let first = url_job(&mut queue, "first");
+        let second = url_job(&mut queue, "second");
+        assert!(queue.may_run(first, 1));
+        assert!(!queue.may_run(second, 1));
+        queue.move_before(second, first);
+        assert!(queue.may_run(second, 1));
+        assert!(!queue.may_run(first, 1));
+        queue.pause(second);
+        assert!(queue.may_run(first, 1));
+    }
+}
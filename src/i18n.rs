@@ -0,0 +1,187 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use dioxus::prelude::*;
+use dioxus_logger::tracing::{error, warn};
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource};
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+use unic_langid::LanguageIdentifier;
+
+use crate::{
+    persistence::{default_data_dir, Persisted},
+    settings::AppSettings,
+};
+
+/// The locale every string is rendered in
+pub static LOCALE: GlobalSignal<Locale> = Signal::global(Locale::initial);
+
+static BUNDLES: LazyLock<HashMap<Locale, FluentBundle<FluentResource>>> =
+    LazyLock::new(|| Locale::iter().map(|x| (x, x.bundle())).collect());
+
+/// Renders the message `key` in the current [`LOCALE`], optionally with `name = value` arguments.
+///
+/// ```ignore
+/// t!("mod-switch-source", platform = "Modrinth")
+/// ```
+#[macro_export]
+macro_rules! t {
+    ($key:literal) => {
+        $crate::i18n::translate($key, None)
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::translate($key, Some(&args))
+    }};
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
+pub enum Locale {
+    #[default]
+    #[serde(rename = "zh-TW")]
+    ZhTw,
+    #[serde(rename = "en-US")]
+    EnUs,
+}
+
+impl Locale {
+    #[must_use]
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::ZhTw => "zh-TW",
+            Self::EnUs => "en-US",
+        }
+    }
+
+    /// The name of the locale in its own language
+    #[must_use]
+    pub const fn native_name(self) -> &'static str {
+        match self {
+            Self::ZhTw => "繁體中文",
+            Self::EnUs => "English",
+        }
+    }
+
+    const fn source(self) -> &'static str {
+        match self {
+            Self::ZhTw => include_str!("../locales/zh-TW.ftl"),
+            Self::EnUs => include_str!("../locales/en-US.ftl"),
+        }
+    }
+
+    /// The locale chosen in the settings, or the one of the environment
+    fn initial() -> Self {
+        AppSettings::load_from(&default_data_dir())
+            .locale
+            .unwrap_or_else(Self::from_env)
+    }
+
+    /// Picks the locale from `LANG`, falling back to [`Locale::default`]
+    fn from_env() -> Self {
+        let lang = std::env::var("LANG").unwrap_or_default();
+        if lang.starts_with("en") {
+            Self::EnUs
+        } else {
+            Self::default()
+        }
+    }
+
+    fn bundle(self) -> FluentBundle<FluentResource> {
+        let langid = self.id().parse::<LanguageIdentifier>().unwrap_or_default();
+        let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+        // the unicode isolation marks render as boxes in some fonts
+        bundle.set_use_isolating(false);
+        let resource = FluentResource::try_new(self.source().to_owned()).unwrap_or_else(
+            |(resource, errors)| {
+                error!("{} has syntax errors: {errors:?}", self.id());
+                resource
+            },
+        );
+        if let Err(errors) = bundle.add_resource(resource) {
+            error!("{} has conflicting messages: {errors:?}", self.id());
+        }
+        bundle
+    }
+}
+
+fn format_in(locale: Locale, key: &str, args: Option<&FluentArgs>) -> Option<String> {
+    let bundle = BUNDLES.get(&locale)?;
+    let pattern = bundle.get_message(key)?.value()?;
+    let mut errors = Vec::new();
+    let value = bundle.format_pattern(pattern, args, &mut errors);
+    if !errors.is_empty() {
+        warn!("Failed to format {key} in {}: {errors:?}", locale.id());
+    }
+    Some(value.into_owned())
+}
+
+/// Use [`t!`] instead
+#[must_use]
+pub fn translate(key: &str, args: Option<&FluentArgs>) -> String {
+    let locale = LOCALE();
+    format_in(locale, key, args)
+        .or_else(|| format_in(Locale::default(), key, args))
+        .unwrap_or_else(|| {
+            warn!("Missing message {key} in {}", locale.id());
+            key.to_owned()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, path::Path};
+
+    use regex::Regex;
+    use walkdir::WalkDir;
+
+    use super::*;
+
+    /// Every message id defined in the bundle of `locale`
+    fn keys(locale: Locale) -> BTreeSet<&'static str> {
+        locale
+            .source()
+            .lines()
+            .filter(|x| x.starts_with(|x: char| x.is_ascii_alphabetic()))
+            .filter_map(|x| x.split_once('='))
+            .map(|(key, _)| key.trim())
+            .collect()
+    }
+
+    #[test]
+    fn bundles_define_the_same_messages() {
+        let all = Locale::iter().flat_map(keys).collect::<BTreeSet<_>>();
+        for locale in Locale::iter() {
+            let missing = all.difference(&keys(locale)).collect::<Vec<_>>();
+            assert!(missing.is_empty(), "{} is missing {missing:?}", locale.id());
+        }
+    }
+
+    #[test]
+    fn every_used_message_is_defined() {
+        let usage = Regex::new(r#"\bt!\(\s*"([^"]+)""#).expect("the pattern is valid");
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let mut used = BTreeSet::new();
+        for entry in WalkDir::new(src).into_iter().flatten() {
+            if entry.path().extension().map_or(true, |x| x != "rs") {
+                continue;
+            }
+            let content = std::fs::read_to_string(entry.path()).expect("sources are readable");
+            // doc examples use made up keys
+            for line in content
+                .lines()
+                .filter(|x| !x.trim_start().starts_with("//"))
+            {
+                used.extend(usage.captures_iter(line).map(|x| x[1].to_owned()));
+            }
+        }
+        assert!(!used.is_empty(), "no `t!` found, is the pattern outdated?");
+        for locale in Locale::iter() {
+            let keys = keys(locale);
+            let missing = used
+                .iter()
+                .filter(|x| !keys.contains(x.as_str()))
+                .collect::<Vec<_>>();
+            assert!(missing.is_empty(), "{} is missing {missing:?}", locale.id());
+        }
+    }
+}
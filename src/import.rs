@@ -0,0 +1,698 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    path::{Component, Path, PathBuf},
+};
+
+use dioxus::prelude::*;
+use dioxus_logger::tracing::{info, warn};
+use rust_lib::api::{
+    backend_exclusive::mod_management::mods::ModMetadata,
+    shared_resources::collection::{Collection, CollectionId},
+};
+use serde::Deserialize;
+use snafu::prelude::*;
+use zip::ZipArchive;
+
+use crate::{
+    collection_display::mod_renderer::{
+        table::ModSource,
+        unmanaged::{add_to_manager, push_to_manager},
+        versions::game_target,
+    },
+    download_queue::FollowUp,
+    platform_api::{self, PlatformApiError, RemoteFile},
+    t,
+    BaseComponents::organisms::{modal::Modal, toast::toast_error},
+};
+
+#[derive(Snafu, Debug)]
+pub enum ImportError {
+    #[snafu(display("Failed to access {}", path.display()))]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("{} is not a valid archive", path.display()))]
+    Archive {
+        path: PathBuf,
+        source: zip::result::ZipError,
+    },
+    #[snafu(display("{} has an invalid modrinth.index.json", path.display()))]
+    ModpackIndex {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[snafu(display("{path} points outside of the collection"))]
+    UnsafePath { path: String },
+    #[snafu(display("Failed to download a modpack file"))]
+    Download { source: PlatformApiError },
+    #[snafu(display("{} is not something that can be imported", path.display()))]
+    Unknown { path: PathBuf },
+    #[snafu(display("Importing panicked"))]
+    Join { source: tokio::task::JoinError },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+    Mod,
+    ResourcePack,
+    ShaderPack,
+    /// A zipped world, `level_dat_at_root` is false when the world is wrapped in a folder
+    World {
+        level_dat_at_root: bool,
+    },
+    /// A Modrinth `.mrpack`
+    Modpack,
+}
+
+impl ImportKind {
+    #[must_use]
+    pub fn title(self) -> String {
+        match self {
+            Self::Mod => t!("import-kind-mod"),
+            Self::ResourcePack => t!("import-kind-resource-pack"),
+            Self::ShaderPack => t!("import-kind-shader-pack"),
+            Self::World { .. } => t!("import-kind-world"),
+            Self::Modpack => t!("import-kind-modpack"),
+        }
+    }
+
+    /// The folder of the collection the file ends up in
+    #[must_use]
+    pub fn folder(self, game_directory: &Path) -> PathBuf {
+        match self {
+            Self::Mod => game_directory.join("mods"),
+            Self::ResourcePack => game_directory.join("resourcepacks"),
+            Self::ShaderPack => game_directory.join("shaderpacks"),
+            Self::World { .. } => game_directory.join("saves"),
+            Self::Modpack => game_directory.to_path_buf(),
+        }
+    }
+}
+
+/// The first component of every entry of a zip, deduplicated
+fn top_level(names: &[&str]) -> Vec<PathBuf> {
+    names
+        .iter()
+        .filter_map(|x| x.split('/').find(|x| !x.is_empty()))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Guesses what a file is from its extension and, for zips, its content
+fn detect(source: PathBuf) -> Result<PlannedImport, ImportError> {
+    let path = source.as_path();
+    let extension = path
+        .extension()
+        .map(|x| x.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let file_name = PathBuf::from(path.file_name().unwrap_or_default());
+    let mut planned = PlannedImport {
+        kind: None,
+        error: None,
+        targets: Vec::new(),
+        requires: None,
+        source: source.clone(),
+    };
+    match extension.as_str() {
+        "jar" => {
+            planned.kind = Some(ImportKind::Mod);
+            planned.targets = vec![Path::new("mods").join(file_name)];
+            return Ok(planned);
+        }
+        "mrpack" => {
+            let index = read_index(path)?;
+            let file = File::open(path).context(IoSnafu { path })?;
+            let archive = ZipArchive::new(file).context(ArchiveSnafu { path })?;
+            let overrides = archive
+                .file_names()
+                .filter(|x| !x.ends_with('/'))
+                .filter_map(|x| {
+                    x.strip_prefix("overrides/")
+                        .or_else(|| x.strip_prefix("client-overrides/"))
+                })
+                .map(PathBuf::from);
+            planned.kind = Some(ImportKind::Modpack);
+            planned.requires = Some(ModpackTarget::of(&index.dependencies));
+            planned.targets = index
+                .files
+                .into_iter()
+                .map(|x| PathBuf::from(x.path))
+                .chain(overrides)
+                .collect();
+            return Ok(planned);
+        }
+        "zip" => {}
+        _ => return Ok(planned),
+    }
+    let file = File::open(path).context(IoSnafu { path })?;
+    let archive = ZipArchive::new(file).context(ArchiveSnafu { path })?;
+    let names = archive.file_names().collect::<Vec<_>>();
+    let saves = Path::new("saves");
+    if names.contains(&"level.dat") {
+        planned.kind = Some(ImportKind::World {
+            level_dat_at_root: true,
+        });
+        planned.targets = vec![saves.join(path.file_stem().unwrap_or_default())];
+    } else if names
+        .iter()
+        .any(|x| x.split_once('/').is_some_and(|(_, x)| x == "level.dat"))
+    {
+        planned.kind = Some(ImportKind::World {
+            level_dat_at_root: false,
+        });
+        planned.targets = top_level(&names)
+            .into_iter()
+            .map(|x| saves.join(x))
+            .collect();
+    } else if names.contains(&"pack.mcmeta") {
+        planned.kind = Some(ImportKind::ResourcePack);
+        planned.targets = vec![Path::new("resourcepacks").join(file_name)];
+    } else if names.iter().any(|x| x.starts_with("shaders/")) {
+        planned.kind = Some(ImportKind::ShaderPack);
+        planned.targets = vec![Path::new("shaderpacks").join(file_name)];
+    }
+    Ok(planned)
+}
+
+/// The minecraft version and mod loader a modpack was made for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModpackTarget {
+    pub game_version: Option<String>,
+    /// Lowercased, as in [`game_target`]
+    pub loader: Option<String>,
+}
+
+impl ModpackTarget {
+    fn of(dependencies: &BTreeMap<String, String>) -> Self {
+        let loader = ["fabric-loader", "quilt-loader", "neoforge", "forge"]
+            .into_iter()
+            .find(|x| dependencies.contains_key(*x))
+            .map(|x| x.trim_end_matches("-loader").to_owned());
+        Self {
+            game_version: dependencies.get("minecraft").cloned(),
+            loader,
+        }
+    }
+
+    /// Whether the modpack's mods work in a collection of `game_version` with `loader`
+    #[must_use]
+    pub fn supports(&self, game_version: &str, loader: Option<&str>) -> bool {
+        self.game_version
+            .as_deref()
+            .map_or(true, |x| x == game_version)
+            && self.loader.as_deref().map_or(true, |x| Some(x) == loader)
+    }
+
+    #[must_use]
+    pub fn title(&self) -> String {
+        [self.game_version.as_deref(), self.loader.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// A dropped file, and where it's going to be put
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedImport {
+    pub source: PathBuf,
+    /// `None` when the file isn't recognized or can't be read, it is skipped
+    pub kind: Option<ImportKind>,
+    /// Why the file couldn't be read
+    pub error: Option<String>,
+    /// What the import writes, relative to the game directory
+    pub targets: Vec<PathBuf>,
+    /// What a modpack was made for
+    pub requires: Option<ModpackTarget>,
+}
+
+impl PlannedImport {
+    #[must_use]
+    pub fn file_name(&self) -> String {
+        self.source
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Where the file ends up, relative to the game directory
+    #[must_use]
+    pub fn destination(&self) -> Option<String> {
+        let folder = self.kind?.folder(Path::new(""));
+        let destination = match self.kind? {
+            ImportKind::Modpack => String::from("."),
+            ImportKind::World {
+                level_dat_at_root: true,
+            } => folder
+                .join(self.source.file_stem().unwrap_or_default())
+                .to_string_lossy()
+                .to_string(),
+            _ => folder.to_string_lossy().to_string(),
+        };
+        Some(destination)
+    }
+
+    /// Whether the file is recognized and, for modpacks, made for the collection's version and loader
+    #[must_use]
+    pub fn importable(&self, game_version: &str, loader: Option<&str>) -> bool {
+        self.kind.is_some()
+            && self
+                .requires
+                .as_ref()
+                .map_or(true, |x| x.supports(game_version, loader))
+    }
+
+    /// The files and folders already in `game_directory` the import replaces
+    #[must_use]
+    pub fn overwrites(&self, game_directory: &Path) -> usize {
+        self.targets
+            .iter()
+            .filter(|x| game_directory.join(x).exists())
+            .count()
+    }
+}
+
+/// Detects the kind of every path, files that can't be read are planned to be skipped
+///
+/// # Errors
+///
+/// This function will return an error if detecting panics.
+pub async fn plan(paths: Vec<PathBuf>) -> Result<Vec<PlannedImport>, ImportError> {
+    tokio::task::spawn_blocking(move || {
+        paths
+            .into_iter()
+            .map(|source| {
+                detect(source.clone()).unwrap_or_else(|err| {
+                    warn!("{err}");
+                    PlannedImport {
+                        source,
+                        kind: None,
+                        error: Some(err.to_string()),
+                        targets: Vec::new(),
+                        requires: None,
+                    }
+                })
+            })
+            .collect()
+    })
+    .await
+    .context(JoinSnafu)
+}
+
+/// Joins a path coming from an archive onto `dir`, refusing anything that escapes it
+fn safe_join(dir: &Path, path: &str) -> Result<PathBuf, ImportError> {
+    let relative = Path::new(path);
+    ensure!(
+        relative
+            .components()
+            .all(|x| matches!(x, Component::Normal(_))),
+        UnsafePathSnafu { path }
+    );
+    Ok(dir.join(relative))
+}
+
+/// Extracts every entry of `archive` under `prefix` into `dir`, with the prefix stripped
+pub fn extract(archive: &Path, prefix: &str, dir: &Path) -> Result<(), ImportError> {
+    let file = File::open(archive).context(IoSnafu { path: archive })?;
+    let mut zip = ZipArchive::new(file).context(ArchiveSnafu { path: archive })?;
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).context(ArchiveSnafu { path: archive })?;
+        let Some(name) = entry.enclosed_name() else {
+            continue;
+        };
+        let Ok(relative) = name.strip_prefix(prefix) else {
+            continue;
+        };
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        let path = dir.join(relative);
+        if entry.is_dir() {
+            std::fs::create_dir_all(&path).context(IoSnafu { path: &path })?;
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).context(IoSnafu { path: parent })?;
+        }
+        let mut output = File::create(&path).context(IoSnafu { path: &path })?;
+        std::io::copy(&mut entry, &mut output).context(IoSnafu { path: &path })?;
+    }
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct ModpackIndex {
+    files: Vec<ModpackFile>,
+    /// e.g. `minecraft` or `fabric-loader` -> version
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+}
+
+fn read_index(source: &Path) -> Result<ModpackIndex, ImportError> {
+    let file = File::open(source).context(IoSnafu { path: source })?;
+    let mut zip = ZipArchive::new(file).context(ArchiveSnafu { path: source })?;
+    let entry = zip
+        .by_name("modrinth.index.json")
+        .context(ArchiveSnafu { path: source })?;
+    serde_json::from_reader(entry).context(ModpackIndexSnafu { path: source })
+}
+
+/// The project and version ids of a file hosted on Modrinth's cdn, e.g.
+/// `https://cdn.modrinth.com/data/{project}/versions/{version}/..`
+fn modrinth_version(url: &str) -> Option<(&str, &str)> {
+    let mut parts = url
+        .strip_prefix("https://cdn.modrinth.com/data/")?
+        .split('/');
+    let project = parts.next().filter(|x| !x.is_empty())?;
+    let version = parts
+        .next()
+        .filter(|x| *x == "versions")
+        .and_then(|_| parts.next())
+        .filter(|x| !x.is_empty())?;
+    Some((project, version))
+}
+
+/// A mod a modpack downloaded from Modrinth, to be added to the mod manager
+pub struct ModpackMod {
+    pub path: PathBuf,
+    pub project_id: String,
+    pub version_id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ModpackFile {
+    path: String,
+    downloads: Vec<String>,
+    file_size: u64,
+    #[serde(default)]
+    hashes: ModpackHashes,
+    env: Option<ModpackEnv>,
+}
+
+#[derive(Deserialize, Default)]
+struct ModpackHashes {
+    sha1: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ModpackEnv {
+    client: String,
+}
+
+/// Downloads the files of a `.mrpack` and applies its overrides onto `game_directory`.
+///
+/// Resolves to the mods downloaded from Modrinth.
+async fn import_modpack(
+    collection_id: &CollectionId,
+    source: &Path,
+    game_directory: &Path,
+) -> Result<Vec<ModpackMod>, ImportError> {
+    let index = {
+        let source = source.to_path_buf();
+        tokio::task::spawn_blocking(move || read_index(&source))
+            .await
+            .context(JoinSnafu)??
+    };
+
+    let mods_dir = game_directory.join("mods");
+    let mut mods = Vec::new();
+    for file in index.files {
+        if file.env.is_some_and(|x| x.client == "unsupported") {
+            continue;
+        }
+        let path = safe_join(game_directory, &file.path)?;
+        let (Some(dir), Some(filename)) = (path.parent(), path.file_name()) else {
+            continue;
+        };
+        let version = file
+            .downloads
+            .first()
+            .and_then(|x| modrinth_version(x))
+            .map(|(project, version)| (project.to_owned(), version.to_owned()));
+        let remote = RemoteFile {
+            filename: filename.to_string_lossy().to_string(),
+            url: file.downloads.into_iter().next(),
+            size: file.file_size,
+            sha1: file.hashes.sha1,
+            primary: true,
+        };
+        // the mods are added to the manager along with the rest of the pack
+        let path = platform_api::download_file(
+            Some(collection_id.clone()),
+            &remote,
+            dir,
+            FollowUp::Caller,
+        )
+        .await
+        .context(DownloadSnafu)?;
+        if let Some((project_id, version_id)) = version.filter(|_| dir == mods_dir) {
+            mods.push(ModpackMod {
+                path,
+                project_id,
+                version_id,
+            });
+        }
+    }
+
+    let source = source.to_path_buf();
+    let game_directory = game_directory.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        extract(&source, "overrides", &game_directory)?;
+        extract(&source, "client-overrides", &game_directory)
+    })
+    .await
+    .context(JoinSnafu)??;
+    Ok(mods)
+}
+
+/// Puts a planned file into the collection `collection_id` at `game_directory`.
+///
+/// Resolves to the mods a modpack downloaded from Modrinth, so they can be added to the mod manager.
+///
+/// # Errors
+///
+/// This function will return an error if the file is unknown, or copying, extracting or downloading fails.
+pub async fn import_file(
+    collection_id: &CollectionId,
+    planned: &PlannedImport,
+    game_directory: &Path,
+) -> Result<Vec<ModpackMod>, ImportError> {
+    let source = &planned.source;
+    let kind = planned.kind.context(UnknownSnafu { path: source })?;
+    let folder = kind.folder(game_directory);
+    info!("Importing {} into {}", source.display(), folder.display());
+    match kind {
+        ImportKind::Mod | ImportKind::ResourcePack | ImportKind::ShaderPack => {
+            tokio::fs::create_dir_all(&folder)
+                .await
+                .context(IoSnafu { path: &folder })?;
+            let path = folder.join(source.file_name().unwrap_or_default());
+            tokio::fs::copy(source, &path)
+                .await
+                .context(IoSnafu { path })?;
+            Ok(Vec::new())
+        }
+        ImportKind::World { level_dat_at_root } => {
+            let source = source.clone();
+            let dir = if level_dat_at_root {
+                folder.join(source.file_stem().unwrap_or_default())
+            } else {
+                folder
+            };
+            tokio::task::spawn_blocking(move || extract(&source, "", &dir))
+                .await
+                .context(JoinSnafu)??;
+            Ok(Vec::new())
+        }
+        ImportKind::Modpack => import_modpack(collection_id, source, game_directory).await,
+    }
+}
+
+/// Files dropped onto a collection, waiting for confirmation
+#[derive(Clone, Copy, PartialEq)]
+pub struct ImportDrop {
+    pub pending: Signal<Vec<PlannedImport>>,
+    pub active: Signal<bool>,
+}
+
+impl ImportDrop {
+    /// Plans the import of the dropped files and opens the confirmation
+    pub fn ondrop(mut self, event: &DragEvent) {
+        event.prevent_default();
+        let Some(files) = event.files() else {
+            return;
+        };
+        let paths = files.files().into_iter().map(PathBuf::from).collect();
+        spawn(async move {
+            match plan(paths).await {
+                Ok(planned) if !planned.is_empty() => {
+                    self.pending.set(planned);
+                    self.active.set(true);
+                }
+                Ok(_) => {}
+                Err(err) => toast_error(err),
+            }
+        });
+    }
+}
+
+/// Makes an element accept dropped files, render [`ImportConfirmation`] with the returned value alongside it.
+///
+/// ```ignore
+/// let import = use_import_drop();
+/// rsx! {
+///     div {
+///         ondragover: move |e| e.prevent_default(),
+///         ondrop: move |e| import.ondrop(&e),
+///     }
+///     ImportConfirmation { collection_id, import }
+/// }
+/// ```
+pub fn use_import_drop() -> ImportDrop {
+    ImportDrop {
+        pending: use_signal(Vec::new),
+        active: use_signal(|| false),
+    }
+}
+
+/// Makes the mod manager entries of the mods a modpack downloaded, see [`add_to_manager`]
+async fn add_modpack_mods(collection: &Collection, mods: Vec<ModpackMod>) -> Vec<ModMetadata> {
+    let mut entries = Vec::new();
+    for x in mods {
+        match add_to_manager(
+            collection,
+            &x.path,
+            ModSource::Modrinth,
+            &x.project_id,
+            &x.version_id,
+        )
+        .await
+        {
+            Ok(entry) => entries.push(entry),
+            Err(err) => toast_error(err),
+        }
+    }
+    entries
+}
+
+#[component]
+pub fn ImportConfirmation(
+    collection_id: ReadOnlySignal<CollectionId>,
+    import: ImportDrop,
+) -> Element {
+    let mut radio = collection_id().use_collection_radio();
+    let mut importing = use_signal(|| false);
+    let ImportDrop {
+        pending,
+        mut active,
+        ..
+    } = import;
+    let game_directory = use_memo(move || radio.read().game_directory().to_path_buf());
+    let target = use_memo(move || game_target(&radio.read()));
+
+    rsx! {
+        Modal {
+            active,
+            div {
+                class: "w-full h-full flex items-center justify-center",
+                div {
+                    class: "w-[700px] flex flex-col gap-[20px] p-[30px] rounded-[30px] bg-background",
+                    div {
+                        class: "text-white text-[28px] font-bold trim",
+                        {t!("import-title", name = radio.read().display_name().clone())}
+                    }
+                    div {
+                        class: "flex flex-col gap-[5px]",
+                        for planned in pending() {
+                            div {
+                                class: "flex items-center gap-[15px] px-[20px] py-[15px] rounded-[15px] bg-deep-background aria-disabled:opacity-50",
+                                aria_disabled: !planned.importable(&target().0, target().1.as_deref()),
+                                div {
+                                    class: "grow min-w-0 flex flex-col gap-[10px]",
+                                    div {
+                                        class: "text-white text-[18px] font-english font-bold truncate trim",
+                                        {planned.file_name()}
+                                    }
+                                    div {
+                                        class: "text-secondary-text text-[15px] font-english truncate trim",
+                                        {planned.destination().unwrap_or_else(|| t!("import-skipped"))}
+                                    }
+                                    if let Some(err) = &planned.error {
+                                        div {
+                                            class: "text-red text-[15px] truncate trim",
+                                            {err.clone()}
+                                        }
+                                    }
+                                    if let Some(requires) = planned.requires.as_ref().filter(|x| !x.supports(&target().0, target().1.as_deref())) {
+                                        div {
+                                            class: "text-red text-[15px] trim",
+                                            {t!("import-incompatible", target = requires.title())}
+                                        }
+                                    }
+                                    if planned.kind.is_some() && planned.overwrites(&game_directory()) > 0 {
+                                        div {
+                                            class: "text-orange text-[15px] trim",
+                                            {t!("import-overwrites", count = planned.overwrites(&game_directory()))}
+                                        }
+                                    }
+                                }
+                                div {
+                                    class: "flex-none px-[10px] py-[5px] rounded-full bg-secondary-surface text-hint text-[13px] font-medium trim",
+                                    {planned.kind.map_or_else(|| t!("import-kind-unknown"), ImportKind::title)}
+                                }
+                            }
+                        }
+                    }
+                    div {
+                        class: "flex justify-end gap-[10px]",
+                        button {
+                            class: "h-[50px] px-[20px] rounded-[15px] bg-deep-background text-white text-[16px] font-medium trim",
+                            onclick: move |_| active.set(false),
+                            {t!("import-cancel")}
+                        }
+                        button {
+                            class: "h-[50px] px-[20px] rounded-[15px] bg-white text-black text-[16px] font-medium aria-busy:opacity-50 trim",
+                            aria_busy: importing(),
+                            disabled: importing(),
+                            onclick: move |_| {
+                                importing.set(true);
+                                spawn(async move {
+                                    let game_directory = game_directory();
+                                    let (game_version, loader) = target();
+                                    let collection_id = collection_id();
+                                    let mut mods = Vec::new();
+                                    for planned in pending().iter().filter(|x| x.importable(&game_version, loader.as_deref())) {
+                                        match import_file(&collection_id, planned, &game_directory).await {
+                                            Ok(x) => mods.extend(x),
+                                            Err(err) => {
+                                                toast_error(err);
+                                                break;
+                                            }
+                                        }
+                                    }
+                                    if !mods.is_empty() {
+                                        let collection = Collection::clone(&radio.read());
+                                        let entries = add_modpack_mods(&collection, mods).await;
+                                        if let Err(err) = radio.with_mut(|x| push_to_manager(x, entries)) {
+                                            toast_error(err);
+                                        }
+                                    }
+                                    importing.set(false);
+                                    active.set(false);
+                                });
+                            },
+                            {t!("import-confirm")}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
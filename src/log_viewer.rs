@@ -0,0 +1,170 @@
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use strum::IntoEnumIterator;
+
+use crate::{
+    logging::{self, LogLevel, LogRecord, LOG_SETTINGS},
+    t, use_mounted,
+};
+
+/// Only the latest matching records are rendered
+const MAX_SHOWN: usize = 1000;
+
+#[component]
+pub fn LogViewer() -> Element {
+    let mut records = use_signal(Vec::<LogRecord>::new);
+    let mut shown_level = use_signal(|| LogLevel::Trace);
+    let mut module = use_signal(String::new);
+    let mut follow = use_signal(|| true);
+    let mut bottom = use_mounted();
+
+    use_future(move || async move {
+        let mut seen = None;
+        loop {
+            if seen != Some(logging::generation()) {
+                let (generation, latest) = logging::records();
+                seen = Some(generation);
+                records.set(latest);
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    });
+
+    let filtered = use_memo(move || {
+        let module = module.read().to_lowercase();
+        let level = shown_level();
+        let records = records.read();
+        let mut filtered = records
+            .iter()
+            .rev()
+            .filter(|x| x.level <= level && x.target.to_lowercase().contains(&module))
+            .take(MAX_SHOWN)
+            .cloned()
+            .collect::<Vec<_>>();
+        filtered.reverse();
+        filtered
+    });
+
+    use_effect(move || {
+        filtered.read();
+        if follow() {
+            if let Some(bottom) = bottom.peek().clone() {
+                spawn(async move {
+                    let _ = bottom.scroll_to(ScrollBehavior::Instant).await;
+                });
+            }
+        }
+    });
+
+    let level = LOG_SETTINGS.read().level;
+    rsx! {
+        div {
+            class: "flex flex-col gap-[20px] p-[30px] min-h-screen",
+            div {
+                class: "text-white text-[40px] font-black trim",
+                {t!("logs-title")}
+            }
+            div {
+                class: "flex flex-wrap items-center gap-[10px]",
+                div {
+                    class: "text-hint text-[16px] font-medium trim",
+                    {t!("logs-record-level")}
+                }
+                for x in LogLevel::iter() {
+                    LevelChip {
+                        level: x,
+                        selected: x == level,
+                        onclick: move |()| LOG_SETTINGS.write().level = x,
+                    }
+                }
+            }
+            div {
+                class: "flex flex-wrap items-center gap-[10px]",
+                div {
+                    class: "text-hint text-[16px] font-medium trim",
+                    {t!("logs-show-level")}
+                }
+                for x in LogLevel::iter() {
+                    LevelChip {
+                        level: x,
+                        selected: x == shown_level(),
+                        onclick: move |()| shown_level.set(x),
+                    }
+                }
+                input {
+                    class: "grow h-[40px] px-[15px] rounded-[15px] bg-background text-white text-[14px]",
+                    placeholder: t!("logs-module-filter"),
+                    value: module(),
+                    oninput: move |x| module.set(x.value()),
+                }
+                button {
+                    class: "h-[40px] px-[15px] rounded-[15px] bg-background text-white text-[14px] font-medium aria-selected:bg-white aria-selected:text-black trim",
+                    aria_selected: follow(),
+                    onclick: move |_| follow.toggle(),
+                    {t!("logs-follow")}
+                }
+            }
+            div {
+                class: "flex flex-col gap-[2px] p-[20px] rounded-[20px] bg-background font-mono text-[13px]",
+                if filtered.read().is_empty() {
+                    div {
+                        class: "text-hint",
+                        {t!("logs-empty")}
+                    }
+                }
+                for record in filtered() {
+                    LogLine {
+                        record,
+                    }
+                }
+                div {
+                    onmounted: move |e| bottom.set(Some(e.data())),
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn LevelChip(level: LogLevel, selected: bool, onclick: EventHandler) -> Element {
+    rsx! {
+        button {
+            class: "h-[40px] px-[15px] rounded-[15px] bg-background text-white text-[14px] font-medium aria-selected:bg-white aria-selected:text-black trim",
+            aria_selected: selected,
+            onclick: move |_| onclick.call(()),
+            {level.title()}
+        }
+    }
+}
+
+#[component]
+fn LogLine(record: LogRecord) -> Element {
+    let class = match record.level {
+        LogLevel::Error => "text-red",
+        LogLevel::Warn => "text-orange",
+        LogLevel::Info => "text-white",
+        LogLevel::Debug | LogLevel::Trace => "text-hint",
+    };
+    rsx! {
+        div {
+            class: "flex gap-[10px] whitespace-pre-wrap break-all",
+            div {
+                class: "shrink-0 text-hint",
+                {record.time.format("%H:%M:%S%.3f").to_string()}
+            }
+            div {
+                class: "shrink-0 w-[50px] {class}",
+                {record.level.title()}
+            }
+            div {
+                class: "shrink-0 text-hint",
+                {record.target}
+            }
+            div {
+                class: "text-white",
+                {record.message}
+            }
+        }
+    }
+}
@@ -0,0 +1,412 @@
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        LazyLock, Mutex, OnceLock, PoisonError,
+    },
+};
+
+use chrono::{DateTime, Local, NaiveDate};
+use dioxus::prelude::*;
+use dioxus_logger::tracing::{
+    field::{Field, Visit},
+    level_filters::LevelFilter,
+    warn, Event, Level, Subscriber,
+};
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+use tracing_subscriber::{fmt::MakeWriter, layer::Context, prelude::*, reload, Layer, Registry};
+
+use crate::{
+    persistence::{data_dir, log_dir, Persisted},
+    BaseComponents::organisms::toast::toast_error,
+};
+
+pub static LOG_SETTINGS: GlobalSignal<LogSettings> =
+    Signal::global(|| LogSettings::load_or_create(&data_dir()));
+
+/// A log file is rotated once it grows past this, besides the daily rotation
+const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+/// Older log files are removed once there are more than this
+const MAX_FILES: usize = 20;
+/// Records kept in memory for the log viewer
+const MAX_RECORDS: usize = 5000;
+
+static RECORDS: LazyLock<Mutex<VecDeque<LogRecord>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::with_capacity(MAX_RECORDS)));
+/// Bumped on every record, so the viewer only copies [`RECORDS`] when something changed
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+static LEVEL_HANDLE: OnceLock<reload::Handle<LevelFilter, Registry>> = OnceLock::new();
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, EnumIter,
+)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    const fn filter(self) -> LevelFilter {
+        match self {
+            Self::Error => LevelFilter::ERROR,
+            Self::Warn => LevelFilter::WARN,
+            Self::Info => LevelFilter::INFO,
+            Self::Debug => LevelFilter::DEBUG,
+            Self::Trace => LevelFilter::TRACE,
+        }
+    }
+
+    #[must_use]
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+        }
+    }
+}
+
+impl From<Level> for LogLevel {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::ERROR => Self::Error,
+            Level::WARN => Self::Warn,
+            Level::INFO => Self::Info,
+            Level::DEBUG => Self::Debug,
+            Level::TRACE => Self::Trace,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogSettings {
+    /// Events below this level are neither written nor shown
+    pub level: LogLevel,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            level: LogLevel::Info,
+        }
+    }
+}
+
+impl Persisted for LogSettings {
+    const FILE_NAME: &'static str = "logging.json";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    pub time: DateTime<Local>,
+    pub level: LogLevel,
+    /// Module path the event came from
+    pub target: String,
+    pub message: String,
+}
+
+/// Sends events to stdout, the rotating files in [`log_dir`] and the log viewer.
+///
+/// # Panics
+///
+/// Panics if a global subscriber was already set.
+pub fn init() {
+    let level = LogSettings::load_from(&data_dir()).level;
+    let (filter, handle) = reload::Layer::new(level.filter());
+    let _ = LEVEL_HANDLE.set(handle);
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(RotatingFile::new(log_dir())),
+        )
+        .with(RecordLayer)
+        .init();
+}
+
+/// Changes the level of the running subscriber, it's persisted separately through [`LOG_SETTINGS`]
+pub fn set_level(level: LogLevel) {
+    if let Some(handle) = LEVEL_HANDLE.get() {
+        if let Err(err) = handle.modify(|x| *x = level.filter()) {
+            warn!("Failed to change the log level: {err}");
+        }
+    }
+}
+
+/// Applies the level of [`LOG_SETTINGS`] and saves them whenever they change, wherever they were changed from
+pub fn use_log_settings_sync() {
+    use_effect(move || {
+        let settings = LOG_SETTINGS.read();
+        set_level(settings.level);
+        if let Err(err) = settings.save_to(&data_dir()) {
+            toast_error(err);
+        }
+    });
+}
+
+/// Records kept for the viewer, with a generation that changes whenever they do
+#[must_use]
+pub fn records() -> (u64, Vec<LogRecord>) {
+    let records = RECORDS.lock().unwrap_or_else(PoisonError::into_inner);
+    (
+        GENERATION.load(Ordering::Relaxed),
+        records.iter().cloned().collect(),
+    )
+}
+
+#[must_use]
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::Relaxed)
+}
+
+/// Keeps the latest events in memory for the log viewer
+struct RecordLayer;
+
+impl<S: Subscriber> Layer<S> for RecordLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let record = LogRecord {
+            time: Local::now(),
+            level: (*metadata.level()).into(),
+            target: metadata.target().to_owned(),
+            message: visitor.message,
+        };
+        let mut records = RECORDS.lock().unwrap_or_else(PoisonError::into_inner);
+        if records.len() == MAX_RECORDS {
+            records.pop_front();
+        }
+        records.push_back(record);
+        GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Formats the message of an event followed by its other fields as `name=value`
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message.insert_str(0, &format!("{value:?}"));
+        } else {
+            self.message
+                .push_str(&format!(" {}={value:?}", field.name()));
+        }
+    }
+}
+
+/// Log files in `dir` named by day, with a new file once the day changes or the current one is full
+struct RotatingFile {
+    dir: PathBuf,
+    max_size: u64,
+    current: Mutex<Option<CurrentFile>>,
+}
+
+struct CurrentFile {
+    date: NaiveDate,
+    index: u32,
+    size: u64,
+    file: File,
+}
+
+impl RotatingFile {
+    const fn new(dir: PathBuf) -> Self {
+        Self::with_max_size(dir, MAX_FILE_SIZE)
+    }
+
+    const fn with_max_size(dir: PathBuf, max_size: u64) -> Self {
+        Self {
+            dir,
+            max_size,
+            current: Mutex::new(None),
+        }
+    }
+
+    fn path(&self, date: NaiveDate, index: u32) -> PathBuf {
+        self.dir.join(format!("prag-portal-{date}.{index:03}.log"))
+    }
+
+    /// Opens the file to write to on `date`, continuing the last one of that day unless it's full
+    fn open(&self, date: NaiveDate, mut index: u32) -> std::io::Result<CurrentFile> {
+        std::fs::create_dir_all(&self.dir)?;
+        loop {
+            let path = self.path(date, index);
+            let size = std::fs::metadata(&path).map_or(0, |x| x.len());
+            let next_exists = self.path(date, index + 1).exists();
+            if size < self.max_size && !next_exists {
+                let file = OpenOptions::new().create(true).append(true).open(&path)?;
+                prune(&self.dir, MAX_FILES);
+                return Ok(CurrentFile {
+                    date,
+                    index,
+                    size,
+                    file,
+                });
+            }
+            index += 1;
+        }
+    }
+}
+
+/// Removes the oldest log files until `keep` are left, the names sort by date then index
+fn prune(dir: &Path, keep: usize) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut files = entries
+        .flatten()
+        .map(|x| x.path())
+        .filter(|x| {
+            x.file_name()
+                .is_some_and(|x| x.to_string_lossy().starts_with("prag-portal-"))
+                && x.extension().is_some_and(|x| x == "log")
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    let excess = files.len().saturating_sub(keep);
+    for file in &files[..excess] {
+        // logging here would write back into the file being rotated
+        let _ = std::fs::remove_file(file);
+    }
+}
+
+impl Write for &RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut current = self.current.lock().unwrap_or_else(PoisonError::into_inner);
+        let today = Local::now().date_naive();
+        let rotate = match &*current {
+            None => Some(0),
+            Some(x) if x.date != today => Some(0),
+            Some(x) if x.size + buf.len() as u64 > self.max_size => Some(x.index + 1),
+            Some(_) => None,
+        };
+        if let Some(index) = rotate {
+            *current = Some(self.open(today, index)?);
+        }
+        let Some(current) = current.as_mut() else {
+            return Ok(0);
+        };
+        current.file.write_all(buf)?;
+        current.size += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        let mut current = self.current.lock().unwrap_or_else(PoisonError::into_inner);
+        current.as_mut().map_or(Ok(()), |x| x.file.flush())
+    }
+}
+
+impl<'a> MakeWriter<'a> for RotatingFile {
+    type Writer = &'a Self;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_log_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("prag-portal-logging-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn write(file: &RotatingFile, text: &str) {
+        let mut writer = file;
+        writer.write_all(text.as_bytes()).expect("failed to write");
+        writer.flush().expect("failed to flush");
+    }
+
+    fn read(path: &Path) -> String {
+        std::fs::read_to_string(path).expect("failed to read the log file")
+    }
+
+    #[test]
+    fn appends_to_the_file_of_the_day() {
+        let dir = temp_log_dir("append");
+        let file = RotatingFile::with_max_size(dir.clone(), 64);
+        write(&file, "first\n");
+        write(&file, "second\n");
+        let path = file.path(Local::now().date_naive(), 0);
+        assert_eq!(read(&path), "first\nsecond\n");
+
+        // a new writer continues the same file rather than starting over
+        let file = RotatingFile::with_max_size(dir.clone(), 64);
+        write(&file, "third\n");
+        assert_eq!(read(&path), "first\nsecond\nthird\n");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rotates_once_the_file_is_full() {
+        let dir = temp_log_dir("rotate");
+        let file = RotatingFile::with_max_size(dir.clone(), 10);
+        let today = Local::now().date_naive();
+        write(&file, "12345678\n");
+        write(&file, "abcdefgh\n");
+        assert_eq!(read(&file.path(today, 0)), "12345678\n");
+        assert_eq!(read(&file.path(today, 1)), "abcdefgh\n");
+
+        // a full file isn't reopened, the writer moves on to the next index
+        let file = RotatingFile::with_max_size(dir.clone(), 10);
+        write(&file, "xyz\n");
+        assert_eq!(read(&file.path(today, 1)), "abcdefgh\nxyz\n");
+        assert!(!file.path(today, 2).exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn prune_keeps_the_newest_files() {
+        let dir = temp_log_dir("prune");
+        std::fs::create_dir_all(&dir).expect("failed to create the log dir");
+        let names = [
+            "prag-portal-2024-01-01.000.log",
+            "prag-portal-2024-01-01.001.log",
+            "prag-portal-2024-01-02.000.log",
+            "prag-portal-2024-01-03.000.log",
+            "other.log",
+            "prag-portal-notes.txt",
+        ];
+        for name in names {
+            std::fs::write(dir.join(name), name).expect("failed to write");
+        }
+        prune(&dir, 2);
+        let mut left = std::fs::read_dir(&dir)
+            .expect("failed to read the log dir")
+            .flatten()
+            .map(|x| x.file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        left.sort();
+        assert_eq!(
+            left,
+            [
+                "other.log",
+                "prag-portal-2024-01-02.000.log",
+                "prag-portal-2024-01-03.000.log",
+                "prag-portal-notes.txt",
+            ]
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,22 +1,40 @@
 #![allow(non_snake_case)]
 pub mod BaseComponents;
+pub mod app_error;
 pub mod builder;
 pub mod collection_display;
 pub mod collection_edit;
 pub mod collections;
+pub mod cover_palette;
+pub mod description;
+pub mod diagnostics;
+pub mod download_history;
 pub mod download_progress;
+pub mod download_queue;
+pub mod i18n;
+pub mod import;
+pub mod log_viewer;
+pub mod logging;
 pub mod main_page;
+pub mod notifications;
 pub mod pages;
+pub mod persistence;
+pub mod platform_api;
 pub mod scrollable;
+pub mod settings;
 pub mod side_bar;
 pub mod svgs;
+#[cfg(test)]
+mod test_support;
 pub mod text_scroller;
+pub mod theme;
+pub mod translation;
+pub mod watcher;
 
 use collection_edit::CollectionEditContainer;
 use dioxus::desktop::tao::dpi::PhysicalSize;
 use dioxus::desktop::WindowBuilder;
 use dioxus::html::input_data::MouseButton;
-use dioxus_logger::tracing::Level;
 use dioxus_radio::hooks::use_init_radio_station;
 use pages::Pages;
 use rand::seq::IteratorRandom;
@@ -32,16 +50,22 @@ use svgs::{CREATE_COLLECTION, CURSEFORGE_OUTLINE, GRASS, MODRINTH_OUTLINE};
 use tailwind_fuse::*;
 use BaseComponents::{
     atoms::switch::{FloatingSwitch, State},
-    organisms::modal::Modal,
+    organisms::{modal::Modal, toast::ToastHost},
 };
 
 use dioxus::{prelude::*, CapturedError};
 
+use crate::app_error::{AppError, AppErrorView, ScopedErrorBoundary};
 use crate::collection_display::CollectionDisplay;
 use crate::collections::Collections;
 use crate::download_progress::DownloadProgress;
+use crate::log_viewer::LogViewer;
 use crate::main_page::MainPage;
+use crate::notifications::Notifiers;
+use crate::settings::Settings;
 use crate::side_bar::SideBar;
+use crate::theme::ThemeStyle;
+use crate::watcher::FolderWatchers;
 
 const FIRST: Asset = asset!("/assets/first_collection_pic.png");
 const SECOND: Asset = asset!("/assets/second_collection_pic.png");
@@ -173,7 +197,7 @@ use rust_lib::api::shared_resources::collection::{
 };
 
 fn main() {
-    dioxus_logger::init(Level::INFO).expect("failed to init logger");
+    logging::init();
 
     let cfg = dioxus::desktop::Config::new().with_window(
         WindowBuilder::new()
@@ -223,6 +247,8 @@ fn TailwindSetup() -> Element {
 fn App() -> Element {
     let error_active = use_signal(|| true);
     let handle_error = move |error: ErrorContext| {
+        let errors = error.errors().to_vec();
+        let retry = EventHandler::new(move |()| error.clear_errors());
         rsx! {
             Modal {
                 active: error_active,
@@ -236,11 +262,13 @@ fn App() -> Element {
                         flex_basis: "80%",
                         div {
                             class: "text-red text-3xl font-black",
-                            "Hmm, something went wrong. Please copy the following error to the developer."
+                            {t!("error-title")}
                         }
-                        pre {
-                            class: "max-w-full overflow-x-scroll font-[13px] font-bold",
-                            "{error:#?}"
+                        for captured in errors {
+                            AppErrorView {
+                                error: captured,
+                                retry,
+                            }
                         }
                     }
                     div {
@@ -255,6 +283,7 @@ fn App() -> Element {
 
 
         }
+        ThemeStyle {}
         div {
             class: "[&_*]:transform-gpu bg-deep-background h-screen w-screen font-display leading-normal",
             ErrorBoundary {
@@ -264,6 +293,7 @@ fn App() -> Element {
 
                 }
             }
+            ToastHost {}
         }
     }
 }
@@ -274,10 +304,7 @@ pub trait IntoRenderError {
 
 impl IntoRenderError for anyhow::Error {
     fn into_render_error(self) -> RenderError {
-        use std::error::Error;
-        let boxed_error: Box<dyn Error + Send + Sync> = Box::from(self);
-        let leaked_error: &'static (dyn Error + Send + Sync) = Box::leak(boxed_error);
-        RenderError::Aborted(CapturedError::from(leaked_error))
+        RenderError::Aborted(CapturedError::from(AppError::from(self)))
     }
 }
 
@@ -300,17 +327,23 @@ impl<P, K: Into<anyhow::Error> + Send + Sync + 'static, T: FnMut() -> Result<P,
 {
     fn throw(&mut self) {
         if let Err(x) = self() {
-            use std::error::Error;
-            let boxed_error: Box<dyn Error + Send + Sync> = Box::from(x.into());
-            let leaked_error: &'static (dyn Error + Send + Sync) = Box::leak(boxed_error);
-            ScopeId::APP.throw_error(leaked_error);
+            throw_error(AppError::from(x.into()));
         }
     }
 }
 
+/// Errors set on the signal are thrown from the calling component, so its nearest boundary catches them
 #[must_use]
 pub fn use_error_handler() -> SyncSignal<Result<(), anyhow::Error>> {
-    use_context()
+    let mut error_handler = use_signal_sync(|| Ok(()));
+    use_effect(move || {
+        if error_handler.read().is_err() {
+            if let Err(err) = std::mem::replace(&mut *error_handler.write(), Ok(())) {
+                throw_error(AppError::from(err));
+            }
+        }
+    });
+    error_handler
 }
 
 #[must_use]
@@ -398,14 +431,11 @@ impl<T: std::fmt::Debug + ErrorCompat + 'static + snafu::Error> ErrorFormatted f
 
 #[component]
 fn Layout() -> Element {
-    let error_handler: SyncSignal<Result<(), anyhow::Error>> =
-        use_context_provider(|| Signal::new_maybe_sync(Ok(())));
-
     use_init_radio_station::<collection::Collections, CollectionRadioChannel>(move || {
         let collections = match Collection::scan() {
             Ok(v) => v,
             Err(err) => {
-                throw_error(err);
+                throw_error(AppError::from(err));
                 return collection::Collections::default();
             }
         };
@@ -416,13 +446,8 @@ fn Layout() -> Element {
             .collect();
         collection::Collections(collections)
     });
-
-    use_memo(move || {
-        if let Err(x) = error_handler.read().as_ref() {
-            return Err(x.to_render_error());
-        }
-        Ok(())
-    })()?;
+    settings::use_settings_sync();
+    logging::use_log_settings_sync();
 
     let pages_scroller = vec![Pages::MainPage, Pages::Explore, Pages::Collections];
 
@@ -449,6 +474,12 @@ fn Layout() -> Element {
                     }
                 }
             },
+            FolderWatchers {
+
+            }
+            Notifiers {
+
+            }
             SideBar {
 
             }
@@ -475,8 +506,11 @@ fn Layout() -> Element {
                 div {
                     class: "absolute inset-0 z-0 min-h-full",
                     transform: "translateY({transforms[&Pages::Collections]})",
-                    Collections {
+                    ScopedErrorBoundary {
+                        class: "m-[30px]",
+                        Collections {
 
+                        }
                     }
                 }
                 div {
@@ -488,6 +522,24 @@ fn Layout() -> Element {
                         }
                     }
                 }
+                div {
+                    class: "absolute inset-0 z-0 min-h-full min-w-full",
+                    ..Pages::Logs.flyer_attributes(HISTORY.read()),
+                    LayoutContainer {
+                        LogViewer {
+
+                        }
+                    }
+                }
+                div {
+                    class: "absolute inset-0 z-0 min-h-full min-w-full",
+                    ..Pages::Settings.flyer_attributes(HISTORY.read()),
+                    LayoutContainer {
+                        Settings {
+
+                        }
+                    }
+                }
                 CollectionContainer {
 
                 }
@@ -525,8 +577,12 @@ fn SingleCollectionContainer(id: CollectionId) -> Element {
     rsx! {
         div {
             class: "bg-deep-background min-h-screen rounded-xl min-w-full",
-            CollectionDisplay {
+            ScopedErrorBoundary {
+                class: "m-[30px]",
                 collection_id: id,
+                CollectionDisplay {
+                    collection_id: id,
+                }
             }
         }
     }
@@ -540,7 +596,10 @@ fn LayoutContainer(children: Element, #[props(default)] extended_class: String)
             class: tw_merge!("bg-deep-background min-h-screen min-w-full", extended_class),
             div {
                 class: "flex flex-col transition-all xl:items-center xl:*:justify-center xl:*:max-w-[1240px] xl:*:w-full",
-                {children}
+                ScopedErrorBoundary {
+                    class: "m-[30px]",
+                    {children}
+                }
             }
         }
     }
@@ -12,13 +12,17 @@ use rust_lib::api::{
 use tailwind_fuse::*;
 
 use crate::{
-    collection_display::HORIZ,
+    collection_display::{worlds::use_world_backup, HORIZ},
     collections::CollectionContext,
+    cover_palette::{accent_channels, use_collection_accent},
+    download_queue,
+    import::{use_import_drop, ImportConfirmation},
+    t,
     text_scroller::use_text_scroller,
-    use_error_handler,
     BaseComponents::{
         atoms::button::{Button, ButtonClass, FillMode, Roundness, Size},
         molecules::switcher::StateSwitcher,
+        organisms::toast::toast_error,
         string_placements::{Alignment, ContentType, Contents},
     },
     Pages, ARROW_RIGHT,
@@ -40,7 +44,7 @@ pub static START: Component = |()| {
 
 pub static INVERTED_STAR: Component = |()| {
     rsx! {
-         svg { height: "31", "viewBox": "0 0 31 31", xmlns: "http://www.w3.org/2000/svg", width: "31", fill: "none", mask { width: "31", style: "mask-type:alpha", x: "0", "maskUnits": "userSpaceOnUse", y: "0", height: "31", id: "mask0_3548_4919", rect { x: "0.5", width: "30", y: "0.5", fill: "#D9D9D9", height: "30", } } g { mask: "url(#mask0_3548_4919)", path { fill: "#191919", d: "M15.504 18.2343L17.5634 19.5047C17.8113 19.6392 18.0453 19.6237 18.2653 19.4581C18.4851 19.2927 18.5588 19.0779 18.4865 18.8137L17.9375 16.4703L19.8075 14.8681C20.0173 14.677 20.08 14.4543 19.9956 14.2C19.9112 13.9454 19.7412 13.806 19.4856 13.7818L17.0503 13.5759L16.0956 11.34C15.9818 11.0731 15.787 10.9397 15.5112 10.9397C15.2354 10.9397 15.0404 11.0731 14.9262 11.34L13.9659 13.584L11.5168 13.7906C11.2595 13.8148 11.0887 13.953 11.0043 14.2053C10.92 14.4576 10.9827 14.6785 11.1925 14.8681L13.0625 16.4703L12.5134 18.8137C12.4411 19.0779 12.5131 19.2927 12.7293 19.4581C12.9458 19.6237 13.1843 19.6392 13.445 19.5047L15.504 18.2343ZM11.8375 24.4415H9.00402C8.32507 24.4415 7.74767 24.2037 7.27184 23.7281C6.79621 23.2523 6.5584 22.6749 6.5584 21.9959V19.1625L4.60402 17.2234C4.33986 16.9749 4.156 16.7051 4.05246 16.414C3.94913 16.1232 3.89746 15.8185 3.89746 15.5C3.89746 15.1814 3.94913 14.8767 4.05246 14.5859C4.156 14.2949 4.33986 14.0251 4.60402 13.7765L6.5584 11.8375V9.00402C6.5584 8.32507 6.79621 7.74767 7.27184 7.27184C7.74767 6.79621 8.32507 6.5584 9.00402 6.5584H11.8375L13.7765 4.60402C14.0301 4.33986 14.3011 4.156 14.5896 4.05246C14.878 3.94913 15.1814 3.89746 15.5 3.89746C15.8185 3.89746 16.1219 3.94913 16.4103 4.05246C16.6988 4.156 16.9699 4.33986 17.2234 4.60402L19.1625 6.5584H21.9959C22.6749 6.5584 23.2523 6.79621 23.7281 7.27184C24.2037 7.74767 24.4415 8.32507 24.4415 9.00402V11.8375L26.3959 13.7765C26.6601 14.0301 26.8439 14.3011 26.9475 14.5896C27.0508 14.878 27.1025 15.1814 27.1025 15.5C27.1025 15.8185 27.0508 16.1219 26.9475 16.4103C26.8439 16.6988 26.6601 16.9699 26.3959 17.2234L24.4415 19.1625V21.9959C24.4415 22.6749 24.2037 23.2523 23.7281 23.7281C23.2523 24.2037 22.6749 24.4415 21.9959 24.4415H19.1625L17.2234 26.3959C16.9749 26.6601 16.7051 26.8439 16.414 26.9475C16.1232 27.0508 15.8185 27.1025 15.5 27.1025C15.1814 27.1025 14.8767 27.0508 14.5859 26.9475C14.2949 26.8439 14.0251 26.6601 13.7765 26.3959L11.8375 24.4415Z", } } }
+         svg { height: "31", "viewBox": "0 0 31 31", xmlns: "http://www.w3.org/2000/svg", width: "31", fill: "none", mask { width: "31", style: "mask-type:alpha", x: "0", "maskUnits": "userSpaceOnUse", y: "0", height: "31", id: "mask0_3548_4919", rect { x: "0.5", width: "30", y: "0.5", fill: "#D9D9D9", height: "30", } } g { mask: "url(#mask0_3548_4919)", path { style: "fill: rgb(var(--color-background))", d: "M15.504 18.2343L17.5634 19.5047C17.8113 19.6392 18.0453 19.6237 18.2653 19.4581C18.4851 19.2927 18.5588 19.0779 18.4865 18.8137L17.9375 16.4703L19.8075 14.8681C20.0173 14.677 20.08 14.4543 19.9956 14.2C19.9112 13.9454 19.7412 13.806 19.4856 13.7818L17.0503 13.5759L16.0956 11.34C15.9818 11.0731 15.787 10.9397 15.5112 10.9397C15.2354 10.9397 15.0404 11.0731 14.9262 11.34L13.9659 13.584L11.5168 13.7906C11.2595 13.8148 11.0887 13.953 11.0043 14.2053C10.92 14.4576 10.9827 14.6785 11.1925 14.8681L13.0625 16.4703L12.5134 18.8137C12.4411 19.0779 12.5131 19.2927 12.7293 19.4581C12.9458 19.6237 13.1843 19.6392 13.445 19.5047L15.504 18.2343ZM11.8375 24.4415H9.00402C8.32507 24.4415 7.74767 24.2037 7.27184 23.7281C6.79621 23.2523 6.5584 22.6749 6.5584 21.9959V19.1625L4.60402 17.2234C4.33986 16.9749 4.156 16.7051 4.05246 16.414C3.94913 16.1232 3.89746 15.8185 3.89746 15.5C3.89746 15.1814 3.94913 14.8767 4.05246 14.5859C4.156 14.2949 4.33986 14.0251 4.60402 13.7765L6.5584 11.8375V9.00402C6.5584 8.32507 6.79621 7.74767 7.27184 7.27184C7.74767 6.79621 8.32507 6.5584 9.00402 6.5584H11.8375L13.7765 4.60402C14.0301 4.33986 14.3011 4.156 14.5896 4.05246C14.878 3.94913 15.1814 3.89746 15.5 3.89746C15.8185 3.89746 16.1219 3.94913 16.4103 4.05246C16.6988 4.156 16.9699 4.33986 17.2234 4.60402L19.1625 6.5584H21.9959C22.6749 6.5584 23.2523 6.79621 23.7281 7.27184C24.2037 7.74767 24.4415 8.32507 24.4415 9.00402V11.8375L26.3959 13.7765C26.6601 14.0301 26.8439 14.3011 26.9475 14.5896C27.0508 14.878 27.1025 15.1814 27.1025 15.5C27.1025 15.8185 27.0508 16.1219 26.9475 16.4103C26.8439 16.6988 26.6601 16.9699 26.3959 17.2234L24.4415 19.1625V21.9959C24.4415 22.6749 24.2037 23.2523 23.7281 23.7281C23.2523 24.2037 22.6749 24.4415 21.9959 24.4415H19.1625L17.2234 26.3959C16.9749 26.6601 16.7051 26.8439 16.414 26.9475C16.1232 27.0508 15.8185 27.1025 15.5 27.1025C15.1814 27.1025 14.8767 27.0508 14.5859 26.9475C14.2949 26.8439 14.0251 26.6601 13.7765 26.3959L11.8375 24.4415Z", } } }
     }
 };
 
@@ -94,6 +98,8 @@ pub fn CollectionBlock(
 ) -> Element {
     let mut radio = collection_id().use_collection_radio();
     let picture_path = radio.read().picture_path().to_string_lossy().to_string();
+    let accent = use_collection_accent(collection_id);
+    let accent = accent_channels(accent());
     let (mut onmounted, status, style) = use_text_scroller();
     let class = tw_merge!("size-[280px] max-w-[280px] min-w-[280px]", extended_class);
     let class = if fat {
@@ -111,14 +117,23 @@ pub fn CollectionBlock(
 
     let log = use_signal_sync(LoggerEvent::default);
 
+    let import = use_import_drop();
+    let backup_worlds = use_world_backup(collection_id);
+
     use_effect(move || {
         info!("{}", log.read());
     });
     rsx! {
+        ImportConfirmation {
+            collection_id,
+            import,
+        }
         button {
             class,
-            style: "z-index:{z_index}; background: radial-gradient(273.29% 100% at 0% 100%, #0E0E0E 22.75%, rgba(14, 14, 14, 0.00) 100%), url('{picture_path}') lightgray 50% / cover no-repeat;",
+            style: "z-index:{z_index}; background: radial-gradient(273.29% 100% at 0% 100%, rgb(var(--color-deep-background)) 22.75%, rgb({accent} / 0.15) 100%), url('{picture_path}') lightgray 50% / cover no-repeat;",
             aria_selected: status(),
+            ondragover: move |e| e.prevent_default(),
+            ondrop: move |e| import.ondrop(&e),
             onclick: move |_| {
                 if !launch_game_hover() {
                     Pages::collection_display(collection_id())
@@ -130,7 +145,7 @@ pub fn CollectionBlock(
                 div {
                     class: "self-start w-full grid grid-flow-col z-10 justify-stretch",
                     div {
-                        class: "flex justify-center items-center bg-green rounded-[15px] justify-self-start size-[45px]",
+                        class: "flex justify-center items-center bg-accent rounded-[15px] justify-self-start size-[45px]",
                         INVERTED_STAR {}
                     }
                     div {
@@ -145,8 +160,13 @@ pub fn CollectionBlock(
                             },
                             onclick: move |x| async move {
                                 x.stop_propagation();
+                                // a missing backup isn't worth keeping the game from starting
+                                if let Err(err) = backup_worlds().await {
+                                    toast_error(err);
+                                }
                                 radio.with_async_mut(|mut collection| async move {
-                                    collection.launch_game(log).await?;
+                                    let label = collection.display_name().clone();
+                                    download_queue::run_backend(collection_id(), label, collection.launch_game(log)).await??;
                                     Ok(collection)
                                 }).await.context("async write collection error")?;
                                 Ok(())
@@ -186,7 +206,7 @@ pub fn CollectionBlock(
                     }
                     div {
                         class: "text-[15px] text-hint text-ellipsis text-nowrap trim",
-                        "遊玩中•由我建立"
+                        {t!("main-playing-created-by-me")}
                     }
                 }
             }
@@ -203,8 +223,8 @@ fn SuggestionPage() -> Element {
         vec![
             Contents::new(
                 [
-                    ContentType::text("建議動作").css("text-3xl"),
-                    ContentType::hint("你還沒完成快速設定，我們建議你盡快完成"),
+                    ContentType::text(t!("main-suggested-action")).css("text-3xl"),
+                    ContentType::hint(t!("main-quick-setup-hint")),
                 ],
                 Alignment::Left,
             )
@@ -216,8 +236,8 @@ fn SuggestionPage() -> Element {
         vec![
             Contents::new(
                 [
-                    ContentType::text("需要幫助？").css("text-3xl"),
-                    ContentType::hint("查看使用手冊與教學"),
+                    ContentType::text(t!("main-need-help")).css("text-3xl"),
+                    ContentType::hint(t!("main-need-help-hint")),
                 ],
                 Alignment::Left,
             )
@@ -229,8 +249,8 @@ fn SuggestionPage() -> Element {
         vec![
             Contents::new(
                 [
-                    ContentType::text("探索內容").css("text-3xl"),
-                    ContentType::hint("開始探索 Minecraft 的第三方社群內容"),
+                    ContentType::text(t!("main-explore")).css("text-3xl"),
+                    ContentType::hint(t!("main-explore-hint")),
                 ],
                 Alignment::Left,
             )
@@ -240,8 +260,8 @@ fn SuggestionPage() -> Element {
         vec![
             Contents::new(
                 [
-                    ContentType::text("創造中心").css("text-3xl"),
-                    ContentType::hint("建立你的個人化收藏"),
+                    ContentType::text(t!("main-creation-center")).css("text-3xl"),
+                    ContentType::hint(t!("main-creation-center-hint")),
                 ],
                 Alignment::Left,
             )
@@ -251,8 +271,8 @@ fn SuggestionPage() -> Element {
         vec![
             Contents::new(
                 [
-                    ContentType::text("打造個人化收藏").css("text-3xl"),
-                    ContentType::hint("你可以透過風格化功能來裝飾你的收藏"),
+                    ContentType::text(t!("main-personalize")).css("text-3xl"),
+                    ContentType::hint(t!("main-personalize-hint")),
                 ],
                 Alignment::Left,
             )
@@ -262,8 +282,8 @@ fn SuggestionPage() -> Element {
         vec![
             Contents::new(
                 [
-                    ContentType::text("建議動作").css("text-3xl"),
-                    ContentType::hint("啟動器更新已經準備就緒"),
+                    ContentType::text(t!("main-suggested-action")).css("text-3xl"),
+                    ContentType::hint(t!("main-update-ready-hint")),
                 ],
                 Alignment::Left,
             )
@@ -281,7 +301,7 @@ fn SuggestionPage() -> Element {
                 extended_css_class: "pr-[5px]",
                 fill_mode: FillMode::Fit,
                 string_placements: vec![
-                    ContentType::text("建議：快速設定").align_left(),
+                    ContentType::text(t!("main-suggest-quick-setup")).align_left(),
                     ContentType::svg(BLOCK)
                         .css(
                             "drop-shadow-lg bg-background svg-[40px] inline-flex justify-center rounded-full",
@@ -295,7 +315,7 @@ fn SuggestionPage() -> Element {
                 extended_css_class: "pr-[5px]",
                 size: Size::Small,
                 string_placements: vec![
-                    ContentType::text("建議：更新提醒").align_left(),
+                    ContentType::text(t!("main-suggest-update")).align_left(),
                     ContentType::svg(BLOCK)
                         .css(
                             "drop-shadow-lg bg-background svg-[40px] rounded-full inline-flex justify-center",
@@ -307,25 +327,25 @@ fn SuggestionPage() -> Element {
                 roundness: Roundness::Pill,
                 fill_mode: FillMode::Fit,
                 size: Size::Small,
-                string_placements: vec![ContentType::text("使用手冊").align_center()]
+                string_placements: vec![ContentType::text(t!("main-manual")).align_center()]
             }
             Button {
                 roundness: Roundness::Pill,
                 fill_mode: FillMode::Fit,
                 size: Size::Small,
-                string_placements: vec![ContentType::text("探索內容").align_center()]
+                string_placements: vec![ContentType::text(t!("main-explore")).align_center()]
             }
             Button {
                 roundness: Roundness::Pill,
                 fill_mode: FillMode::Fit,
                 size: Size::Small,
-                string_placements: vec![ContentType::text("創造中心").align_center()]
+                string_placements: vec![ContentType::text(t!("main-creation-center")).align_center()]
             }
             Button {
                 roundness: Roundness::Pill,
                 fill_mode: FillMode::Fit,
                 size: Size::Small,
-                string_placements: vec![ContentType::text("個人化收藏").align_center()]
+                string_placements: vec![ContentType::text(t!("main-personal-collections")).align_center()]
             }
         }
         div {
@@ -341,18 +361,18 @@ fn SuggestionPage() -> Element {
                     div {
                         span {
                             class: "text-lime-300 text-6xl font-bold leading-[78px] tracking-[6px]",
-                            "探索  創造"
+                            {t!("main-hero-line-1")}
                             br {
 
                             }
                         }
                         span {
                             class: "text-white text-6xl font-normal leading-[78px] tracking-[6px]",
-                            "無窮  無限"
+                            {t!("main-hero-line-2")}
                             br {
 
                             }
-                            "創作  可能"
+                            {t!("main-hero-line-3")}
                         }
                     }
                 }
@@ -396,8 +416,8 @@ fn CollectionsPage() -> Element {
                 string_placements: vec![
                     Contents::new(
                             vec![
-                                ContentType::text("我的錦集").css("text-[35px]"),
-                                ContentType::hint("你最愛的收藏都在這裡")
+                                ContentType::text(t!("my-collections")).css("text-[35px]"),
+                                ContentType::hint(t!("main-my-collections-hint"))
                                     .css("text-[18px]"),
                             ],
                             Alignment::Left,
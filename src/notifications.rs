@@ -0,0 +1,500 @@
+use std::{collections::BTreeSet, future::Future};
+
+use chrono::{DateTime, Local};
+use dioxus::prelude::*;
+use dioxus_logger::tracing::{info, warn};
+use futures_util::future::LocalBoxFuture;
+use rust_lib::api::shared_resources::collection::use_keys;
+use serde::{Deserialize, Serialize};
+use snafu::prelude::*;
+use strum::{EnumIter, IntoEnumIterator};
+
+use crate::{
+    collection_display::mod_renderer::versions::UpdateNotifier,
+    persistence::{data_dir, Persisted},
+    t,
+    BaseComponents::organisms::{modal::Modal, toast::toast_error},
+};
+
+/// Notifications of this session, newest first
+pub static NOTIFICATIONS: GlobalSignal<NotificationCenter> =
+    Signal::global(NotificationCenter::default);
+
+pub static NOTIFICATION_SETTINGS: GlobalSignal<NotificationSettings> =
+    Signal::global(|| NotificationSettings::load_or_create(&data_dir()));
+
+/// Older notifications are dropped once the center holds more than this
+const MAX_NOTIFICATIONS: usize = 100;
+
+#[derive(Snafu, Debug)]
+pub enum NotificationError {
+    #[snafu(display("Failed to reach the desktop notification service"))]
+    Unreachable {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, EnumIter,
+)]
+pub enum NotificationKind {
+    DownloadFinished,
+    CollectionCreated,
+    GameCrashed,
+    ModUpdates,
+}
+
+impl NotificationKind {
+    #[must_use]
+    pub fn title(self) -> String {
+        match self {
+            Self::DownloadFinished => t!("notification-kind-download-finished"),
+            Self::CollectionCreated => t!("notification-kind-collection-created"),
+            Self::GameCrashed => t!("notification-kind-game-crashed"),
+            Self::ModUpdates => t!("notification-kind-mod-updates"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    /// Events that are neither kept in the center nor sent to the desktop
+    pub disabled: BTreeSet<NotificationKind>,
+    /// Whether notifications are also sent to the desktop
+    pub desktop: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            disabled: BTreeSet::new(),
+            desktop: true,
+        }
+    }
+}
+
+impl Persisted for NotificationSettings {
+    const FILE_NAME: &'static str = "notifications.json";
+}
+
+impl NotificationSettings {
+    #[must_use]
+    pub fn is_enabled(&self, kind: NotificationKind) -> bool {
+        !self.disabled.contains(&kind)
+    }
+
+    pub fn toggle(&mut self, kind: NotificationKind) {
+        if !self.disabled.remove(&kind) {
+            self.disabled.insert(kind);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub id: u64,
+    pub kind: NotificationKind,
+    pub title: String,
+    pub body: String,
+    pub time: DateTime<Local>,
+    pub read: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NotificationCenter {
+    pub notifications: Vec<Notification>,
+    next_id: u64,
+}
+
+impl NotificationCenter {
+    pub fn push(&mut self, kind: NotificationKind, title: String, body: String) {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.notifications.insert(
+            0,
+            Notification {
+                id,
+                kind,
+                title,
+                body,
+                time: Local::now(),
+                read: false,
+            },
+        );
+        self.notifications.truncate(MAX_NOTIFICATIONS);
+    }
+
+    #[must_use]
+    pub fn unread(&self) -> usize {
+        self.notifications.iter().filter(|x| !x.read).count()
+    }
+
+    pub fn mark_all_read(&mut self) {
+        for notification in &mut self.notifications {
+            notification.read = true;
+        }
+    }
+
+    pub fn remove(&mut self, id: u64) {
+        self.notifications.retain(|x| x.id != id);
+    }
+
+    pub fn clear(&mut self) {
+        self.notifications.clear();
+    }
+}
+
+/// Shows notifications outside the app.
+///
+/// Implemented by the freedesktop D-Bus service on Linux, and can be swapped for a fake that records what it gets.
+pub trait NotificationSink {
+    fn send<'a>(
+        &'a self,
+        summary: &'a str,
+        body: &'a str,
+    ) -> LocalBoxFuture<'a, Result<(), NotificationError>>;
+}
+
+/// `org.freedesktop.Notifications` on the session bus
+#[cfg(target_os = "linux")]
+pub struct Freedesktop;
+
+#[cfg(target_os = "linux")]
+impl NotificationSink for Freedesktop {
+    fn send<'a>(
+        &'a self,
+        summary: &'a str,
+        body: &'a str,
+    ) -> LocalBoxFuture<'a, Result<(), NotificationError>> {
+        Box::pin(async move {
+            // connecting to the bus is a handshake of its own, so it's only done once
+            static SESSION: tokio::sync::OnceCell<zbus::Connection> =
+                tokio::sync::OnceCell::const_new();
+            let connection = SESSION
+                .get_or_try_init(zbus::Connection::session)
+                .await
+                .boxed()
+                .context(UnreachableSnafu)?;
+            connection
+                .call_method(
+                    Some("org.freedesktop.Notifications"),
+                    "/org/freedesktop/Notifications",
+                    Some("org.freedesktop.Notifications"),
+                    "Notify",
+                    &(
+                        "prag-portal",
+                        0_u32,
+                        "",
+                        summary,
+                        body,
+                        Vec::<&str>::new(),
+                        std::collections::HashMap::<&str, zbus::zvariant::Value>::new(),
+                        -1_i32,
+                    ),
+                )
+                .await
+                .boxed()
+                .context(UnreachableSnafu)?;
+            Ok(())
+        })
+    }
+}
+
+/// Platforms without a desktop notification service only get the in-app center
+#[cfg(not(target_os = "linux"))]
+pub struct InAppOnly;
+
+#[cfg(not(target_os = "linux"))]
+impl NotificationSink for InAppOnly {
+    fn send<'a>(
+        &'a self,
+        _summary: &'a str,
+        _body: &'a str,
+    ) -> LocalBoxFuture<'a, Result<(), NotificationError>> {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+fn desktop_sink() -> impl NotificationSink {
+    #[cfg(target_os = "linux")]
+    {
+        Freedesktop
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        InAppOnly
+    }
+}
+
+/// Records an event in the notification center and sends it to the desktop, unless `kind` is turned off
+pub fn notify(kind: NotificationKind, title: String, body: String) {
+    if let Some(delivery) = record(desktop_sink(), kind, title, body) {
+        spawn(delivery);
+    }
+}
+
+/// Records the event in the notification center, returning its delivery to `sink` when the desktop should
+/// be notified too
+fn record(
+    sink: impl NotificationSink + 'static,
+    kind: NotificationKind,
+    title: String,
+    body: String,
+) -> Option<impl Future<Output = ()>> {
+    let desktop = {
+        let settings = NOTIFICATION_SETTINGS.peek();
+        if !settings.is_enabled(kind) {
+            return None;
+        }
+        settings.desktop
+    };
+    info!("{title}: {body}");
+    NOTIFICATIONS
+        .write()
+        .push(kind, title.clone(), body.clone());
+    desktop.then(|| async move {
+        if let Err(err) = sink.send(&title, &body).await {
+            warn!("Failed to send a desktop notification: {err}");
+        }
+    })
+}
+
+/// Background checks that only surface through notifications, one per collection
+#[component]
+pub fn Notifiers() -> Element {
+    let keys = use_keys();
+    rsx! {
+        for collection_id in keys {
+            UpdateNotifier {
+                key: "{collection_id:?}",
+                collection_id,
+            }
+        }
+    }
+}
+
+/// Opens the notification center, showing how many notifications are unread
+#[component]
+pub fn NotificationBell() -> Element {
+    let mut active = use_signal(|| false);
+    use_effect(move || {
+        if let Err(err) = NOTIFICATION_SETTINGS.read().save_to(&data_dir()) {
+            toast_error(err);
+        }
+    });
+    let unread = NOTIFICATIONS.read().unread();
+    rsx! {
+        NotificationCenterModal { active }
+        button {
+            class: "flex items-center justify-between gap-[10px] rounded bg-background px-[20px] h-[60px] text-white text-[18px] font-bold",
+            onclick: move |_| active.set(true),
+            div {
+                class: "trim",
+                {t!("notification-title")}
+            }
+            if unread > 0 {
+                div {
+                    class: "px-[10px] py-[5px] rounded-full bg-red text-[14px] trim",
+                    "{unread}"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn NotificationCenterModal(active: Signal<bool>) -> Element {
+    use_effect(move || {
+        if active() {
+            NOTIFICATIONS.write().mark_all_read();
+        }
+    });
+    let notifications = NOTIFICATIONS.read().notifications.clone();
+    rsx! {
+        Modal {
+            active,
+            div {
+                class: "flex flex-col gap-[20px] w-[600px] max-h-[80vh] p-[30px] rounded-[20px] bg-background",
+                div {
+                    class: "flex items-center gap-[15px]",
+                    div {
+                        class: "grow text-white text-[30px] font-bold trim",
+                        {t!("notification-title")}
+                    }
+                    if !notifications.is_empty() {
+                        button {
+                            class: "text-hint text-[16px] trim",
+                            onclick: move |_| NOTIFICATIONS.write().clear(),
+                            {t!("notification-clear")}
+                        }
+                    }
+                    button {
+                        class: "text-white text-[16px] trim",
+                        onclick: move |_| active.set(false),
+                        {t!("notification-close")}
+                    }
+                }
+                div {
+                    class: "flex flex-col gap-[10px] overflow-y-scroll",
+                    if notifications.is_empty() {
+                        div {
+                            class: "text-hint text-[16px] trim",
+                            {t!("notification-empty")}
+                        }
+                    }
+                    for notification in notifications {
+                        NotificationRow {
+                            key: "{notification.id}",
+                            notification,
+                        }
+                    }
+                }
+                NotificationToggles {}
+            }
+        }
+    }
+}
+
+#[component]
+fn NotificationRow(notification: Notification) -> Element {
+    let id = notification.id;
+    rsx! {
+        div {
+            class: "flex gap-[15px] p-[15px] rounded-[15px] bg-deep-background",
+            div {
+                class: "grow flex flex-col gap-[10px]",
+                div {
+                    class: "flex gap-[10px]",
+                    div {
+                        class: "grow text-white text-[18px] font-bold trim",
+                        {notification.title}
+                    }
+                    div {
+                        class: "text-hint text-[14px] trim",
+                        {notification.time.format("%H:%M").to_string()}
+                    }
+                }
+                div {
+                    class: "text-hint text-[14px] font-medium",
+                    {notification.body}
+                }
+            }
+            button {
+                class: "self-start text-hint text-[14px] trim",
+                onclick: move |_| NOTIFICATIONS.write().remove(id),
+                {t!("notification-dismiss")}
+            }
+        }
+    }
+}
+
+/// Turns each kind of event, and desktop delivery, on and off
+#[component]
+pub(crate) fn NotificationToggles() -> Element {
+    let settings = NOTIFICATION_SETTINGS.read().clone();
+    rsx! {
+        div {
+            class: "flex flex-wrap gap-[10px]",
+            for kind in NotificationKind::iter() {
+                button {
+                    class: "h-[40px] px-[15px] rounded-[15px] bg-deep-background text-white text-[14px] font-medium aria-selected:bg-white aria-selected:text-black trim",
+                    aria_selected: settings.is_enabled(kind),
+                    onclick: move |_| NOTIFICATION_SETTINGS.write().toggle(kind),
+                    {kind.title()}
+                }
+            }
+            button {
+                class: "h-[40px] px-[15px] rounded-[15px] bg-deep-background text-white text-[14px] font-medium aria-selected:bg-white aria-selected:text-black trim",
+                aria_selected: settings.desktop,
+                onclick: move |_| {
+                    let mut settings = NOTIFICATION_SETTINGS.write();
+                    settings.desktop = !settings.desktop;
+                },
+                {t!("notification-desktop")}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use crate::test_support::block_on_in_dom;
+
+    /// Keeps whatever it is asked to show
+    #[derive(Clone, Default)]
+    struct RecordingSink {
+        sent: Rc<RefCell<Vec<(String, String)>>>,
+    }
+
+    impl NotificationSink for RecordingSink {
+        fn send<'a>(
+            &'a self,
+            summary: &'a str,
+            body: &'a str,
+        ) -> LocalBoxFuture<'a, Result<(), NotificationError>> {
+            self.sent
+                .borrow_mut()
+                .push((summary.to_owned(), body.to_owned()));
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    /// Records a download notification with `settings`, returning what the center and the desktop got
+    fn notify_with(settings: NotificationSettings) -> (Vec<Notification>, Vec<(String, String)>) {
+        let sink = RecordingSink::default();
+        let sent = sink.sent.clone();
+        let notifications = block_on_in_dom(async move {
+            *NOTIFICATION_SETTINGS.write() = settings;
+            let delivery = record(
+                sink,
+                NotificationKind::DownloadFinished,
+                String::from("Downloaded"),
+                String::from("Fabulously Optimized"),
+            );
+            if let Some(delivery) = delivery {
+                delivery.await;
+            }
+            NOTIFICATIONS.peek().notifications.clone()
+        });
+        let sent = sent.borrow().clone();
+        (notifications, sent)
+    }
+
+    #[test]
+    fn sends_enabled_kinds_to_the_desktop() {
+        let (notifications, sent) = notify_with(NotificationSettings::default());
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].kind, NotificationKind::DownloadFinished);
+        assert_eq!(
+            sent,
+            [(
+                String::from("Downloaded"),
+                String::from("Fabulously Optimized")
+            )]
+        );
+    }
+
+    #[test]
+    fn drops_disabled_kinds() {
+        let (notifications, sent) = notify_with(NotificationSettings {
+            disabled: BTreeSet::from([NotificationKind::DownloadFinished]),
+            desktop: true,
+        });
+        assert!(notifications.is_empty());
+        assert!(sent.is_empty());
+    }
+
+    #[test]
+    fn keeps_notifications_in_app_with_the_desktop_off() {
+        let (notifications, sent) = notify_with(NotificationSettings {
+            disabled: BTreeSet::new(),
+            desktop: false,
+        });
+        assert_eq!(notifications.len(), 1);
+        assert!(sent.is_empty());
+    }
+}
@@ -11,6 +11,8 @@ pub enum Pages {
     Explore,
     Collections,
     DownloadProgress,
+    Logs,
+    Settings,
     CollectionPage {
         id: CollectionId,
         state: CollectionPageState,
@@ -49,6 +51,8 @@ impl ToString for Pages {
             Self::Explore => "explore".into(),
             Self::Collections => "collections".into(),
             Self::DownloadProgress => "download-progress".into(),
+            Self::Logs => "logs".into(),
+            Self::Settings => "settings".into(),
             Self::CollectionPage { id, state } => {
                 let mut hasher = DefaultHasher::new();
                 id.hash(&mut hasher);
@@ -89,7 +93,7 @@ impl Pages {
         let no_slideout = history.prev_peek() == Some(self)
             && !matches!(
                 history.active,
-                Self::CollectionPage { .. } | Self::DownloadProgress
+                Self::CollectionPage { .. } | Self::DownloadProgress | Self::Logs | Self::Settings
             );
 
         let edit_case = matches!(
@@ -0,0 +1,150 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use dioxus::prelude::*;
+use dioxus_logger::tracing::warn;
+use rust_lib::api::shared_resources::collection::{
+    use_collections_radio, Collection, CollectionId,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use snafu::prelude::*;
+
+use crate::{settings::AppSettings, BaseComponents::organisms::toast::toast_error};
+
+#[derive(Snafu, Debug)]
+pub enum PersistenceError {
+    #[snafu(display("Failed to access {}", path.display()))]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Failed to serialize {}", path.display()))]
+    Serialize {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+/// Chosen once per run, so everything keeps reading and writing the same place until a restart
+static DATA_DIR: LazyLock<PathBuf> = LazyLock::new(|| {
+    AppSettings::load_from(&default_data_dir())
+        .data_directory
+        .unwrap_or_else(default_data_dir)
+});
+
+/// Root directory for state owned by the frontend, [`default_data_dir`] unless moved in the settings
+#[must_use]
+pub fn data_dir() -> PathBuf {
+    DATA_DIR.clone()
+}
+
+/// Where the data dir is by default, e.g. `~/.local/share/prag-portal`, the settings always stay here
+#[must_use]
+pub fn default_data_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("prag-portal")
+}
+
+/// Where the application log is written, e.g. `~/.local/share/prag-portal/logs`
+#[must_use]
+pub fn log_dir() -> PathBuf {
+    data_dir().join("logs")
+}
+
+/// Root directory for regenerable data, e.g. `~/.cache/prag-portal`
+#[must_use]
+pub fn cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("prag-portal")
+}
+
+/// Directory holding frontend-only state that belongs to a single collection.
+#[must_use]
+pub fn collection_state_dir(collection: &Collection) -> PathBuf {
+    collection.entry_path.join(".prag-portal")
+}
+
+/// A value that is stored as a json file named [`Persisted::FILE_NAME`].
+pub trait Persisted: Serialize + DeserializeOwned + Default {
+    const FILE_NAME: &'static str;
+
+    /// Loads the value from `dir`, falling back to [`Default`] when the file is missing or corrupted.
+    fn load_from(dir: &Path) -> Self {
+        let path = dir.join(Self::FILE_NAME);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&content).unwrap_or_else(|err| {
+            warn!(
+                "{} is corrupted, using default value: {err}",
+                path.display()
+            );
+            Self::default()
+        })
+    }
+
+    /// Same as [`Persisted::load_from`], but writes the default value out when the file is missing,
+    /// so that it can be found and edited by hand.
+    fn load_or_create(dir: &Path) -> Self {
+        let value = Self::load_from(dir);
+        if !dir.join(Self::FILE_NAME).exists() {
+            if let Err(err) = value.save_to(dir) {
+                warn!("{err}");
+            }
+        }
+        value
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if `dir` cannot be created or the file cannot be written.
+    fn save_to(&self, dir: &Path) -> Result<(), PersistenceError> {
+        let path = dir.join(Self::FILE_NAME);
+        std::fs::create_dir_all(dir).context(IoSnafu { path: dir })?;
+        let content = serde_json::to_string_pretty(self).context(SerializeSnafu { path: &path })?;
+        let temp = path.with_extension("json.tmp");
+        std::fs::write(&temp, content).context(IoSnafu { path: &temp })?;
+        std::fs::rename(&temp, &path).context(IoSnafu { path: &path })?;
+        Ok(())
+    }
+}
+
+/// Loads `T` from the collection's state directory and writes it back whenever the signal changes.
+///
+/// Switching `collection_id` loads the value of the new collection instead of carrying the old one over.
+pub fn use_collection_persisted<T: Persisted + 'static>(
+    collection_id: ReadOnlySignal<CollectionId>,
+) -> Signal<T> {
+    let collections = use_collections_radio();
+    let dir = use_memo(move || {
+        collections
+            .read()
+            .0
+            .get(&collection_id())
+            .map(collection_state_dir)
+    });
+    let load = |dir: Option<&Path>| dir.map(T::load_from).unwrap_or_default();
+    let mut state = use_signal(|| load(dir.peek().as_deref()));
+    // where `state` was loaded from, so it is never saved into another collection
+    let mut loaded_from = use_signal(|| dir.peek().clone());
+    use_effect(move || {
+        let dir = dir();
+        if *loaded_from.peek() != dir {
+            state.set(load(dir.as_deref()));
+            loaded_from.set(dir);
+        }
+    });
+    use_effect(move || {
+        let state = state.read();
+        if let Some(dir) = &*loaded_from.read() {
+            if let Err(err) = state.save_to(dir) {
+                toast_error(err);
+            }
+        }
+    });
+    state
+}
@@ -0,0 +1,770 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use dioxus::prelude::*;
+use rust_lib::api::{
+    backend_exclusive::mod_management::mods::ModMetadata,
+    shared_resources::collection::CollectionId,
+};
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
+use snafu::prelude::*;
+
+use crate::{
+    collection_display::mod_renderer::table::ModSource,
+    download_queue::{self, DownloadError, FollowUp},
+};
+
+const MODRINTH_API: &str = "https://api.modrinth.com/v2";
+const CURSEFORGE_API: &str = "https://api.curseforge.com/v1";
+const MINECRAFT_GAME_ID: &str = "432";
+const MOD_CLASS_ID: &str = "6";
+const USER_AGENT: &str = concat!("prag-portal/", env!("CARGO_PKG_VERSION"));
+
+/// Project ids found by [`ModSource::installed_project`], keyed by the first file of the mod
+static INSTALLED_PROJECTS: GlobalSignal<HashMap<PathBuf, String>> = Signal::global(HashMap::new);
+
+/// When the last batched lookup was sent, lookups take turns so every collection checking for updates at
+/// once stays within the platforms' rate limits
+static LAST_BATCH: LazyLock<tokio::sync::Mutex<Option<Instant>>> =
+    LazyLock::new(|| tokio::sync::Mutex::new(None));
+
+const BATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Snafu, Debug)]
+pub enum PlatformApiError {
+    #[snafu(display("Failed to request {url}"))]
+    Request { url: String, source: reqwest::Error },
+    #[snafu(display("Could not find {name} on {}", source_platform.title()))]
+    ProjectNotFound {
+        name: String,
+        source_platform: ModSource,
+    },
+    #[snafu(display("CurseForge requires an api key, please set `CURSEFORGE_API_KEY`"))]
+    MissingApiKey,
+    #[snafu(display("{filename} has no download url"))]
+    MissingDownloadUrl { filename: String },
+    #[snafu(display("Failed to read {}", path.display()))]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(transparent)]
+    Download { source: DownloadError },
+}
+
+/// A project as published on Modrinth or CurseForge
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteProject {
+    pub source: ModSource,
+    pub id: String,
+    pub title: String,
+    /// Markdown on Modrinth, HTML on CurseForge, both can be fed into `Description`
+    pub description: String,
+    pub downloads: u64,
+    pub last_updated: DateTime<Utc>,
+    pub icon_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteVersion {
+    pub source: ModSource,
+    pub project_id: String,
+    pub id: String,
+    pub version_number: String,
+    pub changelog: Option<String>,
+    pub published: DateTime<Utc>,
+    pub game_versions: Vec<String>,
+    /// lowercased loader names, e.g. `fabric`
+    pub loaders: Vec<String>,
+    pub files: Vec<RemoteFile>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteFile {
+    pub filename: String,
+    pub url: Option<String>,
+    pub size: u64,
+    pub sha1: Option<String>,
+    pub primary: bool,
+}
+
+impl RemoteVersion {
+    /// Whether one of the files of this version is `path`, ignoring the `.disabled` suffix
+    #[must_use]
+    pub fn is_installed_at(&self, path: &Path) -> bool {
+        let Some(filename) = path.file_name().and_then(|x| x.to_str()) else {
+            return false;
+        };
+        let filename = filename.trim_end_matches(".disabled");
+        self.files.iter().any(|x| x.filename == filename)
+    }
+
+    #[must_use]
+    pub fn supports(&self, game_version: &str, loader: Option<&str>) -> bool {
+        self.game_versions.iter().any(|x| x == game_version)
+            && loader.map_or(true, |loader| {
+                self.loaders.iter().any(|x| x.eq_ignore_ascii_case(loader))
+            })
+    }
+
+    #[must_use]
+    pub fn primary_file(&self) -> Option<&RemoteFile> {
+        self.files
+            .iter()
+            .find(|x| x.primary)
+            .or_else(|| self.files.first())
+    }
+}
+
+/// Returns the newest version that runs on `game_version` with `loader`
+#[must_use]
+pub fn latest_compatible<'a>(
+    versions: &'a [RemoteVersion],
+    game_version: &str,
+    loader: Option<&str>,
+) -> Option<&'a RemoteVersion> {
+    versions
+        .iter()
+        .filter(|x| x.supports(game_version, loader))
+        .max_by_key(|x| x.published)
+}
+
+pub(crate) fn client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .build()
+        .unwrap_or_default()
+}
+
+fn curseforge_key() -> Result<String, PlatformApiError> {
+    std::env::var("CURSEFORGE_API_KEY").map_err(|_| PlatformApiError::MissingApiKey)
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(
+    url: String,
+    query: &[(&str, &str)],
+    source: ModSource,
+) -> Result<T, PlatformApiError> {
+    let mut request = client().get(&url).query(query);
+    if source == ModSource::Curseforge {
+        request = request.header("x-api-key", curseforge_key()?);
+    }
+    request
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .context(RequestSnafu { url: &url })?
+        .json()
+        .await
+        .context(RequestSnafu { url })
+}
+
+#[derive(Deserialize)]
+struct CurseforgeData<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct CurseforgePage<T> {
+    data: Vec<T>,
+    pagination: CurseforgePagination,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseforgePagination {
+    total_count: usize,
+}
+
+/// The largest page CurseForge serves
+const CURSEFORGE_PAGE_SIZE: usize = 50;
+
+#[derive(Deserialize)]
+struct ModrinthSearch {
+    hits: Vec<ModrinthHit>,
+}
+
+#[derive(Deserialize)]
+struct ModrinthHit {
+    project_id: String,
+    title: String,
+}
+
+#[derive(Deserialize)]
+struct ModrinthProject {
+    id: String,
+    title: String,
+    body: String,
+    downloads: u64,
+    updated: DateTime<Utc>,
+    icon_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ModrinthVersion {
+    id: String,
+    project_id: String,
+    version_number: String,
+    changelog: Option<String>,
+    date_published: DateTime<Utc>,
+    game_versions: Vec<String>,
+    loaders: Vec<String>,
+    files: Vec<ModrinthFile>,
+}
+
+#[derive(Deserialize)]
+struct ModrinthFile {
+    url: String,
+    filename: String,
+    size: u64,
+    primary: bool,
+    hashes: ModrinthHashes,
+}
+
+#[derive(Deserialize)]
+struct ModrinthHashes {
+    sha1: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseforgeMod {
+    id: u64,
+    name: String,
+    download_count: f64,
+    date_modified: DateTime<Utc>,
+    logo: Option<CurseforgeLogo>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseforgeFingerprintMatches {
+    exact_matches: Vec<CurseforgeFingerprintMatch>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseforgeFingerprintMatch {
+    file: CurseforgeFile,
+    latest_files: Vec<CurseforgeFile>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseforgeLogo {
+    url: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseforgeFile {
+    id: u64,
+    mod_id: u64,
+    display_name: String,
+    file_name: String,
+    file_date: DateTime<Utc>,
+    file_length: u64,
+    download_url: Option<String>,
+    game_versions: Vec<String>,
+    hashes: Vec<CurseforgeHash>,
+    #[serde(default)]
+    file_fingerprint: u32,
+}
+
+#[derive(Deserialize)]
+struct CurseforgeHash {
+    value: String,
+    algo: u8,
+}
+
+const CURSEFORGE_LOADERS: [&str; 4] = ["forge", "neoforge", "fabric", "quilt"];
+
+impl From<ModrinthVersion> for RemoteVersion {
+    fn from(value: ModrinthVersion) -> Self {
+        Self {
+            source: ModSource::Modrinth,
+            project_id: value.project_id,
+            id: value.id,
+            version_number: value.version_number,
+            changelog: value.changelog,
+            published: value.date_published,
+            game_versions: value.game_versions,
+            loaders: value.loaders,
+            files: value
+                .files
+                .into_iter()
+                .map(|x| RemoteFile {
+                    filename: x.filename,
+                    url: Some(x.url),
+                    size: x.size,
+                    sha1: x.hashes.sha1,
+                    primary: x.primary,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<CurseforgeFile> for RemoteVersion {
+    fn from(value: CurseforgeFile) -> Self {
+        // CurseForge mixes loaders into `gameVersions`
+        let (loaders, game_versions) = value
+            .game_versions
+            .into_iter()
+            .partition::<Vec<_>, _>(|x| CURSEFORGE_LOADERS.contains(&x.to_lowercase().as_str()));
+        Self {
+            source: ModSource::Curseforge,
+            project_id: value.mod_id.to_string(),
+            id: value.id.to_string(),
+            version_number: value.display_name,
+            changelog: None,
+            published: value.file_date,
+            game_versions,
+            loaders: loaders.into_iter().map(|x| x.to_lowercase()).collect(),
+            files: vec![RemoteFile {
+                filename: value.file_name,
+                url: value.download_url,
+                size: value.file_length,
+                sha1: value
+                    .hashes
+                    .into_iter()
+                    .find(|x| x.algo == 1)
+                    .map(|x| x.value),
+                primary: true,
+            }],
+        }
+    }
+}
+
+/// CurseForge's fingerprint, murmur2 of the file without whitespace bytes
+fn curseforge_fingerprint(bytes: &[u8]) -> u32 {
+    let bytes = bytes
+        .iter()
+        .copied()
+        .filter(|x| !matches!(x, 9 | 10 | 13 | 32))
+        .collect::<Vec<_>>();
+    murmur2::murmur2(&bytes, 1)
+}
+
+impl ModSource {
+    /// Looks up the project titled exactly `name`, used when the mod isn't published on this platform
+    /// under the same file, see [`ModSource::installed_project`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the request fails or no project is titled `name`.
+    pub async fn find_project(self, name: &str) -> Result<RemoteProject, PlatformApiError> {
+        let not_found = || PlatformApiError::ProjectNotFound {
+            name: name.to_owned(),
+            source_platform: self,
+        };
+        match self {
+            Self::Modrinth => {
+                let search: ModrinthSearch = get_json(
+                    format!("{MODRINTH_API}/search"),
+                    &[
+                        ("query", name),
+                        ("limit", "5"),
+                        ("facets", "[[\"project_type:mod\"]]"),
+                    ],
+                    self,
+                )
+                .await?;
+                let hit = search
+                    .hits
+                    .iter()
+                    .find(|x| x.title.eq_ignore_ascii_case(name))
+                    .ok_or_else(not_found)?;
+                self.project(&hit.project_id).await
+            }
+            Self::Curseforge => {
+                let search: CurseforgeData<Vec<CurseforgeMod>> = get_json(
+                    format!("{CURSEFORGE_API}/mods/search"),
+                    &[
+                        ("gameId", MINECRAFT_GAME_ID),
+                        ("classId", MOD_CLASS_ID),
+                        ("pageSize", "5"),
+                        ("searchFilter", name),
+                    ],
+                    self,
+                )
+                .await?;
+                let hit = search
+                    .data
+                    .iter()
+                    .find(|x| x.name.eq_ignore_ascii_case(name))
+                    .ok_or_else(not_found)?;
+                self.project(&hit.id.to_string()).await
+            }
+        }
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if the request fails.
+    pub async fn project(self, id: &str) -> Result<RemoteProject, PlatformApiError> {
+        match self {
+            Self::Modrinth => {
+                let project: ModrinthProject =
+                    get_json(format!("{MODRINTH_API}/project/{id}"), &[], self).await?;
+                Ok(RemoteProject {
+                    source: self,
+                    id: project.id,
+                    title: project.title,
+                    description: project.body,
+                    downloads: project.downloads,
+                    last_updated: project.updated,
+                    icon_url: project.icon_url,
+                })
+            }
+            Self::Curseforge => {
+                let project: CurseforgeData<CurseforgeMod> =
+                    get_json(format!("{CURSEFORGE_API}/mods/{id}"), &[], self).await?;
+                let description: CurseforgeData<String> =
+                    get_json(format!("{CURSEFORGE_API}/mods/{id}/description"), &[], self).await?;
+                let project = project.data;
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let downloads = project.download_count as u64;
+                Ok(RemoteProject {
+                    source: self,
+                    id: project.id.to_string(),
+                    title: project.name,
+                    description: description.data,
+                    downloads,
+                    last_updated: project.date_modified,
+                    icon_url: project.logo.map(|x| x.url),
+                })
+            }
+        }
+    }
+
+    /// Every published version of `project_id`, newest first
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the request fails.
+    pub async fn versions(self, project_id: &str) -> Result<Vec<RemoteVersion>, PlatformApiError> {
+        let mut versions: Vec<RemoteVersion> = match self {
+            Self::Modrinth => get_json::<Vec<ModrinthVersion>>(
+                format!("{MODRINTH_API}/project/{project_id}/version"),
+                &[],
+                self,
+            )
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+            Self::Curseforge => {
+                let mut files = Vec::new();
+                loop {
+                    let page = get_json::<CurseforgePage<CurseforgeFile>>(
+                        format!("{CURSEFORGE_API}/mods/{project_id}/files"),
+                        &[
+                            ("index", &files.len().to_string()),
+                            ("pageSize", &CURSEFORGE_PAGE_SIZE.to_string()),
+                        ],
+                        self,
+                    )
+                    .await?;
+                    let last = page.data.is_empty()
+                        || files.len() + page.data.len() >= page.pagination.total_count;
+                    files.extend(page.data.into_iter().map(Into::into));
+                    if last {
+                        break files;
+                    }
+                }
+            }
+        };
+        versions.sort_by(|a, b| b.published.cmp(&a.published));
+        Ok(versions)
+    }
+
+    /// The changelog of `version`, CurseForge only serves it through a separate endpoint
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the request fails.
+    pub async fn changelog(self, version: &RemoteVersion) -> Result<String, PlatformApiError> {
+        if let Some(changelog) = &version.changelog {
+            return Ok(changelog.clone());
+        }
+        match self {
+            Self::Modrinth => Ok(String::new()),
+            Self::Curseforge => {
+                let changelog: CurseforgeData<String> = get_json(
+                    format!(
+                        "{CURSEFORGE_API}/mods/{}/files/{}/changelog",
+                        version.project_id, version.id
+                    ),
+                    &[],
+                    self,
+                )
+                .await?;
+                Ok(changelog.data)
+            }
+        }
+    }
+    /// The version `bytes` has been published as on this platform, by its hash.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a request fails for any other reason than an unknown hash.
+    pub async fn identify(self, bytes: &[u8]) -> Result<Option<RemoteVersion>, PlatformApiError> {
+        match self {
+            Self::Modrinth => {
+                let sha1 = hex::encode(Sha1::digest(bytes));
+                let url = format!("{MODRINTH_API}/version_file/{sha1}");
+                let response = client()
+                    .get(&url)
+                    .query(&[("algorithm", "sha1")])
+                    .send()
+                    .await
+                    .context(RequestSnafu { url: &url })?;
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    return Ok(None);
+                }
+                let version: ModrinthVersion = response
+                    .error_for_status()
+                    .context(RequestSnafu { url: &url })?
+                    .json()
+                    .await
+                    .context(RequestSnafu { url })?;
+                Ok(Some(version.into()))
+            }
+            Self::Curseforge => {
+                let url = format!("{CURSEFORGE_API}/fingerprints/{MINECRAFT_GAME_ID}");
+                let matches: CurseforgeData<CurseforgeFingerprintMatches> = client()
+                    .post(&url)
+                    .header("x-api-key", curseforge_key()?)
+                    .json(&serde_json::json!({ "fingerprints": [curseforge_fingerprint(bytes)] }))
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .context(RequestSnafu { url: &url })?
+                    .json()
+                    .await
+                    .context(RequestSnafu { url })?;
+                Ok(matches
+                    .data
+                    .exact_matches
+                    .into_iter()
+                    .next()
+                    .map(|x| x.file.into()))
+            }
+        }
+    }
+
+    /// The newest version of each file in `paths` that runs on `game_version` with `loader`, looked up by
+    /// their hashes in a single request. Files the platform doesn't know are left out.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a file can't be read or the request fails.
+    pub async fn latest_versions(
+        self,
+        paths: &[PathBuf],
+        game_version: &str,
+        loader: Option<&str>,
+    ) -> Result<HashMap<PathBuf, RemoteVersion>, PlatformApiError> {
+        let mut hashes = HashMap::new();
+        for path in paths {
+            let bytes = tokio::fs::read(path).await.context(ReadSnafu { path })?;
+            let hash = match self {
+                Self::Modrinth => hex::encode(Sha1::digest(&bytes)),
+                Self::Curseforge => curseforge_fingerprint(&bytes).to_string(),
+            };
+            hashes.insert(hash, path.clone());
+        }
+        if hashes.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut last = LAST_BATCH.lock().await;
+        if let Some(last) = *last {
+            tokio::time::sleep(BATCH_INTERVAL.saturating_sub(last.elapsed())).await;
+        }
+        *last = Some(Instant::now());
+        let latest: HashMap<PathBuf, RemoteVersion> = match self {
+            Self::Modrinth => {
+                let url = format!("{MODRINTH_API}/version_files/update");
+                let versions: HashMap<String, ModrinthVersion> = client()
+                    .post(&url)
+                    .json(&serde_json::json!({
+                        "hashes": hashes.keys().collect::<Vec<_>>(),
+                        "algorithm": "sha1",
+                        "loaders": loader.into_iter().collect::<Vec<_>>(),
+                        "game_versions": [game_version],
+                    }))
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .context(RequestSnafu { url: &url })?
+                    .json()
+                    .await
+                    .context(RequestSnafu { url })?;
+                versions
+                    .into_iter()
+                    .filter_map(|(hash, version)| Some((hashes.remove(&hash)?, version.into())))
+                    .collect()
+            }
+            Self::Curseforge => {
+                let url = format!("{CURSEFORGE_API}/fingerprints/{MINECRAFT_GAME_ID}");
+                let fingerprints = hashes
+                    .keys()
+                    .filter_map(|x| x.parse::<u32>().ok())
+                    .collect::<Vec<_>>();
+                let matches: CurseforgeData<CurseforgeFingerprintMatches> = client()
+                    .post(&url)
+                    .header("x-api-key", curseforge_key()?)
+                    .json(&serde_json::json!({ "fingerprints": fingerprints }))
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .context(RequestSnafu { url: &url })?
+                    .json()
+                    .await
+                    .context(RequestSnafu { url })?;
+                matches
+                    .data
+                    .exact_matches
+                    .into_iter()
+                    .filter_map(|x| {
+                        let path = hashes.remove(&x.file.file_fingerprint.to_string())?;
+                        let versions = x
+                            .latest_files
+                            .into_iter()
+                            .map(Into::into)
+                            .collect::<Vec<RemoteVersion>>();
+                        let latest = latest_compatible(&versions, game_version, loader)?;
+                        Some((path, latest.clone()))
+                    })
+                    .collect()
+            }
+        };
+        drop(last);
+        // the hashes have been looked up anyway
+        INSTALLED_PROJECTS.write().extend(
+            latest
+                .iter()
+                .map(|(path, version)| (path.clone(), version.project_id.clone())),
+        );
+        Ok(latest)
+    }
+
+    /// The project `mods` has been installed from, found by the hash of its file so a mod sharing its
+    /// name with another project can't be mixed up.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file can't be read, a request fails or the file isn't
+    /// published on the platform of `mods`.
+    pub async fn installed_project(mods: &ModMetadata) -> Result<RemoteProject, PlatformApiError> {
+        let source = Self::of(mods);
+        let not_found = || PlatformApiError::ProjectNotFound {
+            name: mods.name.clone(),
+            source_platform: source,
+        };
+        let path = mods
+            .get_filepaths()
+            .into_iter()
+            .flatten()
+            .next()
+            .ok_or_else(not_found)?;
+        let cached = INSTALLED_PROJECTS.peek().get(&path).cloned();
+        let id = match cached {
+            Some(id) => id,
+            None => {
+                let bytes = tokio::fs::read(&path)
+                    .await
+                    .context(ReadSnafu { path: &path })?;
+                let id = source
+                    .identify(&bytes)
+                    .await?
+                    .ok_or_else(not_found)?
+                    .project_id;
+                INSTALLED_PROJECTS.write().insert(path, id.clone());
+                id
+            }
+        };
+        source.project(&id).await
+    }
+
+    /// The project id of `mods` if [`ModSource::installed_project`] has already found it
+    #[must_use]
+    pub fn known_project_id(mods: &ModMetadata) -> Option<String> {
+        let path = mods.get_filepaths().into_iter().flatten().next()?;
+        INSTALLED_PROJECTS.read().get(&path).cloned()
+    }
+
+    /// [`ModSource::installed_project`] on the platform of `mods`, otherwise the project with its exact name
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a request fails or the project can't be found.
+    pub async fn project_of(self, mods: &ModMetadata) -> Result<RemoteProject, PlatformApiError> {
+        if self == Self::of(mods) {
+            Self::installed_project(mods).await
+        } else {
+            self.find_project(&mods.name).await
+        }
+    }
+}
+
+/// Forgets the project [`ModSource::installed_project`] found for the file at `path`, once something else
+/// is installed there
+pub fn forget_installed_project(path: &Path) {
+    INSTALLED_PROJECTS.write().remove(path);
+}
+
+/// Finds the version a mod file has been published as, by its hash.
+///
+/// Modrinth is asked first, CurseForge only when an api key is available.
+///
+/// # Errors
+///
+/// This function will return an error if a request fails for any other reason than an unknown hash.
+pub async fn identify_file(bytes: &[u8]) -> Result<Option<RemoteVersion>, PlatformApiError> {
+    if let Some(version) = ModSource::Modrinth.identify(bytes).await? {
+        return Ok(Some(version));
+    }
+    if curseforge_key().is_err() {
+        return Ok(None);
+    }
+    ModSource::Curseforge.identify(bytes).await
+}
+
+/// Downloads `file` into `dir` through the download queue, returning the path of the written file.
+///
+/// The file is only put in place once it matches the sha1 it has been published with, then `follow_up`
+/// runs on it.
+///
+/// # Errors
+///
+/// This function will return an error if `file` has no url, the request fails, the file cannot be written,
+/// doesn't match its hash, the follow-up fails or the download is cancelled.
+pub async fn download_file(
+    collection_id: Option<CollectionId>,
+    file: &RemoteFile,
+    dir: &Path,
+    follow_up: FollowUp,
+) -> Result<PathBuf, PlatformApiError> {
+    let url = file.url.as_ref().context(MissingDownloadUrlSnafu {
+        filename: &file.filename,
+    })?;
+    download_queue::download(
+        collection_id,
+        file.filename.clone(),
+        url.clone(),
+        dir.join(&file.filename),
+        file.sha1.clone(),
+        follow_up,
+    )
+    .await
+    .map_err(Into::into)
+}
@@ -0,0 +1,263 @@
+use std::path::PathBuf;
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use crate::{
+    builder::component::MEMORY_CHOICES,
+    diagnostics::DiagnosticReportButton,
+    download_progress::QueueLimits,
+    i18n::{Locale, LOCALE},
+    notifications::NotificationToggles,
+    persistence::{data_dir, default_data_dir, Persisted},
+    t,
+    theme::{current_theme, open_themes_dir, themes_dir, Color, Theme, THEMES},
+    BaseComponents::organisms::toast::toast_error,
+};
+
+/// Preferences that apply to the whole app, stored in [`default_data_dir`] so moving the data dir can't lose them
+pub static SETTINGS: GlobalSignal<AppSettings> =
+    Signal::global(|| AppSettings::load_or_create(&default_data_dir()));
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// Gigabytes of memory new collections start with
+    pub default_memory: usize,
+    /// `None` uses the `java` found on `PATH`
+    pub java_path: Option<PathBuf>,
+    /// Passed to the JVM of new collections
+    pub java_arguments: String,
+    /// `None` follows `LANG`
+    pub locale: Option<Locale>,
+    /// Id of a [`Theme`], falls back to dark when it no longer exists
+    pub theme: String,
+    /// Replaces the accent of the theme
+    pub accent: Option<Color>,
+    /// Takes effect after a restart, `None` keeps [`default_data_dir`]
+    pub data_directory: Option<PathBuf>,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            default_memory: 8,
+            java_path: None,
+            java_arguments: String::new(),
+            locale: None,
+            theme: Theme::DARK.to_owned(),
+            accent: None,
+            data_directory: None,
+        }
+    }
+}
+
+impl Persisted for AppSettings {
+    const FILE_NAME: &'static str = "settings.json";
+}
+
+impl AppSettings {
+    /// The java executable to run
+    #[must_use]
+    pub fn java(&self) -> PathBuf {
+        self.java_path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("java"))
+    }
+}
+
+/// Applies the locale of [`SETTINGS`] and saves them whenever they change, wherever they were changed from
+pub fn use_settings_sync() {
+    use_effect(move || {
+        let settings = SETTINGS.read();
+        if let Some(locale) = settings.locale {
+            if *LOCALE.peek() != locale {
+                *LOCALE.write() = locale;
+            }
+        }
+        if let Err(err) = settings.save_to(&default_data_dir()) {
+            toast_error(err);
+        }
+    });
+}
+
+#[component]
+pub fn Settings() -> Element {
+    let settings = SETTINGS.read().clone();
+    let java_path = settings
+        .java_path
+        .as_ref()
+        .map(|x| x.display().to_string())
+        .unwrap_or_default();
+    let data_directory = settings
+        .data_directory
+        .as_ref()
+        .map(|x| x.display().to_string())
+        .unwrap_or_default();
+    rsx! {
+        div {
+            class: "flex flex-col gap-[20px] p-[30px] min-h-screen",
+            div {
+                class: "text-white text-[40px] font-black trim",
+                {t!("settings-title")}
+            }
+            SettingsSection {
+                title: t!("settings-language"),
+                div {
+                    class: "flex flex-wrap gap-[10px]",
+                    for locale in Locale::iter() {
+                        Chip {
+                            selected: *LOCALE.read() == locale,
+                            onclick: move |()| SETTINGS.write().locale = Some(locale),
+                            {locale.native_name()}
+                        }
+                    }
+                }
+            }
+            SettingsSection {
+                title: t!("settings-theme"),
+                hint: t!("settings-theme-hint", folder = themes_dir().display().to_string()),
+                div {
+                    class: "flex flex-wrap gap-[10px]",
+                    for (id, title) in THEMES.read().iter().map(|x| (x.id.clone(), x.title())) {
+                        Chip {
+                            selected: settings.theme.eq_ignore_ascii_case(&id),
+                            onclick: move |()| SETTINGS.write().theme.clone_from(&id),
+                            {title}
+                        }
+                    }
+                    Chip {
+                        selected: false,
+                        onclick: move |()| *THEMES.write() = Theme::load_all(),
+                        {t!("settings-theme-reload")}
+                    }
+                    Chip {
+                        selected: false,
+                        onclick: move |()| {
+                            if let Err(err) = open_themes_dir() {
+                                toast_error(err);
+                            }
+                        },
+                        {t!("settings-theme-open-folder")}
+                    }
+                }
+                div {
+                    class: "flex flex-wrap items-center gap-[10px]",
+                    div {
+                        class: "text-white text-[16px] font-medium trim",
+                        {t!("settings-accent")}
+                    }
+                    input {
+                        class: "w-[60px] h-[40px] rounded-[15px] bg-deep-background cursor-pointer",
+                        r#type: "color",
+                        value: current_theme().colors.accent.to_string(),
+                        oninput: move |x| {
+                            if let Ok(accent) = x.value().parse() {
+                                SETTINGS.write().accent = Some(accent);
+                            }
+                        },
+                    }
+                    if settings.accent.is_some() {
+                        Chip {
+                            selected: false,
+                            onclick: move |()| SETTINGS.write().accent = None,
+                            {t!("settings-accent-reset")}
+                        }
+                    }
+                }
+            }
+            SettingsSection {
+                title: t!("settings-default-memory"),
+                hint: t!("settings-default-memory-hint"),
+                div {
+                    class: "flex flex-wrap gap-[10px]",
+                    for memory in MEMORY_CHOICES {
+                        Chip {
+                            selected: settings.default_memory == memory,
+                            onclick: move |()| SETTINGS.write().default_memory = memory,
+                            "{memory} GB"
+                        }
+                    }
+                }
+            }
+            SettingsSection {
+                title: t!("settings-java"),
+                hint: t!("settings-java-hint"),
+                input {
+                    class: "h-[50px] px-[20px] rounded-[15px] bg-deep-background text-white text-[16px]",
+                    placeholder: t!("settings-java-path"),
+                    value: java_path,
+                    oninput: move |x| {
+                        let value = x.value();
+                        SETTINGS.write().java_path = (!value.trim().is_empty()).then(|| PathBuf::from(value));
+                    },
+                }
+                input {
+                    class: "h-[50px] px-[20px] rounded-[15px] bg-deep-background text-white text-[16px]",
+                    placeholder: t!("settings-java-arguments"),
+                    value: settings.java_arguments.clone(),
+                    oninput: move |x| SETTINGS.write().java_arguments = x.value(),
+                }
+            }
+            SettingsSection {
+                title: t!("settings-downloads"),
+                QueueLimits {}
+            }
+            SettingsSection {
+                title: t!("settings-notifications"),
+                NotificationToggles {}
+            }
+            SettingsSection {
+                title: t!("settings-data-directory"),
+                hint: t!("settings-data-directory-hint", current = data_dir().display().to_string()),
+                input {
+                    class: "h-[50px] px-[20px] rounded-[15px] bg-deep-background text-white text-[16px]",
+                    placeholder: default_data_dir().display().to_string(),
+                    value: data_directory,
+                    oninput: move |x| {
+                        let value = x.value();
+                        SETTINGS.write().data_directory = (!value.trim().is_empty()).then(|| PathBuf::from(value));
+                    },
+                }
+            }
+            SettingsSection {
+                title: t!("diagnostic-title"),
+                hint: t!("settings-diagnostics-hint"),
+                DiagnosticReportButton {}
+            }
+        }
+    }
+}
+
+#[component]
+fn SettingsSection(title: String, hint: Option<String>, children: Element) -> Element {
+    rsx! {
+        div {
+            class: "flex flex-col gap-[15px] p-[30px] rounded-[20px] bg-background",
+            div {
+                class: "text-white text-[20px] font-bold trim",
+                {title}
+            }
+            if let Some(hint) = hint {
+                div {
+                    class: "text-hint text-[16px] font-medium",
+                    {hint}
+                }
+            }
+            {children}
+        }
+    }
+}
+
+#[component]
+fn Chip(selected: bool, onclick: EventHandler, children: Element) -> Element {
+    rsx! {
+        button {
+            class: "h-[40px] px-[15px] rounded-[15px] bg-deep-background text-white text-[14px] font-medium aria-selected:bg-white aria-selected:text-black trim",
+            aria_selected: selected,
+            onclick: move |_| onclick.call(()),
+            {children}
+        }
+    }
+}
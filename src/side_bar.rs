@@ -4,7 +4,9 @@ use dioxus::prelude::*;
 use rust_lib::api::shared_resources::collection::{use_collections_radio, CollectionId};
 
 use crate::{
-    svgs,
+    import::{use_import_drop, ImportConfirmation},
+    notifications::NotificationBell,
+    svgs, t,
     text_scroller::use_text_scroller,
     BaseComponents::{
         atoms::button::{Button, Roundness},
@@ -49,7 +51,7 @@ pub fn SideBar() -> Element {
                 class: "flex justify-self-end group-aria-busy:hidden",
                 div {
                     class: "text-lime-300 trim",
-                    "我的錦集"
+                    {t!("my-collections")}
                 }
             }
         }
@@ -105,7 +107,7 @@ pub fn SideBar() -> Element {
                             roundness: Roundness::Squircle,
                             string_placements: vec![
                                 ContentType::svg(HOME).align_left(),
-                                ContentType::text("首頁")
+                                ContentType::text(t!("sidebar-home"))
                                     .css("text-black group-data-[main-page=false]/main:hidden")
                                     .align_right(),
                             ],
@@ -140,7 +142,7 @@ pub fn SideBar() -> Element {
                                 roundness: Roundness::Squircle,
                                 string_placements: vec![
                                     ContentType::svg(EXPLORE).align_left(),
-                                    ContentType::text("探索")
+                                    ContentType::text(t!("sidebar-explore"))
                                         .css("text-black group-data-[explore=false]/main:hidden")
                                         .align_right(),
                                 ],
@@ -171,7 +173,7 @@ pub fn SideBar() -> Element {
                             roundness: Roundness::Squircle,
                             string_placements: vec![
                                 ContentType::svg(SIDEBAR_COLLECTION).align_left(),
-                                ContentType::text("收藏庫")
+                                ContentType::text(t!("sidebar-library"))
                                     .css("text-black group-data-[collections=false]/main:hidden")
                                     .align_right(),
                             ],
@@ -185,10 +187,10 @@ pub fn SideBar() -> Element {
                             extended_css_class: "z-10 bg-background text-black absolute -right-[300px] transition-all [&_*]:transition-all
                                 min-w-[100px] max-w-[100px]
 
-                                hover:bg-green
+                                hover:bg-accent
                                 [&:not(:hover)]:justify-center
 
-                                group-aria-[selected=collections]/main:bg-green 
+                                group-aria-[selected=collections]/main:bg-accent 
                                 hover:min-w-[300px] 
                                 hover:max-w-[300px] 
                                 [&:not(:hover)]:group-hover/main:group-data-[main-page=true]/main:-right-[400px] 
@@ -200,6 +202,16 @@ pub fn SideBar() -> Element {
                 // middle
                 div {
                     class: "relative flex flex-col flex-nowrap overflow-y-scroll space-y-1",
+                    NotificationBell {}
+                    Button {
+                        roundness: Roundness::Squircle,
+                        string_placements: vec![
+                            ContentType::text(t!("logs-title")).align_left(),
+                        ],
+                        switcher: Pages::Logs,
+                        focus_color_change: true,
+                        extended_css_class: "bg-background",
+                    }
                     Button {
                         roundness: Roundness::Squircle,
                         string_placements: folded_images,
@@ -257,13 +269,13 @@ pub fn SideBar() -> Element {
                                                 }
                                                 div {
                                                     class: "text-hint text-[12px] font-medium trim",
-                                                    "模組 - 安裝中"
+                                                    {t!("sidebar-installing-mods")}
                                                 }
                                             }
                                             div {
                                                 class: "flex items-end gap-[3px]",
                                                 div {
-                                                    class: "text-green text-[20px] font-bold font-english trim",
+                                                    class: "text-accent text-[20px] font-bold font-english trim",
                                                     "25"
                                                 }
                                                 div {
@@ -295,7 +307,7 @@ pub fn SideBar() -> Element {
                                 div {
                                     class: "flex flex-row-reverse text-[25px] font-bold text-white",
                                     class: if !delayed() { "hidden" },
-                                    "下載"
+                                    {t!("sidebar-downloads")}
                                 }
                             }
                         }
@@ -328,7 +340,7 @@ pub fn SideBar() -> Element {
                                 if onmiddle() {
                                     div {
                                         class: "flex flex-row-reverse text-[25px] font-bold text-white",
-                                        "指南"
+                                        {t!("sidebar-guide")}
                                     }
                                 }
                             }
@@ -338,6 +350,7 @@ pub fn SideBar() -> Element {
                         }
                         button {
                             class: "flex justify-center items-center rounded bg-background px-[20px]",
+                            onclick: move |_| Pages::Settings.switch_active_to_self(),
                             onmouseover: move |_| {
                                 onright.set(true);
                             },
@@ -362,7 +375,7 @@ pub fn SideBar() -> Element {
                                 if onright() {
                                     div {
                                         class: "flex flex-row-reverse text-[25px] font-bold text-white",
-                                        "設定"
+                                        {t!("sidebar-settings")}
                                     }
                                 }
                             }
@@ -386,7 +399,7 @@ fn SidebarCollectionBlock(collection_id: ReadOnlySignal<CollectionId>) -> Elemen
     let display_name = radio.read().display_name().clone();
     let img_block = rsx! {
         div {
-            class: "relative transition-all container w-[50px] h-[50px] group-aria-expanded:w-20 group-aria-expanded:h-20 border-2 border-[#2E2E2E] rounded-[15px] group-aria-expanded:rounded-[5px]",
+            class: "relative transition-all container w-[50px] h-[50px] group-aria-expanded:w-20 group-aria-expanded:h-20 border-2 border-secondary-surface rounded-[15px] group-aria-expanded:rounded-[5px]",
             {
                 ContentType::image(&picture_path)
                     .css(
@@ -394,17 +407,25 @@ fn SidebarCollectionBlock(collection_id: ReadOnlySignal<CollectionId>) -> Elemen
                     )
             }
             div {
-                class: "absolute inset-x-0 bottom-0 w-3 h-3 bg-[#CCE246] rounded-full",
+                class: "absolute inset-x-0 bottom-0 w-3 h-3 bg-accent rounded-full",
             }
         }
     };
 
     let (element, status, style) = use_text_scroller();
 
+    let import = use_import_drop();
+
     rsx! {
+        ImportConfirmation {
+            collection_id,
+            import,
+        }
         div {
             class: "group",
             aria_selected: status(),
+            ondragover: move |e| e.prevent_default(),
+            ondrop: move |e| import.ondrop(&e),
             Button {
                 roundness: Roundness::Squircle,
                 string_placements: vec![
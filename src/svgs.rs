@@ -40,7 +40,7 @@ pub static GRASS: Component = |()| {
 };
 
 pub static SHADOW_ADD: Component = |()| {
-    rsx! { svg { height: "32", fill: "none", "viewBox": "0 0 32 32", width: "32", xmlns: "http://www.w3.org/2000/svg", path { fill: "#191919", d: "M25.8056 30.9446C25.365 30.9446 24.9843 30.7846 24.6635 30.4646C24.3426 30.1446 24.1822 29.7629 24.1822 29.3196V26.4446H21.3001C20.8562 26.4446 20.4739 26.2846 20.1531 25.9646C19.8322 25.6446 19.6718 25.2629 19.6718 24.8196C19.6718 24.3765 19.8322 23.995 20.1531 23.675C20.4739 23.3547 20.8562 23.1946 21.3001 23.1946H24.1822V20.3196C24.1822 19.8765 24.3426 19.495 24.6635 19.175C24.9843 18.8547 25.3653 18.6946 25.8064 18.6946C26.2478 18.6946 26.6279 18.8547 26.9468 19.175C27.2657 19.495 27.4251 19.8765 27.4251 20.3196V23.1946H30.2872C30.7283 23.1946 31.1111 23.3539 31.4356 23.6725C31.7597 23.9908 31.9218 24.3703 31.9218 24.8108C31.9218 25.2517 31.7618 25.6321 31.4418 25.9521C31.1215 26.2721 30.7399 26.4321 30.2968 26.4321H27.4218V29.3071C27.4218 29.7504 27.2626 30.1342 26.9443 30.4583C26.6257 30.7825 26.2461 30.9446 25.8056 30.9446ZM15.4726 31.8525C13.411 31.8525 11.4569 31.4381 9.61055 30.6092C7.76416 29.7806 6.13847 28.6513 4.73347 27.2213C3.32875 25.7913 2.20166 24.1124 1.35222 22.1846C0.502498 20.2568 0.0776367 18.1911 0.0776367 15.9875C0.0776367 13.7839 0.49222 11.7243 1.32139 9.80876C2.15055 7.89348 3.29194 6.20751 4.74555 4.75084C6.19916 3.29446 7.89014 2.15154 9.81847 1.32209C11.7468 0.492648 13.8072 0.0779266 15.9997 0.0779266C18.0508 0.0779266 19.9699 0.432648 21.7568 1.14209C23.5437 1.85154 25.1476 2.83432 26.5685 4.09043C27.9979 5.34126 29.1632 6.81751 30.0643 8.51918C30.9657 10.2208 31.5401 12.075 31.7876 14.0817C31.8565 14.3508 31.8339 14.6063 31.7197 14.8479C31.6056 15.0893 31.4278 15.2756 31.1864 15.4067C30.9447 15.5378 30.6894 15.6136 30.4206 15.6342C30.1514 15.6547 29.8825 15.5995 29.6139 15.4683C29.0131 15.2114 28.3886 15.0182 27.7406 14.8888C27.0925 14.7593 26.4304 14.6946 25.7543 14.6946C22.9407 14.6946 20.5619 15.6711 18.6181 17.6242C16.6744 19.5772 15.7026 21.9585 15.7026 24.7679C15.7026 25.5682 15.8001 26.3536 15.9951 27.1242C16.1901 27.8947 16.4857 28.6308 16.8818 29.3325C16.9651 29.4503 17.0189 29.5758 17.0431 29.7092C17.0672 29.8428 17.0793 29.9838 17.0793 30.1321C17.0793 30.5971 16.9361 31 16.6497 31.3408C16.3633 31.682 15.971 31.8525 15.4726 31.8525ZM3.31764 15.9842C3.31764 18.8556 4.1843 21.419 5.91764 23.6746C7.65097 25.9302 9.88569 27.4095 12.6218 28.1125C11.2185 26.3678 10.089 24.4785 9.23347 22.4446C8.37764 20.4107 7.94972 18.2617 7.94972 15.9975C7.94972 13.4461 8.51264 11.0995 9.63847 8.95751C10.764 6.81557 12.2537 5.02265 14.1076 3.57876C11.0485 3.99682 8.48528 5.3814 6.41805 7.73251C4.35111 10.0836 3.31764 12.8342 3.31764 15.9842Z", } } }
+    rsx! { svg { height: "32", fill: "none", "viewBox": "0 0 32 32", width: "32", xmlns: "http://www.w3.org/2000/svg", path { style: "fill: rgb(var(--color-background))", d: "M25.8056 30.9446C25.365 30.9446 24.9843 30.7846 24.6635 30.4646C24.3426 30.1446 24.1822 29.7629 24.1822 29.3196V26.4446H21.3001C20.8562 26.4446 20.4739 26.2846 20.1531 25.9646C19.8322 25.6446 19.6718 25.2629 19.6718 24.8196C19.6718 24.3765 19.8322 23.995 20.1531 23.675C20.4739 23.3547 20.8562 23.1946 21.3001 23.1946H24.1822V20.3196C24.1822 19.8765 24.3426 19.495 24.6635 19.175C24.9843 18.8547 25.3653 18.6946 25.8064 18.6946C26.2478 18.6946 26.6279 18.8547 26.9468 19.175C27.2657 19.495 27.4251 19.8765 27.4251 20.3196V23.1946H30.2872C30.7283 23.1946 31.1111 23.3539 31.4356 23.6725C31.7597 23.9908 31.9218 24.3703 31.9218 24.8108C31.9218 25.2517 31.7618 25.6321 31.4418 25.9521C31.1215 26.2721 30.7399 26.4321 30.2968 26.4321H27.4218V29.3071C27.4218 29.7504 27.2626 30.1342 26.9443 30.4583C26.6257 30.7825 26.2461 30.9446 25.8056 30.9446ZM15.4726 31.8525C13.411 31.8525 11.4569 31.4381 9.61055 30.6092C7.76416 29.7806 6.13847 28.6513 4.73347 27.2213C3.32875 25.7913 2.20166 24.1124 1.35222 22.1846C0.502498 20.2568 0.0776367 18.1911 0.0776367 15.9875C0.0776367 13.7839 0.49222 11.7243 1.32139 9.80876C2.15055 7.89348 3.29194 6.20751 4.74555 4.75084C6.19916 3.29446 7.89014 2.15154 9.81847 1.32209C11.7468 0.492648 13.8072 0.0779266 15.9997 0.0779266C18.0508 0.0779266 19.9699 0.432648 21.7568 1.14209C23.5437 1.85154 25.1476 2.83432 26.5685 4.09043C27.9979 5.34126 29.1632 6.81751 30.0643 8.51918C30.9657 10.2208 31.5401 12.075 31.7876 14.0817C31.8565 14.3508 31.8339 14.6063 31.7197 14.8479C31.6056 15.0893 31.4278 15.2756 31.1864 15.4067C30.9447 15.5378 30.6894 15.6136 30.4206 15.6342C30.1514 15.6547 29.8825 15.5995 29.6139 15.4683C29.0131 15.2114 28.3886 15.0182 27.7406 14.8888C27.0925 14.7593 26.4304 14.6946 25.7543 14.6946C22.9407 14.6946 20.5619 15.6711 18.6181 17.6242C16.6744 19.5772 15.7026 21.9585 15.7026 24.7679C15.7026 25.5682 15.8001 26.3536 15.9951 27.1242C16.1901 27.8947 16.4857 28.6308 16.8818 29.3325C16.9651 29.4503 17.0189 29.5758 17.0431 29.7092C17.0672 29.8428 17.0793 29.9838 17.0793 30.1321C17.0793 30.5971 16.9361 31 16.6497 31.3408C16.3633 31.682 15.971 31.8525 15.4726 31.8525ZM3.31764 15.9842C3.31764 18.8556 4.1843 21.419 5.91764 23.6746C7.65097 25.9302 9.88569 27.4095 12.6218 28.1125C11.2185 26.3678 10.089 24.4785 9.23347 22.4446C8.37764 20.4107 7.94972 18.2617 7.94972 15.9975C7.94972 13.4461 8.51264 11.0995 9.63847 8.95751C10.764 6.81557 12.2537 5.02265 14.1076 3.57876C11.0485 3.99682 8.48528 5.3814 6.41805 7.73251C4.35111 10.0836 3.31764 12.8342 3.31764 15.9842Z", } } }
 };
 
 pub static FOLDER_UPLOAD: Component = |()| {
@@ -78,7 +78,7 @@ pub fn LINE(
     #[props(extends = GlobalAttributes, extends = svg)] mut attributes: Vec<Attribute>,
 ) -> Element {
     rsx! {
-         svg { xmlns: "http://www.w3.org/2000/svg", width: "477", "viewBox": "0 0 477 4", height: "4", fill: "none", ..attributes, path { stroke: "#2F2F2F", "stroke-width": "3", d: "M2 2L475 2", "stroke-linecap": "round", "stroke-dasharray": "10 10", } }
+         svg { xmlns: "http://www.w3.org/2000/svg", width: "477", "viewBox": "0 0 477 4", height: "4", fill: "none", ..attributes, path { style: "stroke: rgb(var(--color-secondary-surface))", "stroke-width": "3", d: "M2 2L475 2", "stroke-linecap": "round", "stroke-dasharray": "10 10", } }
     }
 }
 
@@ -0,0 +1,35 @@
+//! Helpers shared by tests that touch global signals
+
+use std::{
+    future::{poll_fn, Future},
+    pin::pin,
+    sync::Once,
+};
+
+use dioxus::prelude::*;
+
+fn app() -> Element {
+    rsx! {}
+}
+
+/// Runs `future` on a current thread runtime inside a fresh dom, so every global signal it touches
+/// starts out from its initial value.
+///
+/// The data dir is moved into the temp dir first, so nothing the app persists is overwritten.
+pub fn block_on_in_dom<F: Future>(future: F) -> F::Output {
+    static DATA_DIR: Once = Once::new();
+    DATA_DIR.call_once(|| {
+        let dir = std::env::temp_dir().join(format!("prag-portal-data-{}", std::process::id()));
+        std::env::set_var("XDG_DATA_HOME", dir);
+    });
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let mut future = pin!(future);
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to build runtime")
+        .block_on(poll_fn(|cx| {
+            dom.in_runtime(|| ScopeId::ROOT.in_runtime(|| future.as_mut().poll(cx)))
+        }))
+}
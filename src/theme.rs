@@ -0,0 +1,330 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use dioxus::prelude::*;
+use dioxus_logger::tracing::warn;
+use serde::{Deserialize, Serialize};
+use snafu::prelude::*;
+
+use crate::{persistence::data_dir, settings::SETTINGS, t};
+
+/// Every theme that can be picked, the built-in ones first, reloaded from the settings page
+pub static THEMES: GlobalSignal<Vec<Theme>> = Signal::global(Theme::load_all);
+
+#[derive(Snafu, Debug, Clone, PartialEq, Eq)]
+#[snafu(display("{value} is not a colour, expected #RRGGBB"))]
+pub struct ParseColorError {
+    value: String,
+}
+
+/// An opaque colour, written as `#RRGGBB`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    #[must_use]
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Space separated channels, so the variable works in `rgb(var(--x) / <alpha>)`
+    #[must_use]
+    pub fn channels(self) -> String {
+        format!("{} {} {}", self.r, self.g, self.b)
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.trim().trim_start_matches('#');
+        let channel = |i: usize| {
+            hex.get(i..i + 2)
+                .and_then(|x| u8::from_str_radix(x, 16).ok())
+        };
+        match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(Self::new(r, g, b)),
+            _ => ParseColorSnafu { value: s }.fail(),
+        }
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = ParseColorError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Color> for String {
+    fn from(value: Color) -> Self {
+        value.to_string()
+    }
+}
+
+/// The colours behind the tailwind palette, named after their tailwind class.
+///
+/// `white` and `black` are the foreground and the text on top of it, so they swap in a light theme.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Palette {
+    pub white: Color,
+    pub black: Color,
+    pub surface: Color,
+    pub secondary_surface: Color,
+    pub hint: Color,
+    pub secondary: Color,
+    pub secondary_text: Color,
+    pub deep_background: Color,
+    pub background: Color,
+    /// Highlights the current selection and call to actions
+    pub accent: Color,
+    pub green: Color,
+    pub red: Color,
+    pub light_blue: Color,
+    pub tinted_yellow: Color,
+    pub purple: Color,
+    pub orange: Color,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Palette {
+    #[must_use]
+    pub const fn dark() -> Self {
+        Self {
+            white: Color::new(0xFF, 0xFF, 0xFF),
+            black: Color::new(0x23, 0x2E, 0x3D),
+            surface: Color::new(0x1E, 0x1E, 0x1E),
+            secondary_surface: Color::new(0x2F, 0x2F, 0x2F),
+            hint: Color::new(0x4F, 0x4F, 0x4F),
+            secondary: Color::new(0x7D, 0x7D, 0x7D),
+            secondary_text: Color::new(0x7D, 0x7D, 0x7D),
+            deep_background: Color::new(0x0E, 0x0E, 0x0E),
+            background: Color::new(0x19, 0x19, 0x19),
+            accent: Color::new(0xCD, 0xE3, 0x47),
+            green: Color::new(0xCD, 0xE3, 0x47),
+            red: Color::new(0xC9, 0x2B, 0x45),
+            light_blue: Color::new(0x7C, 0xAE, 0xD3),
+            tinted_yellow: Color::new(0xD3, 0xE9, 0x50),
+            purple: Color::new(0x97, 0x47, 0xFF),
+            orange: Color::new(0xF1, 0x64, 0x36),
+        }
+    }
+
+    #[must_use]
+    pub const fn light() -> Self {
+        Self {
+            white: Color::new(0x19, 0x19, 0x19),
+            black: Color::new(0xF7, 0xF7, 0xF7),
+            surface: Color::new(0xFF, 0xFF, 0xFF),
+            secondary_surface: Color::new(0xE0, 0xE0, 0xE0),
+            hint: Color::new(0xA3, 0xA3, 0xA3),
+            secondary: Color::new(0x6B, 0x6B, 0x6B),
+            secondary_text: Color::new(0x6B, 0x6B, 0x6B),
+            deep_background: Color::new(0xE9, 0xE9, 0xE9),
+            background: Color::new(0xF7, 0xF7, 0xF7),
+            accent: Color::new(0x8A, 0x9E, 0x1A),
+            green: Color::new(0x7E, 0x98, 0x12),
+            red: Color::new(0xC9, 0x2B, 0x45),
+            light_blue: Color::new(0x3C, 0x7F, 0xB0),
+            tinted_yellow: Color::new(0xA6, 0xBC, 0x1E),
+            purple: Color::new(0x7A, 0x2F, 0xE0),
+            orange: Color::new(0xE0, 0x52, 0x1F),
+        }
+    }
+
+    fn variables(&self) -> [(&'static str, Color); 16] {
+        [
+            ("white", self.white),
+            ("black", self.black),
+            ("surface", self.surface),
+            ("secondary-surface", self.secondary_surface),
+            ("hint", self.hint),
+            ("secondary", self.secondary),
+            ("secondary-text", self.secondary_text),
+            ("deep-background", self.deep_background),
+            ("background", self.background),
+            ("accent", self.accent),
+            ("green", self.green),
+            ("red", self.red),
+            ("light-blue", self.light_blue),
+            ("tinted-yellow", self.tinted_yellow),
+            ("purple", self.purple),
+            ("orange", self.orange),
+        ]
+    }
+}
+
+/// Whether native widgets, e.g. scrollbars, should be drawn light or dark
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorScheme {
+    #[default]
+    Dark,
+    Light,
+}
+
+/// A palette with a name, either built in or a json file in [`themes_dir`].
+///
+/// Colours missing from a file fall back to the dark theme, so a file only needs the ones it changes:
+/// `{ "name": "Midnight", "colors": { "accent": "#7CAED3", "background": "#101828" } }`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Theme {
+    /// Stored in the settings, the file stem for user themes
+    #[serde(skip)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub color_scheme: ColorScheme,
+    #[serde(default)]
+    pub colors: Palette,
+}
+
+impl Theme {
+    pub const DARK: &'static str = "dark";
+    pub const LIGHT: &'static str = "light";
+
+    #[must_use]
+    pub fn dark() -> Self {
+        Self {
+            id: Self::DARK.to_owned(),
+            name: String::from("Dark"),
+            color_scheme: ColorScheme::Dark,
+            colors: Palette::dark(),
+        }
+    }
+
+    #[must_use]
+    pub fn light() -> Self {
+        Self {
+            id: Self::LIGHT.to_owned(),
+            name: String::from("Light"),
+            color_scheme: ColorScheme::Light,
+            colors: Palette::light(),
+        }
+    }
+
+    /// The built-in themes followed by the ones in [`themes_dir`], unreadable files are skipped
+    #[must_use]
+    pub fn load_all() -> Vec<Self> {
+        let mut themes = vec![Self::dark(), Self::light()];
+        let Ok(entries) = std::fs::read_dir(themes_dir()) else {
+            return themes;
+        };
+        let mut paths = entries
+            .flatten()
+            .map(|x| x.path())
+            .filter(|x| x.extension().is_some_and(|x| x == "json"))
+            .collect::<Vec<_>>();
+        paths.sort();
+        for path in paths {
+            let Some(id) = path.file_stem().map(|x| x.to_string_lossy().to_string()) else {
+                continue;
+            };
+            if themes.iter().any(|x| x.id == id) {
+                warn!("{} shadows a built-in theme, skipping", path.display());
+                continue;
+            }
+            match read_theme(&path) {
+                Ok(theme) => themes.push(Self { id, ..theme }),
+                Err(err) => warn!("Failed to load theme {}: {err}", path.display()),
+            }
+        }
+        themes
+    }
+
+    #[must_use]
+    pub fn title(&self) -> String {
+        match self.id.as_str() {
+            Self::DARK => t!("settings-theme-dark"),
+            Self::LIGHT => t!("settings-theme-light"),
+            _ => self.name.clone(),
+        }
+    }
+
+    /// Declares the palette as `--color-*` variables on the root element
+    #[must_use]
+    pub fn css(&self) -> String {
+        let scheme = match self.color_scheme {
+            ColorScheme::Dark => "dark",
+            ColorScheme::Light => "light",
+        };
+        let mut css = format!(":root {{ color-scheme: {scheme};");
+        for (name, color) in self.colors.variables() {
+            css.push_str(&format!(" --color-{name}: {};", color.channels()));
+        }
+        css.push_str(" }");
+        css
+    }
+}
+
+fn read_theme(path: &Path) -> anyhow::Result<Theme> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Where user themes are read from, e.g. `~/.local/share/prag-portal/themes`
+#[must_use]
+pub fn themes_dir() -> PathBuf {
+    data_dir().join("themes")
+}
+
+/// Opens [`themes_dir`] in the file manager, creating it first so there's somewhere to put themes
+///
+/// # Errors
+///
+/// This function will return an error if the directory cannot be created or opened.
+pub fn open_themes_dir() -> anyhow::Result<()> {
+    let dir = themes_dir();
+    std::fs::create_dir_all(&dir)?;
+    opener::open(&dir)?;
+    Ok(())
+}
+
+/// The theme picked in the settings with its accent override applied, dark if the theme is gone
+#[must_use]
+pub fn current_theme() -> Theme {
+    let settings = SETTINGS.read();
+    let mut theme = THEMES
+        .read()
+        .iter()
+        .find(|x| x.id.eq_ignore_ascii_case(&settings.theme))
+        .cloned()
+        .unwrap_or_else(Theme::dark);
+    if let Some(accent) = settings.accent {
+        theme.colors.accent = accent;
+    }
+    theme
+}
+
+/// Applies [`current_theme`] to the whole window
+#[component]
+pub fn ThemeStyle() -> Element {
+    let css = current_theme().css();
+    rsx! {
+        document::Style {
+            {css}
+        }
+    }
+}
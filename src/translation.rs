@@ -0,0 +1,588 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+    sync::LazyLock,
+};
+
+use dioxus::prelude::*;
+use futures_util::future::LocalBoxFuture;
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use snafu::prelude::*;
+
+use crate::persistence::{cache_dir, data_dir, Persisted, PersistenceError};
+
+pub static TRANSLATION_CONFIG: GlobalSignal<TranslationConfig> =
+    Signal::global(|| TranslationConfig::load_or_create(&data_dir()));
+
+#[derive(Snafu, Debug)]
+pub enum TranslationError {
+    #[snafu(display("Failed to request {url}"))]
+    Request { url: String, source: reqwest::Error },
+    #[snafu(display("Expected {expected} translations, got {actual}"))]
+    LengthMismatch { expected: usize, actual: usize },
+    #[snafu(display("Failed to save translation cache"))]
+    Cache { source: PersistenceError },
+}
+
+/// A machine translation service
+pub trait Translator {
+    /// Distinguishes the caches of different backends
+    fn id(&self) -> String;
+
+    /// Translates every text into `target`, the output has to line up with `texts`
+    fn translate<'a>(
+        &'a self,
+        texts: &'a [String],
+        target: &'a str,
+    ) -> LocalBoxFuture<'a, Result<Vec<String>, TranslationError>>;
+}
+
+/// A [LibreTranslate](https://libretranslate.com) instance
+pub struct LibreTranslate {
+    pub url: String,
+    pub api_key: Option<String>,
+}
+
+#[derive(Serialize)]
+struct LibreTranslateRequest<'a> {
+    q: &'a [String],
+    source: &'a str,
+    target: &'a str,
+    format: &'a str,
+    api_key: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LibreTranslateResponse {
+    translated_text: Vec<String>,
+}
+
+impl Translator for LibreTranslate {
+    fn id(&self) -> String {
+        format!("libretranslate-{}", self.url.replace(['/', ':'], "_"))
+    }
+
+    fn translate<'a>(
+        &'a self,
+        texts: &'a [String],
+        target: &'a str,
+    ) -> LocalBoxFuture<'a, Result<Vec<String>, TranslationError>> {
+        Box::pin(async move {
+            let url = format!("{}/translate", self.url.trim_end_matches('/'));
+            let response: LibreTranslateResponse = reqwest::Client::new()
+                .post(&url)
+                .json(&LibreTranslateRequest {
+                    q: texts,
+                    source: "auto",
+                    target,
+                    format: "text",
+                    api_key: self.api_key.as_deref(),
+                })
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .context(RequestSnafu { url: &url })?
+                .json()
+                .await
+                .context(RequestSnafu { url })?;
+            ensure!(
+                response.translated_text.len() == texts.len(),
+                LengthMismatchSnafu {
+                    expected: texts.len(),
+                    actual: response.translated_text.len(),
+                }
+            );
+            Ok(response.translated_text)
+        })
+    }
+}
+
+/// Translates nothing, it only tags the text with the target language.
+///
+/// Useful for working on the layout offline.
+#[derive(Default)]
+pub struct StubTranslator;
+
+impl Translator for StubTranslator {
+    fn id(&self) -> String {
+        String::from("stub")
+    }
+
+    fn translate<'a>(
+        &'a self,
+        texts: &'a [String],
+        target: &'a str,
+    ) -> LocalBoxFuture<'a, Result<Vec<String>, TranslationError>> {
+        Box::pin(async move { Ok(texts.iter().map(|x| format!("[{target}] {x}")).collect()) })
+    }
+}
+
+/// Translation stays off until a backend is set in `translation.json`, e.g.
+/// `{ "type": "LibreTranslate", "url": "https://libretranslate.com", "api_key": "..." }`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum TranslationBackend {
+    #[default]
+    Disabled,
+    LibreTranslate {
+        url: String,
+        api_key: Option<String>,
+    },
+    Stub,
+}
+
+impl TranslationBackend {
+    /// `None` when translating is turned off
+    #[must_use]
+    pub fn translator(&self) -> Option<Box<dyn Translator>> {
+        match self {
+            Self::Disabled => None,
+            // the public instance refuses requests without a key, self-hosted ones don't need one
+            Self::LibreTranslate { url, api_key: None } if url.contains("libretranslate.com") => {
+                None
+            }
+            Self::LibreTranslate { url, api_key } => Some(Box::new(LibreTranslate {
+                url: url.clone(),
+                api_key: api_key.clone(),
+            })),
+            Self::Stub => Some(Box::new(StubTranslator)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TranslationConfig {
+    pub backend: TranslationBackend,
+    /// Language code understood by the backend, `zt` is Traditional Chinese on LibreTranslate
+    pub target_language: String,
+}
+
+impl Default for TranslationConfig {
+    fn default() -> Self {
+        Self {
+            backend: TranslationBackend::Disabled,
+            target_language: String::from("zt"),
+        }
+    }
+}
+
+impl Persisted for TranslationConfig {
+    const FILE_NAME: &'static str = "translation.json";
+}
+
+/// Previously translated texts of one backend and target language
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct TranslationCache(HashMap<String, String>);
+
+impl Persisted for TranslationCache {
+    const FILE_NAME: &'static str = "translations.json";
+}
+
+static HTML_TEXT: LazyLock<Regex> = LazyLock::new(|| {
+    #[allow(clippy::unwrap_used)]
+    Regex::new(r"(<(/?)([a-zA-Z0-9]+)[^>]*>)|([^<]+)").unwrap()
+});
+
+/// Html tags whose content is left untouched
+const UNTOUCHED_HTML: [&str; 5] = ["a", "code", "pre", "script", "style"];
+
+/// Calls `f` on every text node of an html fragment outside of [`UNTOUCHED_HTML`].
+///
+/// `depth` is how many untouched tags are open, it carries over between fragments of the same document.
+fn map_html_text(
+    html: &str,
+    depth: &mut usize,
+    f: &mut impl FnMut(&str) -> Option<String>,
+) -> String {
+    HTML_TEXT
+        .captures_iter(html)
+        .map(|x| {
+            if let (Some(tag), Some(name)) = (x.get(1), x.get(3)) {
+                if UNTOUCHED_HTML.contains(&name.as_str().to_lowercase().as_str()) {
+                    if x.get(2).is_some_and(|x| !x.is_empty()) {
+                        *depth = depth.saturating_sub(1);
+                    } else {
+                        *depth += 1;
+                    }
+                }
+                return tag.as_str().to_owned();
+            }
+            let text = x.get(4).map_or("", |x| x.as_str());
+            if *depth > 0 || text.trim().is_empty() {
+                return text.to_owned();
+            }
+            f(text).unwrap_or_else(|| text.to_owned())
+        })
+        .collect()
+}
+
+fn flush_prose<'a>(
+    pending: &mut String,
+    events: &mut Vec<Event<'a>>,
+    f: &mut impl FnMut(&str) -> Option<String>,
+) {
+    if pending.is_empty() {
+        return;
+    }
+    let text = std::mem::take(pending);
+    let mapped = if text.trim().is_empty() {
+        None
+    } else {
+        f(&text)
+    };
+    events.push(Event::Text(mapped.unwrap_or(text).into()));
+}
+
+/// Parses `markdown`, replacing its prose with whatever `f` returns for it.
+///
+/// Code, links and html tags are never passed to `f`. Consecutive lines of a paragraph are passed as one text.
+pub fn map_prose<'a>(
+    markdown: &'a str,
+    mut f: impl FnMut(&str) -> Option<String>,
+) -> Vec<Event<'a>> {
+    let mut events = Vec::new();
+    let mut pending = String::new();
+    let mut untouched_depth = 0usize;
+    let mut html_depth = 0usize;
+    for event in Parser::new(markdown) {
+        match event {
+            // text inside untouched html tags comes in between their inline html events
+            Event::Text(text) if untouched_depth == 0 && html_depth == 0 => pending.push_str(&text),
+            Event::SoftBreak if untouched_depth == 0 && html_depth == 0 && !pending.is_empty() => {
+                pending.push(' ');
+            }
+            Event::Html(html) | Event::InlineHtml(html) if untouched_depth == 0 => {
+                flush_prose(&mut pending, &mut events, &mut f);
+                let mapped = map_html_text(&html, &mut html_depth, &mut f);
+                events.push(Event::Html(mapped.into()));
+            }
+            event => {
+                flush_prose(&mut pending, &mut events, &mut f);
+                match &event {
+                    Event::Start(Tag::CodeBlock(_) | Tag::Link { .. } | Tag::Image { .. }) => {
+                        untouched_depth += 1;
+                    }
+                    Event::End(TagEnd::CodeBlock | TagEnd::Link | TagEnd::Image) => {
+                        untouched_depth = untouched_depth.saturating_sub(1);
+                    }
+                    _ => {}
+                }
+                events.push(event);
+            }
+        }
+    }
+    flush_prose(&mut pending, &mut events, &mut f);
+    events
+}
+
+/// Translates the prose of `markdown`, returning the translation of every text [`map_prose`] yields.
+///
+/// # Errors
+///
+/// This function will return an error if the backend fails or the cache cannot be written.
+pub async fn translate_markdown(
+    markdown: &str,
+    translator: &dyn Translator,
+    target: &str,
+) -> Result<HashMap<String, String>, TranslationError> {
+    let dir = cache_dir()
+        .join("translations")
+        .join(format!("{}-{target}", translator.id()));
+    translate_markdown_cached(markdown, translator, target, &dir).await
+}
+
+async fn translate_markdown_cached(
+    markdown: &str,
+    translator: &dyn Translator,
+    target: &str,
+    dir: &Path,
+) -> Result<HashMap<String, String>, TranslationError> {
+    let mut texts = BTreeSet::new();
+    map_prose(markdown, |x| {
+        texts.insert(x.to_owned());
+        None
+    });
+
+    let mut cache = TranslationCache::load_from(dir);
+    let missing = texts
+        .iter()
+        .filter(|x| !cache.0.contains_key(*x))
+        .cloned()
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        let translated = translator.translate(&missing, target).await?;
+        cache.0.extend(missing.into_iter().zip(translated));
+        cache.save_to(dir).context(CacheSnafu)?;
+    }
+
+    Ok(texts
+        .into_iter()
+        .filter_map(|x| cache.0.get(&x).cloned().map(|translated| (x, translated)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        future::Future,
+        path::PathBuf,
+        sync::{Arc, Mutex},
+    };
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    /// Hands the texts to [`StubTranslator`], remembering what it has been asked for
+    #[derive(Default)]
+    struct RecordingTranslator {
+        stub: StubTranslator,
+        asked: RefCell<Vec<String>>,
+    }
+
+    impl Translator for RecordingTranslator {
+        fn id(&self) -> String {
+            self.stub.id()
+        }
+
+        fn translate<'a>(
+            &'a self,
+            texts: &'a [String],
+            target: &'a str,
+        ) -> LocalBoxFuture<'a, Result<Vec<String>, TranslationError>> {
+            self.asked.borrow_mut().extend(texts.iter().cloned());
+            self.stub.translate(texts, target)
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to build runtime")
+            .block_on(future)
+    }
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "prag-portal-translation-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    /// Answers every request with what `answer` returns for its texts, like a LibreTranslate instance would,
+    /// remembering the request bodies
+    async fn serve(
+        answer: fn(&[String]) -> Vec<String>,
+    ) -> (String, Arc<Mutex<Vec<serde_json::Value>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("a local port is free");
+        let url = format!(
+            "http://{}",
+            listener.local_addr().expect("the listener is bound")
+        );
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buffer = [0; 1_024];
+                let body_start = loop {
+                    if let Some(x) = request.windows(4).position(|x| x == b"\r\n\r\n") {
+                        break x + 4;
+                    }
+                    match stream.read(&mut buffer).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => request.extend_from_slice(&buffer[..n]),
+                    }
+                };
+                let length = String::from_utf8_lossy(&request[..body_start])
+                    .lines()
+                    .find_map(|x| {
+                        x.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .and_then(|x| x.trim().parse::<usize>().ok())
+                    })
+                    .unwrap_or(0);
+                while request.len() < body_start + length {
+                    match stream.read(&mut buffer).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => request.extend_from_slice(&buffer[..n]),
+                    }
+                }
+                let body: serde_json::Value =
+                    serde_json::from_slice(&request[body_start..]).expect("the request is json");
+                let texts = body["q"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|x| x.as_str().map(ToOwned::to_owned))
+                    .collect::<Vec<_>>();
+                recorded.lock().expect("not poisoned").push(body);
+                let response = serde_json::json!({ "translatedText": answer(&texts) }).to_string();
+                let head = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    response.len(),
+                );
+                let _ = stream.write_all(head.as_bytes()).await;
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        (url, requests)
+    }
+
+    fn translate(
+        markdown: &str,
+        translator: &dyn Translator,
+        dir: &Path,
+    ) -> HashMap<String, String> {
+        block_on(translate_markdown_cached(markdown, translator, "zt", dir))
+            .expect("the stub translator never fails")
+    }
+
+    #[test]
+    fn stub_tags_the_target_language() {
+        let texts = vec![String::from("Hello"), String::from("World")];
+        let translated = block_on(StubTranslator.translate(&texts, "zt"));
+        assert_eq!(
+            translated.expect("the stub translator never fails"),
+            ["[zt] Hello", "[zt] World"]
+        );
+    }
+
+    #[test]
+    fn translates_prose_but_not_code_or_links() {
+        let markdown =
+            "A fast mod.\n\n```\nlet x = 1;\n```\n\nSee [the wiki](https://example.com) for more.\n";
+        let translations = translate(markdown, &StubTranslator, &cache_dir("prose"));
+        assert_eq!(
+            translations.get("A fast mod."),
+            Some(&String::from("[zt] A fast mod."))
+        );
+        assert_eq!(translations.get("See "), Some(&String::from("[zt] See ")));
+        assert!(translations
+            .keys()
+            .all(|x| !x.contains("let x") && !x.contains("the wiki")));
+    }
+
+    #[test]
+    fn joins_the_lines_of_a_paragraph() {
+        let translations = translate(
+            "first line\nsecond line",
+            &StubTranslator,
+            &cache_dir("lines"),
+        );
+        assert_eq!(
+            translations.keys().collect::<Vec<_>>(),
+            ["first line second line"]
+        );
+    }
+
+    #[test]
+    fn leaves_html_code_untouched() {
+        let translations = translate(
+            "<p>Hello <code>x</code> there</p>\n",
+            &StubTranslator,
+            &cache_dir("html"),
+        );
+        assert!(translations.contains_key("Hello "));
+        assert!(translations.contains_key(" there"));
+        assert!(!translations.contains_key("x"));
+    }
+
+    #[test]
+    fn leaves_inline_html_code_untouched() {
+        let translations = translate(
+            "Run <code>let x = 1;</code> first\n",
+            &StubTranslator,
+            &cache_dir("inline-html"),
+        );
+        assert!(translations.contains_key("Run "));
+        assert!(translations.contains_key(" first"));
+        assert!(translations.keys().all(|x| !x.contains("let x")));
+    }
+
+    #[test]
+    fn libretranslate_sends_the_texts_and_reads_the_translations() {
+        block_on(async {
+            let (url, requests) =
+                serve(|texts| texts.iter().map(|x| x.to_uppercase()).collect()).await;
+            let translator = LibreTranslate {
+                url: format!("{url}/"),
+                api_key: Some(String::from("key")),
+            };
+            let texts = vec![String::from("Hello"), String::from("World")];
+            let translated = translator
+                .translate(&texts, "zt")
+                .await
+                .expect("the local server answers");
+            assert_eq!(translated, ["HELLO", "WORLD"]);
+            assert_eq!(
+                *requests.lock().expect("not poisoned"),
+                [serde_json::json!({
+                    "q": ["Hello", "World"],
+                    "source": "auto",
+                    "target": "zt",
+                    "format": "text",
+                    "api_key": "key",
+                })]
+            );
+        });
+    }
+
+    #[test]
+    fn libretranslate_rejects_missing_translations() {
+        block_on(async {
+            let (url, _) = serve(|texts| texts.iter().take(1).cloned().collect()).await;
+            let translator = LibreTranslate { url, api_key: None };
+            let texts = vec![String::from("Hello"), String::from("World")];
+            let result = translator.translate(&texts, "zt").await;
+            assert!(matches!(
+                result,
+                Err(TranslationError::LengthMismatch {
+                    expected: 2,
+                    actual: 1
+                })
+            ));
+        });
+    }
+
+    #[test]
+    fn reuses_cached_translations() {
+        let dir = cache_dir("cache");
+        let first = RecordingTranslator::default();
+        let translated = translate("Hello there", &first, &dir);
+        assert_eq!(*first.asked.borrow(), ["Hello there"]);
+
+        let second = RecordingTranslator::default();
+        assert_eq!(translate("Hello there", &second, &dir), translated);
+        assert!(second.asked.borrow().is_empty());
+    }
+
+    #[test]
+    fn translation_is_off_until_configured() {
+        assert!(TranslationConfig::default().backend.translator().is_none());
+        let libre = |url: &str, api_key: Option<&str>| TranslationBackend::LibreTranslate {
+            url: url.to_owned(),
+            api_key: api_key.map(ToOwned::to_owned),
+        };
+        assert!(libre("https://libretranslate.com", None)
+            .translator()
+            .is_none());
+        assert!(libre("https://libretranslate.com", Some("key"))
+            .translator()
+            .is_some());
+        assert!(libre("http://localhost:5000", None).translator().is_some());
+    }
+}
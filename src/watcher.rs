@@ -0,0 +1,214 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+    time::Duration,
+};
+
+use dioxus::prelude::*;
+use dioxus_logger::tracing::{debug, warn};
+use notify::{RecursiveMode, Watcher};
+use rust_lib::api::shared_resources::collection::{use_keys, CollectionId};
+use strum::{EnumIter, IntoEnumIterator};
+use tokio::{sync::mpsc, time::Instant};
+
+use crate::{
+    notifications::{notify, NotificationKind},
+    t,
+    BaseComponents::organisms::toast::toast_error,
+};
+
+/// Editors and the game write files in several steps, events closer than this are handled together
+const DEBOUNCE: Duration = Duration::from_millis(300);
+/// A folder that keeps changing, e.g. while a world is being saved, is still reported this often
+const MAX_LATENCY: Duration = Duration::from_secs(2);
+
+/// How many times a folder of a collection has changed since the app started
+static FOLDER_CHANGES: GlobalSignal<HashMap<(CollectionId, WatchedFolder), u64>> =
+    Signal::global(HashMap::new);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter)]
+pub enum WatchedFolder {
+    Mods,
+    Screenshots,
+    Saves,
+    ResourcePacks,
+    ShaderPacks,
+    CrashReports,
+}
+
+impl WatchedFolder {
+    #[must_use]
+    pub const fn dir_name(self) -> &'static str {
+        match self {
+            Self::Mods => "mods",
+            Self::Screenshots => "screenshots",
+            Self::Saves => "saves",
+            Self::ResourcePacks => "resourcepacks",
+            Self::ShaderPacks => "shaderpacks",
+            Self::CrashReports => "crash-reports",
+        }
+    }
+
+    fn of(game_directory: &Path, path: &Path) -> Option<Self> {
+        Self::iter().find(|x| path.starts_with(game_directory.join(x.dir_name())))
+    }
+}
+
+/// Reads how many times `folder` of the collection has changed, subscribing to further changes.
+///
+/// Reading it in a [`use_resource`] or [`use_memo`] reruns them whenever the folder changes on disk.
+#[must_use]
+pub fn folder_changes(collection_id: CollectionId, folder: WatchedFolder) -> u64 {
+    FOLDER_CHANGES
+        .read()
+        .get(&(collection_id, folder))
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Whether a mod file is still on disk, in either its enabled or disabled form
+fn mod_file_exists(path: &Path) -> bool {
+    let path = path.to_string_lossy();
+    let enabled = path.strip_suffix(".disabled").unwrap_or(&path);
+    Path::new(enabled).exists() || Path::new(&format!("{enabled}.disabled")).exists()
+}
+
+/// Watches the folders of every collection
+#[component]
+pub fn FolderWatchers() -> Element {
+    let keys = use_keys();
+    rsx! {
+        for collection_id in keys {
+            FolderWatcher {
+                key: "{collection_id:?}",
+                collection_id,
+            }
+        }
+    }
+}
+
+#[component]
+fn FolderWatcher(collection_id: ReadOnlySignal<CollectionId>) -> Element {
+    let mut radio = collection_id().use_collection_radio();
+    let game_directory = use_memo(move || radio.read().game_directory().to_path_buf());
+    let mut task = use_signal(|| None::<Task>);
+
+    // spawned instead of a resource, so the radio reads in the loop don't restart the watcher
+    use_effect(move || {
+        let game_directory = game_directory();
+        let collection_id = collection_id.peek().to_owned();
+        if let Some(task) = task.take() {
+            task.cancel();
+        }
+        task.set(Some(spawn(async move {
+            let (sender, mut receiver) = mpsc::unbounded_channel();
+            let mut watcher = match notify::recommended_watcher(move |event| {
+                let _ = sender.send(event);
+            }) {
+                Ok(x) => x,
+                Err(err) => {
+                    toast_error(err);
+                    return;
+                }
+            };
+            // the folders are left to the game to create, the game directory is watched to pick them up
+            // once they appear
+            let mut watch = |path: &Path| {
+                if let Err(err) = watcher.watch(path, RecursiveMode::NonRecursive) {
+                    warn!("Failed to watch {}: {err}", path.display());
+                }
+            };
+            if game_directory.is_dir() {
+                watch(&game_directory);
+            }
+            for folder in WatchedFolder::iter() {
+                let path = game_directory.join(folder.dir_name());
+                if path.is_dir() {
+                    watch(&path);
+                }
+            }
+
+            // the watcher stops once it's dropped, it lives in `watch` as long as this future
+            while let Some(event) = receiver.recv().await {
+                let mut changed = BTreeSet::new();
+                let mut crashed = false;
+                let mut event = Some(event);
+                let deadline = Instant::now() + MAX_LATENCY;
+                while let Some(x) = event.take() {
+                    match x {
+                        Ok(x) => {
+                            let folders = x
+                                .paths
+                                .iter()
+                                .filter_map(|x| WatchedFolder::of(&game_directory, x))
+                                .collect::<Vec<_>>();
+                            // the game writes a new report into `crash-reports` every time it crashes
+                            crashed |= x.kind.is_create()
+                                && folders.contains(&WatchedFolder::CrashReports);
+                            if x.kind.is_create() {
+                                for path in x.paths.iter().filter(|x| {
+                                    x.is_dir()
+                                        && WatchedFolder::iter().any(|folder| {
+                                            **x == game_directory.join(folder.dir_name())
+                                        })
+                                }) {
+                                    watch(path);
+                                }
+                            }
+                            changed.extend(folders);
+                        }
+                        Err(err) => warn!("{err}"),
+                    }
+                    let wait = DEBOUNCE.min(deadline.saturating_duration_since(Instant::now()));
+                    if wait.is_zero() {
+                        break;
+                    }
+                    event = tokio::time::timeout(wait, receiver.recv())
+                        .await
+                        .ok()
+                        .flatten();
+                }
+                debug!("{} changed: {changed:?}", game_directory.display());
+
+                {
+                    let mut changes = FOLDER_CHANGES.write();
+                    for folder in &changed {
+                        *changes.entry((collection_id, *folder)).or_default() += 1;
+                    }
+                }
+
+                if crashed {
+                    notify(
+                        NotificationKind::GameCrashed,
+                        t!(
+                            "notification-game-crashed",
+                            name = radio.read().display_name().clone()
+                        ),
+                        t!("notification-game-crashed-body"),
+                    );
+                }
+
+                if changed.contains(&WatchedFolder::Mods) {
+                    let collection = radio.read_owned();
+                    let Some(mut controller) = collection.mod_controller.clone() else {
+                        continue;
+                    };
+                    let len = controller.manager.mods.len();
+                    controller.manager.mods.retain(|x| {
+                        x.get_filepaths()
+                            .into_iter()
+                            .flatten()
+                            .any(|x| mod_file_exists(&x))
+                    });
+                    if controller.manager.mods.len() != len {
+                        if let Err(err) = radio.with_mut(|x| x.mod_controller = Some(controller)) {
+                            toast_error(err);
+                        }
+                    }
+                }
+            }
+        })));
+    });
+
+    rsx! {}
+}
@@ -56,6 +56,7 @@ module.exports = {
     fontFamily: {
       'display': ['GenSenRounded TW'],
       'english': ['IBM Plex Mono'],
+      'mono': ['IBM Plex Mono', 'ui-monospace', 'monospace'],
     },
     fontMetrics: {
       'display': {
@@ -89,22 +90,24 @@ module.exports = {
         'slow': 'theme(animationTiming.slow)',
         'quick': 'theme(animationTiming.quick)',
       },
+      // driven by the `Theme` in src/theme.rs
       colors: {
-        'white': '#FFFFFF',
-        'secondary-surface': '#2F2F2F',
-        'surface': '#1E1E1E',
-        'hint': '#4F4F4F',
-        'secondary': '#7D7D7D',
-        'secondary-text': '#7D7D7D',
-        'deep-background': '#0E0E0E',
-        'background': '#191919',
-        'green': '#CDE347',
-        'red': '#C92B45',
-        'black': "#232E3D",
-        'light-blue': '#7CAED3',
-        'tinted-yellow': '#D3E950',
-        'purple': '#9747FF',
-        'orange': '#F16436',
+        'accent': 'rgb(var(--color-accent) / <alpha-value>)',
+        'white': 'rgb(var(--color-white) / <alpha-value>)',
+        'secondary-surface': 'rgb(var(--color-secondary-surface) / <alpha-value>)',
+        'surface': 'rgb(var(--color-surface) / <alpha-value>)',
+        'hint': 'rgb(var(--color-hint) / <alpha-value>)',
+        'secondary': 'rgb(var(--color-secondary) / <alpha-value>)',
+        'secondary-text': 'rgb(var(--color-secondary-text) / <alpha-value>)',
+        'deep-background': 'rgb(var(--color-deep-background) / <alpha-value>)',
+        'background': 'rgb(var(--color-background) / <alpha-value>)',
+        'green': 'rgb(var(--color-green) / <alpha-value>)',
+        'red': 'rgb(var(--color-red) / <alpha-value>)',
+        'black': 'rgb(var(--color-black) / <alpha-value>)',
+        'light-blue': 'rgb(var(--color-light-blue) / <alpha-value>)',
+        'tinted-yellow': 'rgb(var(--color-tinted-yellow) / <alpha-value>)',
+        'purple': 'rgb(var(--color-purple) / <alpha-value>)',
+        'orange': 'rgb(var(--color-orange) / <alpha-value>)',
         'modrinth': '#1BD96A',
       },
       animation: {